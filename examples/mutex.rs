@@ -1,127 +1,14 @@
-//! A simple mutex implementation.
+//! Sharing a counter with a mutex.
 //!
-//! This mutex exposes both blocking and async methods for acquiring a lock.
+//! The mutex exposes both blocking and async methods for acquiring a lock. It is built on top of
+//! `Event`, see `src/primitives/mutex.rs` for its implementation.
 
 #[cfg(not(target_family = "wasm"))]
 mod example {
-    #![allow(dead_code)]
-
-    use std::ops::{Deref, DerefMut};
     use std::sync::{mpsc, Arc};
     use std::thread;
-    use std::time::{Duration, Instant};
-
-    use event_listener::{listener, Event, Listener};
-    use try_lock::{Locked, TryLock};
-
-    /// A simple mutex.
-    struct Mutex<T> {
-        /// Blocked lock operations.
-        lock_ops: Event,
-
-        /// The inner non-blocking mutex.
-        data: TryLock<T>,
-    }
-
-    unsafe impl<T: Send> Send for Mutex<T> {}
-    unsafe impl<T: Send> Sync for Mutex<T> {}
-
-    impl<T> Mutex<T> {
-        /// Creates a mutex.
-        fn new(t: T) -> Mutex<T> {
-            Mutex {
-                lock_ops: Event::new(),
-                data: TryLock::new(t),
-            }
-        }
-
-        /// Attempts to acquire a lock.
-        fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
-            self.data.try_lock().map(MutexGuard)
-        }
-
-        /// Blocks until a lock is acquired.
-        fn lock(&self) -> MutexGuard<'_, T> {
-            loop {
-                // Attempt grabbing a lock.
-                if let Some(guard) = self.try_lock() {
-                    return guard;
-                }
-
-                // Set up an event listener.
-                listener!(self.lock_ops => listener);
-
-                // Try again.
-                if let Some(guard) = self.try_lock() {
-                    return guard;
-                }
-
-                // Wait for a notification.
-                listener.wait();
-            }
-        }
-
-        /// Blocks until a lock is acquired or the timeout is reached.
-        fn lock_timeout(&self, timeout: Duration) -> Option<MutexGuard<'_, T>> {
-            let deadline = Instant::now() + timeout;
-
-            loop {
-                // Attempt grabbing a lock.
-                if let Some(guard) = self.try_lock() {
-                    return Some(guard);
-                }
 
-                // Set up an event listener.
-                listener!(self.lock_ops => listener);
-
-                // Try again.
-                if let Some(guard) = self.try_lock() {
-                    return Some(guard);
-                }
-
-                // Wait until a notification is received.
-                listener.wait_deadline(deadline)?;
-            }
-        }
-
-        /// Acquires a lock asynchronously.
-        async fn lock_async(&self) -> MutexGuard<'_, T> {
-            loop {
-                // Attempt grabbing a lock.
-                if let Some(guard) = self.try_lock() {
-                    return guard;
-                }
-
-                // Set up an event listener.
-                listener!(self.lock_ops => listener);
-
-                // Try again.
-                if let Some(guard) = self.try_lock() {
-                    return guard;
-                }
-
-                // Wait until a notification is received.
-                listener.await;
-            }
-        }
-    }
-
-    /// A guard holding a lock.
-    struct MutexGuard<'a, T>(Locked<'a, T>);
-
-    impl<T> Deref for MutexGuard<'_, T> {
-        type Target = T;
-
-        fn deref(&self) -> &T {
-            &self.0
-        }
-    }
-
-    impl<T> DerefMut for MutexGuard<'_, T> {
-        fn deref_mut(&mut self) -> &mut T {
-            &mut self.0
-        }
-    }
+    use event_listener::primitives::Mutex;
 
     pub(super) fn entry() {
         const N: usize = 10;
@@ -133,12 +20,17 @@ mod example {
         let (tx, rx) = mpsc::channel();
 
         // Spawn a bunch of threads incrementing the counter.
-        for _ in 0..N {
+        for i in 0..N {
             let counter = counter.clone();
             let tx = tx.clone();
 
             thread::spawn(move || {
-                let mut counter = counter.lock();
+                // Lock the counter either by blocking, or asynchronously.
+                let mut counter = if i % 2 == 0 {
+                    counter.lock_blocking()
+                } else {
+                    futures_lite::future::block_on(counter.lock())
+                };
                 *counter += 1;
 
                 // If this is the last increment, signal that we're done.
@@ -152,7 +44,7 @@ mod example {
         rx.recv().unwrap();
 
         // The counter must equal the number of threads.
-        assert_eq!(*counter.lock(), N);
+        assert_eq!(*counter.lock_blocking(), N);
 
         println!("Done!");
     }
@@ -0,0 +1,229 @@
+//! Performing several notifications at once.
+
+use crate::notify::{self, Internal, Notification, NotificationPrivate};
+use crate::{Event, IntoNotification};
+
+use core::fmt;
+
+use std::boxed::Box;
+use std::vec::Vec;
+
+/// A guard that records notifications and performs them all at once.
+///
+/// This is created by [`Event::batch()`]. Notifications recorded with [`NotifyBatch::notify()`]
+/// are performed in order when the batch is flushed, either with [`NotifyBatch::flush()`] or
+/// when the guard is dropped. A flush emits a single `SeqCst` fence and takes the internal lock
+/// only once, no matter how many notifications were recorded.
+pub struct NotifyBatch<'a, T = ()> {
+    /// The event to notify.
+    event: &'a Event<T>,
+
+    /// The recorded notifications, in order.
+    pending: Vec<Boxed<'a, T>>,
+}
+
+impl<T> fmt::Debug for NotifyBatch<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotifyBatch")
+            .field("event", self.event)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl<T> Drop for NotifyBatch<'_, T> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<'a, T> NotifyBatch<'a, T> {
+    /// Records a notification to be performed when the batch is flushed.
+    ///
+    /// The notification behaves exactly as if it had been passed to [`Event::notify()`] at flush
+    /// time, right after the notifications recorded before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// let mut batch = event.batch();
+    /// batch.notify(1).notify(1.additional());
+    ///
+    /// // Nothing is notified until the batch is flushed.
+    /// assert!(!event.is_notified());
+    /// drop(batch);
+    /// assert!(event.is_notified());
+    /// ```
+    pub fn notify<N>(&mut self, notify: N) -> &mut Self
+    where
+        N: IntoNotification<Tag = T>,
+        N::Notify: 'a,
+    {
+        self.pending
+            .push(Boxed(Box::new(notify.into_notification())));
+        self
+    }
+
+    /// Returns the number of recorded notifications that have not been performed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let mut batch = event.batch();
+    /// batch.notify(1).notify(2);
+    /// assert_eq!(batch.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no notifications are waiting to be performed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert!(event.batch().is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Performs the recorded notifications and returns how many listeners they notified in total.
+    ///
+    /// The batch is empty afterwards and can be used to record more notifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// let mut batch = event.batch();
+    /// for _ in 0..2 {
+    ///     batch.notify(1.additional());
+    /// }
+    /// assert_eq!(batch.flush(), 2);
+    /// assert_eq!(batch.flush(), 0);
+    /// ```
+    pub fn flush(&mut self) -> usize {
+        if self.pending.is_empty() {
+            return 0;
+        }
+
+        // Make sure the notifications come after whatever triggered them.
+        notify::full_fence();
+
+        let forwarded: Vec<_> = self
+            .pending
+            .iter()
+            .map(|notify| {
+                (
+                    notify.count(Internal::new()),
+                    notify.is_additional(Internal::new()),
+                )
+            })
+            .collect();
+
+        let inner = unsafe { &*self.event.inner() };
+        inner.advance_epoch();
+        let count = inner.notify_batch(self.pending.drain(..));
+        inner.notify_parent();
+        for (count, additional) in forwarded {
+            inner.forwards.notify(count, additional);
+        }
+        count
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns a guard that records notifications and performs them all at once.
+    ///
+    /// Every [`Event::notify()`] call emits a fence and takes the internal lock. Code that
+    /// notifies many times in a row can record the notifications on a batch instead, which pays
+    /// these costs only once when it is flushed or dropped.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listeners: Vec<_> = (0..3).map(|_| event.listen()).collect();
+    ///
+    /// {
+    ///     let mut batch = event.batch();
+    ///     for _ in 0..3 {
+    ///         batch.notify(1.additional());
+    ///     }
+    /// }
+    ///
+    /// for listener in listeners {
+    ///     listener.wait();
+    /// }
+    /// ```
+    pub fn batch(&self) -> NotifyBatch<'_, T> {
+        NotifyBatch {
+            event: self,
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// A recorded notification.
+struct Boxed<'a, T>(Box<dyn Notification<Tag = T> + 'a>);
+
+impl<T> NotificationPrivate for Boxed<'_, T> {
+    type Tag = T;
+
+    fn fence(&self, i: Internal) {
+        self.0.fence(i);
+    }
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.0.is_additional(i)
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.0.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.0.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.0.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.0.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.0.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        self.0.next_tag(i)
+    }
+}
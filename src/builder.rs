@@ -0,0 +1,401 @@
+//! Configuring events before they are created.
+
+use crate::sync::atomic::AtomicPtr;
+use crate::sync::Arc;
+use crate::{Event, Inner};
+
+use core::fmt;
+
+/// The order in which listeners of an [`Event`] are notified.
+///
+/// This is set with [`EventBuilder::wake_order()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WakeOrder {
+    /// Notify the listener that started listening first, first.
+    ///
+    /// This is fair: every listener is notified eventually, as long as notifications keep
+    /// coming. This is the default.
+    Fifo,
+
+    /// Notify the listener that started listening last, first.
+    ///
+    /// This tends to give the best throughput for contended locks, since the most recently
+    /// parked thread is the most likely one to still have the relevant data in its cache. A
+    /// listener may starve as long as newer listeners keep arriving.
+    Lifo,
+
+    /// Notify the listeners in random order.
+    ///
+    /// This sits in between the other two: no listener is favored, but no listener is guaranteed
+    /// to be notified within a bounded number of notifications either. The randomness comes from
+    /// a simple pseudo-random generator and is not suitable for anything security-related.
+    Random,
+}
+
+impl Default for WakeOrder {
+    fn default() -> Self {
+        Self::Fifo
+    }
+}
+
+/// What happens when the backup queue of an [`Event`] is full.
+///
+/// This is set with [`EventBuilder::backup_queue()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QueueOverflow {
+    /// Wait for the list to be unlocked and apply the operation directly.
+    ///
+    /// This never allocates, but a thread may spin for as long as another thread holds the lock.
+    Spin,
+
+    /// Like [`QueueOverflow::Spin`], except that [`Event::try_listen()`] gives up with an
+    /// [`AllocError`] instead of waiting.
+    ///
+    /// Operations that can't fail, such as notifying or dropping a listener, still wait.
+    ///
+    /// [`AllocError`]: crate::AllocError
+    Fail,
+
+    /// Keep the operation in a second queue that grows as needed.
+    ///
+    /// This never waits, but allocates under heavy contention. The second queue is only
+    /// allocated once the first one overflows.
+    Allocate,
+}
+
+/// A builder for an [`Event`] with a non-default configuration.
+///
+/// This is created by [`Event::builder()`].
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, Listener, WakeOrder};
+///
+/// let event = Event::builder().wake_order(WakeOrder::Lifo).build();
+///
+/// let first = event.listen();
+/// let last = event.listen();
+///
+/// event.notify(1);
+/// last.wait();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventBuilder {
+    /// The order in which listeners are notified.
+    pub(crate) wake_order: WakeOrder,
+
+    /// The number of notifications kept for future listeners.
+    pub(crate) permit_capacity: usize,
+
+    /// The number of listeners to allocate room for up front.
+    pub(crate) capacity: usize,
+
+    /// The capacity of the backup queue and what to do once it is full, or `None` if it grows
+    /// as needed.
+    pub(crate) backup_queue: Option<(usize, QueueOverflow)>,
+
+    /// The number of rounds a blocking wait spins before parking.
+    pub(crate) spin_before_park: u32,
+
+    /// The number of listeners that bounded listening admits, or `None` if it is unbounded.
+    pub(crate) listen_bound: Option<usize>,
+}
+
+impl EventBuilder {
+    /// Creates a builder with the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventBuilder;
+    ///
+    /// let event = EventBuilder::new().build();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the order in which listeners are notified.
+    ///
+    /// This applies to every notification of the event, except [`Event::notify_last()`], which
+    /// always notifies the listener that started listening last first. Listeners that are
+    /// already notified are never passed over, no matter the order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, WakeOrder};
+    ///
+    /// let event = Event::builder().wake_order(WakeOrder::Random).build();
+    /// ```
+    pub fn wake_order(mut self, wake_order: WakeOrder) -> Self {
+        self.wake_order = wake_order;
+        self
+    }
+
+    /// Keeps up to `capacity` notifications around for future listeners.
+    ///
+    /// By default, a notification that finds nobody to notify is lost, unless it is
+    /// [`sticky`], in which case a single permit is kept. With a capacity, every notification
+    /// that can't be delivered is kept as a permit instead, up to `capacity` permits. Each new
+    /// listener takes one permit and is notified right away, with the tag of the notification
+    /// that left the permit behind.
+    ///
+    /// Permits count as notified listeners, so a notification that is not [`additional`] only
+    /// adds permits until there are as many notified listeners and permits together as it asks
+    /// for. Closing the event drops all permits.
+    ///
+    /// This replaces an external counter that a consumer would otherwise have to check before and
+    /// after starting to listen, so that no notification gets lost in between.
+    ///
+    /// [`sticky`]: crate::IntoNotification::sticky
+    /// [`additional`]: crate::IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::builder().permit_capacity(2).build();
+    ///
+    /// // Nobody is listening, so these are kept, up to the capacity.
+    /// event.notify(3.additional());
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// assert!(listener1.discard());
+    /// assert!(listener2.discard());
+    /// assert!(!listener3.discard());
+    /// ```
+    pub fn permit_capacity(mut self, capacity: usize) -> Self {
+        self.permit_capacity = capacity;
+        self
+    }
+
+    /// Allocates room for at least `capacity` listeners up front.
+    ///
+    /// Without the `std` feature, listeners are kept in a list that grows as needed. Allocating
+    /// it up front means no listener has to reallocate it as long as there are no more than
+    /// `capacity` listeners at a time, which keeps latency predictable and makes it possible to
+    /// do all allocation during startup. Use [`Event::shrink_to_fit()`] to give memory back after
+    /// a burst of listeners.
+    ///
+    /// With the `std` feature, every listener carries its own entry in the list, so this has no
+    /// effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::builder().capacity(16).build();
+    ///
+    /// let listener = event.listen();
+    /// event.notify(1);
+    /// listener.wait();
+    /// ```
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Bounds the backup queue to `capacity` operations.
+    ///
+    /// Without the `std` feature, an operation that finds the list of listeners locked by another
+    /// thread is put in a backup queue, which the thread holding the lock applies before
+    /// unlocking. By default, this queue grows as needed. With this option, it holds at most
+    /// `capacity` operations, allocated up front, and `overflow` decides what happens when it is
+    /// full. Waiting suits targets without much memory to spare, while allocating suits servers
+    /// where latency matters more.
+    ///
+    /// With the `std` feature, there is no backup queue, so this has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, QueueOverflow};
+    ///
+    /// let event = Event::builder()
+    ///     .backup_queue(8, QueueOverflow::Spin)
+    ///     .build();
+    ///
+    /// let listener = event.listen();
+    /// event.notify(1);
+    /// listener.wait();
+    /// ```
+    pub fn backup_queue(mut self, capacity: usize, overflow: QueueOverflow) -> Self {
+        assert!(
+            capacity > 0,
+            "the backup queue must have room for an operation"
+        );
+        self.backup_queue = Some((capacity, overflow));
+        self
+    }
+
+    /// Makes blocking waits spin for up to `rounds` rounds before parking the thread.
+    ///
+    /// Parking a thread and unparking it again takes a system call each. When notifications tend
+    /// to follow shortly after a listener starts waiting, such as with a lock that is held only
+    /// briefly, spinning first lets the waiting thread see the notification without either. Each
+    /// round checks for a notification. The first few rounds spin for twice as long as the one
+    /// before, and later rounds yield the thread to the scheduler instead. Spinning wastes CPU
+    /// time and power when the wait turns out to be long, so keep `rounds` low.
+    ///
+    /// By default, waits park right away. This only affects blocking waits, such as
+    /// [`Listener::wait()`], and it has no effect without the `std` feature.
+    ///
+    /// [`Listener::wait()`]: crate::Listener::wait
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::builder().spin_before_park(8).build();
+    ///
+    /// let listener = event.listen();
+    /// event.notify(1);
+    /// listener.wait();
+    /// ```
+    pub fn spin_before_park(mut self, rounds: u32) -> Self {
+        self.spin_before_park = rounds;
+        self
+    }
+
+    /// Sets the number of listeners that bounded listening admits to `max`.
+    ///
+    /// This bound is only checked by [`Event::try_listen_bounded()`] and
+    /// [`Event::listen_bounded()`]. Once the event has `max` listeners, the former fails with
+    /// [`TryListenError::Full`], and the latter waits until a listener goes away. This bounds the
+    /// memory used by services that let untrusted clients wait on an event, as long as they only
+    /// listen through these methods. The other ways of listening, such as [`Event::listen()`] and
+    /// [`listener!`], can't fail, so they don't check the bound, but their listeners count
+    /// towards it.
+    ///
+    /// [`listener!`]: crate::listener
+    ///
+    /// By default, the number of listeners is unbounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, TryListenError};
+    ///
+    /// let event = Event::builder().listen_bound(1).build();
+    ///
+    /// let listener = event.try_listen_bounded().unwrap();
+    /// assert_eq!(event.try_listen_bounded().unwrap_err(), TryListenError::Full);
+    ///
+    /// drop(listener);
+    /// assert!(event.try_listen_bounded().is_ok());
+    /// ```
+    ///
+    /// [`TryListenError::Full`]: crate::TryListenError::Full
+    pub fn listen_bound(mut self, max: usize) -> Self {
+        assert!(max > 0, "an event must admit at least one listener");
+        self.listen_bound = Some(max);
+        self
+    }
+
+    /// Creates an [`Event`] with this configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, WakeOrder};
+    ///
+    /// let event = Event::builder().wake_order(WakeOrder::Lifo).build();
+    /// event.notify(1);
+    /// ```
+    pub fn build(self) -> Event {
+        Event::from_inner(Inner::with_config(&self))
+    }
+
+    /// Creates an [`Event`] with this configuration that notifies listeners with tags.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, WakeOrder};
+    ///
+    /// let event = Event::builder()
+    ///     .wake_order(WakeOrder::Lifo)
+    ///     .build_with_tag::<usize>();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn build_with_tag<T>(self) -> Event<T> {
+        Event::from_inner(Inner::with_config(&self))
+    }
+}
+
+impl<T> Event<T> {
+    /// Creates an event that is initialized with the given state.
+    fn from_inner(inner: Inner<T>) -> Self {
+        Self {
+            inner: AtomicPtr::new(Arc::into_raw(Arc::new(inner)) as *mut Inner<T>),
+        }
+    }
+}
+
+impl Event {
+    /// Creates a builder for an [`Event`] with a non-default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, WakeOrder};
+    ///
+    /// let event = Event::builder().wake_order(WakeOrder::Lifo).build();
+    /// ```
+    pub fn builder() -> EventBuilder {
+        EventBuilder::new()
+    }
+}
+
+/// A small pseudo-random number generator for [`WakeOrder::Random`].
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator that is seeded on first use.
+    pub(crate) fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns a number in `0..n`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        if self.0 == 0 {
+            // Seed with our own address, so that different events use different sequences.
+            let addr = self as *const Self as usize as u64;
+            self.0 = (addr | 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        }
+
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let x = self.0.wrapping_mul(0x2545_F491_4F6C_DD1D);
+
+        ((x >> 32) as usize) % n
+    }
+}
+
+impl fmt::Debug for Rng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Rng { .. }")
+    }
+}
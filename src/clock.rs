@@ -0,0 +1,133 @@
+//! User-provided time sources for timed waits without `std`.
+
+use core::ptr;
+use core::task::{RawWaker, RawWakerVTable, Waker};
+
+/// A source of time for timed blocking waits.
+///
+/// [`Listener::wait_timeout()`] and [`Listener::wait_deadline()`] need the standard library's
+/// clock and thread parking. Implementing this trait, for example on top of a hardware timer,
+/// makes [`Listener::wait_deadline_with()`] and [`Listener::wait_timeout_with()`] available
+/// instead, including on `no_std` targets.
+///
+/// Since there is no thread to park, waiting with a [`Clock`] polls the listener in a loop and
+/// calls [`Clock::idle()`] between two polls.
+///
+/// [`Listener::wait_timeout()`]: crate::Listener::wait_timeout
+/// [`Listener::wait_deadline()`]: crate::Listener::wait_deadline
+/// [`Listener::wait_deadline_with()`]: crate::Listener::wait_deadline_with
+/// [`Listener::wait_timeout_with()`]: crate::Listener::wait_timeout_with
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicU64, Ordering};
+/// use event_listener::{Clock, Event, Listener, WaitTimeoutError};
+///
+/// /// A clock that ticks every time it is read, standing in for a hardware timer.
+/// struct Ticks(AtomicU64);
+///
+/// impl Clock for Ticks {
+///     type Instant = u64;
+///
+///     fn now(&self) -> u64 {
+///         self.0.fetch_add(1, Ordering::Relaxed)
+///     }
+/// }
+///
+/// let clock = Ticks(AtomicU64::new(0));
+/// let event = Event::new();
+///
+/// // There is no notification, so this times out after 10 ticks.
+/// let listener = event.listen();
+/// assert_eq!(listener.wait_timeout_with(&clock, 10), Err(WaitTimeoutError));
+///
+/// let listener = event.listen();
+/// event.notify(1);
+/// assert_eq!(listener.wait_timeout_with(&clock, 10), Ok(()));
+/// ```
+pub trait Clock {
+    /// A point in time.
+    type Instant: Ord;
+
+    /// Returns the current time.
+    fn now(&self) -> Self::Instant;
+
+    /// Pauses briefly before the listener is polled again.
+    ///
+    /// The default implementation emits a spin loop hint. Implementations can instead wait for
+    /// the next interrupt or put the core into a low-power state.
+    #[inline]
+    fn idle(&self) {
+        core::hint::spin_loop();
+    }
+}
+
+/// Waits for an event or interrupt with the `wfe` instruction.
+///
+/// This puts the core to sleep until an interrupt arrives or another core executes `sev`. Pass
+/// it to [`Listener::wait_with()`] to block on bare-metal Cortex-M targets. Listeners waiting
+/// this way execute `sev` when notified, so that a notification from another core wakes them up.
+///
+/// On other targets, this only emits a spin loop hint, so that code using it can be tested on
+/// the host.
+///
+/// This function is available with the `cortex-m` feature.
+///
+/// [`Listener::wait_with()`]: crate::Listener::wait_with
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, Listener};
+///
+/// let event = Event::new();
+/// let listener = event.listen();
+/// event.notify(1);
+///
+/// listener.wait_with(event_listener::wfe);
+/// ```
+#[cfg(feature = "cortex-m")]
+#[inline]
+pub fn wfe() {
+    #[cfg(target_arch = "arm")]
+    // SAFETY: `wfe` only waits for an event.
+    unsafe {
+        core::arch::asm!("wfe", options(nomem, nostack, preserves_flags));
+    }
+
+    #[cfg(not(target_arch = "arm"))]
+    core::hint::spin_loop();
+}
+
+/// Signals an event to every core with the `sev` instruction.
+#[cfg(all(feature = "cortex-m", target_arch = "arm"))]
+#[inline]
+fn sev() {
+    // SAFETY: `sev` only signals an event.
+    unsafe {
+        core::arch::asm!("sev", options(nomem, nostack, preserves_flags));
+    }
+}
+
+/// Returns the waker of listeners that are polled in a loop.
+///
+/// There is no task to wake, so this does nothing when woken, except for executing `sev` on
+/// Cortex-M to wake up a core sleeping in `wfe()`.
+pub(crate) fn idle_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, noop);
+
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    fn wake(_: *const ()) {
+        #[cfg(all(feature = "cortex-m", target_arch = "arm"))]
+        sev();
+    }
+
+    fn noop(_: *const ()) {}
+
+    // SAFETY: The vtable functions never touch the data pointer.
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
@@ -0,0 +1,428 @@
+//! An event with two classes of listeners.
+
+use crate::notify::{full_fence, Internal, NotificationPrivate};
+use crate::sync::atomic::{AtomicPtr, Ordering};
+use crate::sync::Arc;
+#[cfg(not(loom))]
+use crate::sync::WithMut;
+use crate::{Inner, InnerListener, IntoNotification};
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::pin::Pin;
+use core::ptr;
+
+use alloc::boxed::Box;
+
+/// An event whose listeners belong to one of two classes, A and B.
+///
+/// Each class has its own queue of listeners, and notifications target one class or the other.
+/// This is what reader-writer locks and similar primitives need: readers listen as class A and
+/// writers as class B, so that a release can wake up one writer, or all readers. Unlike two
+/// separate [`Event`]s, both queues live in a single allocation, made the first time the event
+/// is used.
+///
+/// [`Event`]: crate::Event
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{DualEvent, Listener};
+///
+/// let event = DualEvent::new();
+///
+/// let reader1 = event.listen_a();
+/// let reader2 = event.listen_a();
+/// let writer = event.listen_b();
+///
+/// // Wake up one writer, or all readers if there is none.
+/// assert_eq!(event.notify_b_or_a(1, usize::MAX), 1);
+/// writer.wait();
+///
+/// assert_eq!(event.notify_b_or_a(1, usize::MAX), 2);
+/// reader1.wait();
+/// reader2.wait();
+/// ```
+pub struct DualEvent<T = ()> {
+    /// A pointer to the queues, or null if they haven't been allocated yet.
+    inner: AtomicPtr<Dual<T>>,
+}
+
+unsafe impl<T: Send> Send for DualEvent<T> {}
+unsafe impl<T: Send> Sync for DualEvent<T> {}
+
+impl<T> core::panic::UnwindSafe for DualEvent<T> {}
+impl<T> core::panic::RefUnwindSafe for DualEvent<T> {}
+
+/// The two queues of a [`DualEvent`].
+struct Dual<T> {
+    /// The listeners of class A.
+    a: Inner<T>,
+
+    /// The listeners of class B.
+    b: Inner<T>,
+}
+
+/// The class of a [`DualListener`].
+#[derive(Clone, Copy)]
+enum Class {
+    A,
+    B,
+}
+
+/// A reference to one of the queues of a [`DualEvent`].
+pub(crate) struct ClassRef<T> {
+    dual: Arc<Dual<T>>,
+    class: Class,
+}
+
+impl<T> Deref for ClassRef<T> {
+    type Target = Inner<T>;
+
+    #[inline]
+    fn deref(&self) -> &Inner<T> {
+        match self.class {
+            Class::A => &self.dual.a,
+            Class::B => &self.dual.b,
+        }
+    }
+}
+
+impl<T> Borrow<Inner<T>> for ClassRef<T> {
+    #[inline]
+    fn borrow(&self) -> &Inner<T> {
+        self
+    }
+}
+
+impl<T> fmt::Debug for DualEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.try_inner() {
+            Some(dual) => f
+                .debug_struct("DualEvent")
+                .field("listeners_a", &dual.a.listeners.load(Ordering::Acquire))
+                .field("listeners_b", &dual.b.listeners.load(Ordering::Acquire))
+                .finish(),
+            None => f
+                .debug_tuple("DualEvent")
+                .field(&format_args!("<uninitialized>"))
+                .finish(),
+        }
+    }
+}
+
+impl Default for DualEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DualEvent {
+    /// Creates a new [`DualEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`DualEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+impl<T> DualEvent<T> {
+    /// Creates a new [`DualEvent`] with a tag type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::<usize>::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn with_tag() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`DualEvent`] with a tag type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::<usize>::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn with_tag() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Returns a guard listening for a notification of class A.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// let reader = event.listen_a();
+    /// ```
+    pub fn listen_a(&self) -> DualListener<T> {
+        self.listen(Class::A)
+    }
+
+    /// Returns a guard listening for a notification of class B.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// let writer = event.listen_b();
+    /// ```
+    pub fn listen_b(&self) -> DualListener<T> {
+        self.listen(Class::B)
+    }
+
+    /// Notifies a number of listeners of class A.
+    ///
+    /// This works like [`Event::notify()`] on the queue of class A, and returns the number of
+    /// listeners that were notified.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// let reader = event.listen_a();
+    /// let writer = event.listen_b();
+    ///
+    /// assert_eq!(event.notify_a(usize::MAX), 1);
+    /// ```
+    pub fn notify_a(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        Self::notify_class(unsafe { &(*self.inner()).a }, notify)
+    }
+
+    /// Notifies a number of listeners of class B.
+    ///
+    /// This works like [`Event::notify()`] on the queue of class B, and returns the number of
+    /// listeners that were notified.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{DualEvent, IntoNotification};
+    ///
+    /// let event = DualEvent::new();
+    /// let writer1 = event.listen_b();
+    /// let writer2 = event.listen_b();
+    ///
+    /// assert_eq!(event.notify_b(1.additional()), 1);
+    /// ```
+    pub fn notify_b(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        Self::notify_class(unsafe { &(*self.inner()).b }, notify)
+    }
+
+    /// Notifies listeners of class B if there are any, and listeners of class A otherwise.
+    ///
+    /// This is the usual way to release a reader-writer lock: wake up a writer if one is waiting,
+    /// and otherwise all readers. Returns the number of listeners that were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::DualEvent;
+    ///
+    /// let event = DualEvent::new();
+    /// let reader1 = event.listen_a();
+    /// let reader2 = event.listen_a();
+    ///
+    /// // There are no writers, so the readers are woken up.
+    /// assert_eq!(event.notify_b_or_a(1, usize::MAX), 2);
+    /// ```
+    pub fn notify_b_or_a(
+        &self,
+        b: impl IntoNotification<Tag = T>,
+        a: impl IntoNotification<Tag = T>,
+    ) -> usize {
+        let dual = unsafe { &*self.inner() };
+
+        // Make sure the listeners are counted after whatever triggered the notification.
+        full_fence();
+
+        if dual.b.listeners.load(Ordering::Acquire) > 0 {
+            Self::notify_class(&dual.b, b)
+        } else {
+            Self::notify_class(&dual.a, a)
+        }
+    }
+
+    /// Returns a listener of the given class.
+    fn listen(&self, class: Class) -> DualListener<T> {
+        let dual = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        let mut listener = Box::pin(InnerListener {
+            event: ClassRef {
+                dual: Arc::clone(&dual),
+                class,
+            },
+            listener: None,
+        });
+        listener.as_mut().listen();
+
+        DualListener { listener }
+    }
+
+    /// Notifies the listeners of one class.
+    fn notify_class(inner: &Inner<T>, notify: impl IntoNotification<Tag = T>) -> usize {
+        let mut notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(Internal::new());
+
+        notify.before_notify(Internal::new());
+        let count = inner.notify(&mut notify);
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Returns a reference to the queues, if they have been allocated.
+    #[inline]
+    fn try_inner(&self) -> Option<&Dual<T>> {
+        let inner = self.inner.load(Ordering::Acquire);
+        unsafe { inner.as_ref() }
+    }
+
+    /// Returns a raw, initialized pointer to the queues, allocating them on first use.
+    ///
+    /// This returns a raw pointer instead of reference because `from_raw` requires raw/mut
+    /// provenance.
+    fn inner(&self) -> *const Dual<T> {
+        let mut inner = self.inner.load(Ordering::Acquire);
+
+        // If this is the first use, initialize the state.
+        if inner.is_null() {
+            let new = Arc::into_raw(Arc::new(Dual {
+                a: Inner::<T>::new(),
+                b: Inner::<T>::new(),
+            })) as *mut Dual<T>;
+
+            // Replace the null pointer with the new state pointer.
+            inner = self
+                .inner
+                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
+                .unwrap_or_else(|x| x);
+
+            if inner.is_null() {
+                inner = new;
+            } else {
+                // A concurrent operation has initialized the state, deallocate ours.
+                unsafe {
+                    drop(Arc::from_raw(new));
+                }
+            }
+        }
+
+        inner
+    }
+}
+
+impl<T> Drop for DualEvent<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.with_mut(|&mut inner| {
+            // If the state pointer has been initialized, drop it.
+            if !inner.is_null() {
+                unsafe {
+                    drop(Arc::from_raw(inner));
+                }
+            }
+        })
+    }
+}
+
+/// A guard waiting for a notification of one class of a [`DualEvent`].
+///
+/// This is created by [`DualEvent::listen_a()`] and [`DualEvent::listen_b()`]. See the
+/// [`Listener`] trait for the functionality exposed by this type. Like an
+/// [`EventListener`], a listener that is dropped after being notified passes the notification
+/// on to another listener of the same class.
+///
+/// [`Listener`]: crate::Listener
+/// [`EventListener`]: crate::EventListener
+pub struct DualListener<T = ()> {
+    listener: Pin<Box<InnerListener<T, ClassRef<T>>>>,
+}
+
+unsafe impl<T: Send> Send for DualListener<T> {}
+unsafe impl<T: Send> Sync for DualListener<T> {}
+
+impl<T> core::panic::UnwindSafe for DualListener<T> {}
+impl<T> core::panic::RefUnwindSafe for DualListener<T> {}
+impl<T> Unpin for DualListener<T> {}
+
+impl<T> fmt::Debug for DualListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = match self.listener.event.class {
+            Class::A => "A",
+            Class::B => "B",
+        };
+
+        f.debug_struct("DualListener")
+            .field("class", &class)
+            .field("state", &format_args!("{}", self.listener.state_name()))
+            .finish()
+    }
+}
+
+impl<T> DualListener<T> {
+    #[inline]
+    pub(crate) fn listener(&self) -> &InnerListener<T, ClassRef<T>> {
+        &self.listener
+    }
+
+    #[inline]
+    pub(crate) fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, ClassRef<T>>> {
+        self.listener.as_mut()
+    }
+}
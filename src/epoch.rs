@@ -0,0 +1,296 @@
+//! Counting notifications, for waiting without missing one that happened in between.
+
+use crate::sync::atomic::Ordering;
+use crate::{listener, Event, Inner};
+
+use core::fmt;
+
+/// Set in [`Inner::epoch`] if the current epoch has been observed.
+///
+/// Adding one to an observed epoch clears the flag and advances the counter in the upper bits.
+const OBSERVED: usize = 1;
+
+/// A ticket for waiting on an [`Event`] with the eventcount protocol.
+///
+/// This is created by [`Event::prepare_wait()`], and consumed by [`Event::commit_wait()`] or
+/// [`Event::cancel_wait()`].
+#[must_use = "a prepared wait should be committed or cancelled"]
+pub struct WaitTicket {
+    /// The epoch of the event when the wait was prepared.
+    epoch: usize,
+}
+
+impl fmt::Debug for WaitTicket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitTicket")
+            .field("epoch", &self.epoch)
+            .finish()
+    }
+}
+
+impl<T> Inner<T> {
+    /// Starts a new epoch, unless nobody has observed the current one.
+    ///
+    /// This is called for every notification, after the fence that orders it after whatever
+    /// triggered it.
+    #[inline]
+    pub(crate) fn advance_epoch(&self) {
+        if self.epoch.load(Ordering::SeqCst) & OBSERVED != 0 {
+            self.epoch.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns the current epoch of the event.
+    ///
+    /// The epoch changes whenever the event is notified after this method was called. Comparing
+    /// a previously returned epoch with the current one tells whether a notification happened in
+    /// between, without registering a listener. This is the classic eventcount pattern: take the
+    /// epoch, check a condition, and only wait with [`Event::wait_epoch()`] if the condition
+    /// didn't hold. If it changes in between, the wait returns right away.
+    ///
+    /// Notifications only advance the epoch if it was observed since the last one, so events
+    /// whose epoch is never looked at don't pay for the bookkeeping. The counter wraps around.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// let epoch = event.epoch();
+    /// assert_eq!(event.epoch(), epoch);
+    ///
+    /// event.notify(1);
+    /// assert_ne!(event.epoch(), epoch);
+    /// ```
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        let inner = unsafe { &*self.inner() };
+        inner.epoch.fetch_or(OBSERVED, Ordering::SeqCst) >> 1
+    }
+
+    /// Waits for a notification, unless the event was notified since `epoch` was taken.
+    ///
+    /// The future resolves right away if the epoch of the event differs from `epoch`. Otherwise,
+    /// it waits for the next notification, like [`Event::listen()`] would. See [`Event::epoch()`]
+    /// for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let flag = AtomicBool::new(false);
+    /// let event = Event::new();
+    ///
+    /// let epoch = event.epoch();
+    /// if !flag.load(Ordering::SeqCst) {
+    ///     // The flag is set right after we checked it.
+    ///     flag.store(true, Ordering::SeqCst);
+    ///     event.notify(1);
+    ///
+    ///     // The notification isn't missed, even though nobody was listening.
+    ///     event.wait_epoch(epoch).await;
+    /// }
+    /// # });
+    /// ```
+    pub async fn wait_epoch(&self, epoch: usize) {
+        if self.epoch() != epoch {
+            return;
+        }
+
+        // Set up an event listener.
+        listener!(self => listener);
+
+        // Check again now that we're listening.
+        if self.epoch() != epoch {
+            return;
+        }
+
+        listener.await;
+    }
+
+    /// Blocks until a notification, unless the event was notified since `epoch` was taken.
+    ///
+    /// This is the blocking version of [`Event::wait_epoch()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::Event;
+    ///
+    /// let flag = Arc::new(AtomicBool::new(false));
+    /// let event = Arc::new(Event::new());
+    ///
+    /// thread::spawn({
+    ///     let flag = flag.clone();
+    ///     let event = event.clone();
+    ///     move || {
+    ///         flag.store(true, Ordering::SeqCst);
+    ///         event.notify(1);
+    ///     }
+    /// });
+    ///
+    /// loop {
+    ///     let epoch = event.epoch();
+    ///     if flag.load(Ordering::SeqCst) {
+    ///         break;
+    ///     }
+    ///     event.wait_epoch_blocking(epoch);
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_epoch_blocking(&self, epoch: usize) {
+        use crate::Listener;
+
+        if self.epoch() != epoch {
+            return;
+        }
+
+        // Set up an event listener.
+        listener!(self => listener);
+
+        // Check again now that we're listening.
+        if self.epoch() != epoch {
+            return;
+        }
+
+        listener.wait();
+    }
+
+    /// Prepares to wait for a notification, and returns a ticket for the wait.
+    ///
+    /// This is the first step of the classic eventcount protocol, an alternative to listeners
+    /// that doesn't allocate:
+    ///
+    /// 1. Call [`Event::prepare_wait()`].
+    /// 2. Check the condition you want to wait for.
+    /// 3. If it holds, give the ticket back with [`Event::cancel_wait()`]. Otherwise, wait with
+    ///    [`Event::commit_wait()`], which returns right away if the event was notified since the
+    ///    ticket was prepared.
+    ///
+    /// No listener is registered until the wait is committed, so the fast path where the
+    /// condition already holds costs a single atomic operation. See [`Event::epoch()`] for the
+    /// underlying counter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let items = AtomicUsize::new(0);
+    /// let event = Event::new();
+    ///
+    /// // A producer.
+    /// items.fetch_add(1, Ordering::SeqCst);
+    /// event.notify(1);
+    ///
+    /// // A consumer.
+    /// loop {
+    ///     let ticket = event.prepare_wait();
+    ///     if items.load(Ordering::SeqCst) > 0 {
+    ///         event.cancel_wait(ticket);
+    ///         break;
+    ///     }
+    ///     event.commit_wait(ticket).await;
+    /// }
+    /// # });
+    /// ```
+    #[inline]
+    pub fn prepare_wait(&self) -> WaitTicket {
+        WaitTicket {
+            epoch: self.epoch(),
+        }
+    }
+
+    /// Waits for a notification, unless the event was notified since the ticket was prepared.
+    ///
+    /// See [`Event::prepare_wait()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    ///
+    /// let ticket = event.prepare_wait();
+    /// event.notify(1);
+    ///
+    /// // Returns right away, since the event was notified in the meantime.
+    /// event.commit_wait(ticket).await;
+    /// # });
+    /// ```
+    pub async fn commit_wait(&self, ticket: WaitTicket) {
+        self.wait_epoch(ticket.epoch).await;
+    }
+
+    /// Blocks until a notification, unless the event was notified since the ticket was prepared.
+    ///
+    /// This is the blocking version of [`Event::commit_wait()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::Event;
+    ///
+    /// let ready = Arc::new(AtomicBool::new(false));
+    /// let event = Arc::new(Event::new());
+    ///
+    /// thread::spawn({
+    ///     let ready = ready.clone();
+    ///     let event = event.clone();
+    ///     move || {
+    ///         ready.store(true, Ordering::SeqCst);
+    ///         event.notify(1);
+    ///     }
+    /// });
+    ///
+    /// loop {
+    ///     let ticket = event.prepare_wait();
+    ///     if ready.load(Ordering::SeqCst) {
+    ///         event.cancel_wait(ticket);
+    ///         break;
+    ///     }
+    ///     event.commit_wait_blocking(ticket);
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn commit_wait_blocking(&self, ticket: WaitTicket) {
+        self.wait_epoch_blocking(ticket.epoch);
+    }
+
+    /// Gives up on a prepared wait.
+    ///
+    /// Since preparing a wait doesn't register anything, this only consumes the ticket. It marks
+    /// the end of the protocol in code that follows [`Event::prepare_wait()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// let ticket = event.prepare_wait();
+    /// event.cancel_wait(ticket);
+    /// ```
+    #[inline]
+    pub fn cancel_wait(&self, ticket: WaitTicket) {
+        drop(ticket);
+    }
+}
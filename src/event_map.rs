@@ -0,0 +1,220 @@
+//! A table of events, one per key.
+
+use crate::notify::{Internal, NotificationPrivate};
+use crate::{Event, EventListener, IntoNotification};
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::Hash;
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// A table of events that are created on demand, one per key.
+///
+/// This is the pattern behind "parking lot" style wait queues: instead of storing an [`Event`]
+/// in every object that can be waited on, objects share one table and are identified by a key,
+/// such as their address. The event for a key is created by the first [`EventMap::listen()`] on
+/// it, and removed again once it has no listeners left.
+///
+/// Empty events are removed whenever [`EventMap::notify()`] finds one, or all at once with
+/// [`EventMap::remove_idle()`].
+///
+/// This type is available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::EventMap;
+///
+/// let map = EventMap::<&str>::new();
+///
+/// let listener = map.listen("rx");
+/// assert_eq!(map.notify("tx", 1), 0);
+/// assert_eq!(map.notify("rx", 1), 1);
+///
+/// drop(listener);
+/// map.remove_idle();
+/// assert!(map.is_empty());
+/// ```
+pub struct EventMap<K, T = ()> {
+    /// The events, by key.
+    events: Mutex<HashMap<K, Event<T>>>,
+}
+
+impl<K, T> fmt::Debug for EventMap<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.events.try_lock() {
+            Ok(events) => f
+                .debug_struct("EventMap")
+                .field("keys", &events.len())
+                .finish(),
+            Err(_) => f
+                .debug_tuple("EventMap")
+                .field(&format_args!("<locked>"))
+                .finish(),
+        }
+    }
+}
+
+impl<K, T> Default for EventMap<K, T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            events: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash> EventMap<K> {
+    /// Creates an empty map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::<u64>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K: Eq + Hash, T> EventMap<K, T> {
+    /// Creates an empty map whose events carry tags of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::<u64, usize>::with_tag();
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn with_tag() -> Self {
+        Self::default()
+    }
+
+    /// Returns a guard listening for a notification on the event for `key`.
+    ///
+    /// The event is created if there is none for `key` yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::new();
+    /// let listener = map.listen(7);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map.notify(&7, 1), 1);
+    /// ```
+    pub fn listen(&self, key: K) -> EventListener<T> {
+        self.lock()
+            .entry(key)
+            .or_insert_with(Event::with_tag)
+            .listen()
+    }
+
+    /// Notifies a number of active listeners on the event for `key`.
+    ///
+    /// Returns the number of listeners that were notified, like [`Event::notify()`]. If there is
+    /// no event for `key`, nobody is listening and this returns `0`. If the event has no
+    /// listeners left, it is removed from the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::new();
+    /// let listener1 = map.listen(1);
+    /// let listener2 = map.listen(1);
+    ///
+    /// assert_eq!(map.notify(&1, 1), 1);
+    /// assert_eq!(map.notify(&2, 1), 0);
+    /// ```
+    pub fn notify<Q>(&self, key: &Q, notify: impl IntoNotification<Tag = T>) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let mut notify = notify.into_notification();
+        let mut events = self.lock();
+
+        match events.get(key) {
+            Some(event) if event.listener_count() > 0 => return event.notify(notify),
+
+            // Listeners only register with the map locked, so nobody can be listening anymore.
+            Some(_) => {
+                events.remove(key);
+            }
+            None => {}
+        }
+
+        // Nobody was notified, but the hooks still run.
+        notify.before_notify(Internal::new());
+        notify.after_notify(0, Internal::new());
+        0
+    }
+
+    /// Removes every event that has no listeners left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::new();
+    /// let listener1 = map.listen(1);
+    /// let listener2 = map.listen(2);
+    ///
+    /// drop(listener1);
+    /// map.remove_idle();
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn remove_idle(&self) {
+        self.lock().retain(|_, event| event.listener_count() > 0);
+    }
+
+    /// Returns the number of events in the map.
+    ///
+    /// This includes events that have no listeners left but have not been removed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::new();
+    /// let listener = map.listen("key");
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if there are no events in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventMap;
+    ///
+    /// let map = EventMap::<u8>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, Event<T>>> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
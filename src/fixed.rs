@@ -0,0 +1,729 @@
+//! A variant of [`Event`] that keeps its listeners in fixed-size inline storage.
+//!
+//! [`Event`]: crate::Event
+
+use crate::notify::{GenericNotify, Internal, NotificationPrivate};
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::{IntoNotification, NEVER_INSERTED_PANIC};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::ops;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+/// A synchronization primitive for notifying async tasks that never allocates.
+///
+/// This works like [`Event`], but the listeners live in an array of `N` slots stored inline, so
+/// neither creating the event nor listening to it touches the heap. Once `N` listeners are
+/// registered, [`FixedEvent::listen()`] returns an error until one of them is dropped.
+///
+/// The constructors are `const`, so a [`FixedEvent`] can be stored in a `static` on targets that
+/// have no allocator at all. Listeners borrow the event instead of sharing ownership of it.
+///
+/// The list is protected by a spin lock that is only held for a bounded amount of work, and
+/// tasks are woken after it has been released. There are no blocking methods.
+///
+/// [`Event`]: crate::Event
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::FixedEvent;
+/// # futures_lite::future::block_on(async {
+///
+/// static EVENT: FixedEvent<4> = FixedEvent::new();
+///
+/// let listener = EVENT.listen().unwrap();
+/// EVENT.notify(1);
+/// listener.await;
+/// # });
+/// ```
+pub struct FixedEvent<const N: usize, T = ()> {
+    /// Whether the list is locked.
+    locked: AtomicBool,
+
+    /// The list of listeners.
+    list: UnsafeCell<List<N, T>>,
+}
+
+unsafe impl<const N: usize, T: Send> Send for FixedEvent<N, T> {}
+unsafe impl<const N: usize, T: Send> Sync for FixedEvent<N, T> {}
+
+impl<const N: usize, T> core::panic::UnwindSafe for FixedEvent<N, T> {}
+impl<const N: usize, T> core::panic::RefUnwindSafe for FixedEvent<N, T> {}
+
+impl<const N: usize, T> fmt::Debug for FixedEvent<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = self.lock();
+        f.debug_struct("FixedEvent")
+            .field("capacity", &N)
+            .field("listeners_notified", &list.notified)
+            .field("listeners_total", &list.len)
+            .finish()
+    }
+}
+
+impl<const N: usize> Default for FixedEvent<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FixedEvent<N> {
+    /// Creates a new [`FixedEvent`] with room for `N` listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self::with_tag()
+    }
+
+    /// Creates a new [`FixedEvent`] with room for `N` listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self::with_tag()
+    }
+}
+
+impl<const N: usize, T> FixedEvent<N, T> {
+    /// Creates a new [`FixedEvent`] with a tag type and room for `N` listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4, usize>::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn with_tag() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            list: UnsafeCell::new(List::new()),
+        }
+    }
+
+    /// Creates a new [`FixedEvent`] with a tag type and room for `N` listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4, usize>::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn with_tag() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            list: UnsafeCell::new(List::new()),
+        }
+    }
+
+    /// Returns the number of listeners this event has room for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// assert_eq!(event.capacity(), 4);
+    /// ```
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns a guard listening for a notification, or an error if all `N` slots are in use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{CapacityError, FixedEvent};
+    ///
+    /// let event = FixedEvent::<1>::new();
+    ///
+    /// let listener = event.listen().unwrap();
+    /// assert_eq!(event.listen().unwrap_err(), CapacityError);
+    ///
+    /// // Dropping a listener frees its slot.
+    /// drop(listener);
+    /// assert!(event.listen().is_ok());
+    /// ```
+    pub fn listen(&self) -> Result<FixedListener<'_, N, T>, CapacityError> {
+        let key = self.lock().insert()?;
+
+        Ok(FixedListener {
+            event: self,
+            key: Some(key),
+        })
+    }
+
+    /// Notifies a number of active listeners.
+    ///
+    /// This works like [`Event::notify()`] and accepts the same kinds of notifications.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{FixedEvent, IntoNotification};
+    ///
+    /// let event = FixedEvent::<4>::new();
+    ///
+    /// let listener1 = event.listen().unwrap();
+    /// let listener2 = event.listen().unwrap();
+    ///
+    /// assert_eq!(event.notify(1), 1);
+    /// assert_eq!(event.notify(1), 0);
+    /// assert_eq!(event.notify(1.additional()), 1);
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let mut notify = notify.into_notification();
+        notify.fence(Internal::new());
+        notify.before_notify(Internal::new());
+
+        let mut wakers = Wakers::<N>::new();
+        let count = self.lock().notify(&mut notify, &mut wakers);
+        wakers.wake();
+
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Tell whether any listeners are currently notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// let listener = event.listen().unwrap();
+    /// assert!(!event.is_notified());
+    ///
+    /// event.notify(1);
+    /// assert!(event.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.lock().notified > 0
+    }
+
+    /// Returns the number of listeners currently registered to this [`FixedEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// let listener = event.listen().unwrap();
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.lock().len
+    }
+
+    /// Locks the list.
+    fn lock(&self) -> ListGuard<'_, N, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            crate::sync::spin_loop();
+        }
+
+        ListGuard { event: self }
+    }
+
+    /// Removes a listener and wakes the listener its notification was propagated to.
+    fn remove(&self, key: usize, propagate: bool) -> State<T> {
+        let mut wakers = Wakers::<N>::new();
+        let state = self.lock().remove(key, propagate, &mut wakers);
+        wakers.wake();
+
+        state
+    }
+}
+
+/// A guard waiting for a notification from a [`FixedEvent`].
+///
+/// If a notified listener is dropped without receiving a notification, dropping will notify
+/// another active listener.
+pub struct FixedListener<'a, const N: usize, T = ()> {
+    /// The event this listener belongs to.
+    event: &'a FixedEvent<N, T>,
+
+    /// The key of our slot, or `None` once the notification has been received.
+    key: Option<usize>,
+}
+
+impl<const N: usize, T> fmt::Debug for FixedListener<'_, N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedListener")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize, T> FixedListener<'_, N, T> {
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// let listener1 = event.listen().unwrap();
+    /// let listener2 = event.listen().unwrap();
+    ///
+    /// event.notify(1);
+    ///
+    /// assert!(listener1.discard());
+    /// assert!(!listener2.discard());
+    /// ```
+    pub fn discard(mut self) -> bool {
+        match self.key.take() {
+            Some(key) => self.event.remove(key, false).is_notified(),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if this listener listens to the given [`FixedEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::FixedEvent;
+    ///
+    /// let event = FixedEvent::<4>::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// assert!(listener.listens_to(&event));
+    /// assert!(!listener.listens_to(&FixedEvent::new()));
+    /// ```
+    #[inline]
+    pub fn listens_to(&self, event: &FixedEvent<N, T>) -> bool {
+        core::ptr::eq(self.event, event)
+    }
+}
+
+impl<const N: usize, T> Future for FixedListener<'_, N, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let key = self.key.expect(NEVER_INSERTED_PANIC);
+        let mut list = self.event.lock();
+
+        match mem::replace(list.state_mut(key), State::Created) {
+            State::Notified { tag, .. } => {
+                // We have been notified, remove the listener.
+                let mut wakers = Wakers::<N>::new();
+                list.remove(key, false, &mut wakers);
+                drop(list);
+                self.key = None;
+                Poll::Ready(tag)
+            }
+
+            State::Waiting(waker) if waker.will_wake(cx.waker()) => {
+                *list.state_mut(key) = State::Waiting(waker);
+                Poll::Pending
+            }
+
+            _ => {
+                *list.state_mut(key) = State::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<const N: usize, T> Drop for FixedListener<'_, N, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.event.remove(key, true);
+        }
+    }
+}
+
+/// An error returned by [`FixedEvent::listen()`] when every slot is already in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no room for another listener")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// A locked [`FixedEvent`].
+struct ListGuard<'a, const N: usize, T> {
+    event: &'a FixedEvent<N, T>,
+}
+
+impl<const N: usize, T> ops::Deref for ListGuard<'_, N, T> {
+    type Target = List<N, T>;
+
+    fn deref(&self) -> &List<N, T> {
+        // SAFETY: We hold the lock.
+        unsafe { &*self.event.list.get() }
+    }
+}
+
+impl<const N: usize, T> ops::DerefMut for ListGuard<'_, N, T> {
+    fn deref_mut(&mut self) -> &mut List<N, T> {
+        // SAFETY: We hold the lock.
+        unsafe { &mut *self.event.list.get() }
+    }
+}
+
+impl<const N: usize, T> Drop for ListGuard<'_, N, T> {
+    fn drop(&mut self) {
+        self.event.locked.store(false, Ordering::Release);
+    }
+}
+
+/// Wakers collected while the list is locked, to be woken once it is released.
+struct Wakers<const N: usize> {
+    wakers: [Option<Waker>; N],
+    len: usize,
+}
+
+impl<const N: usize> Wakers<N> {
+    const NONE: Option<Waker> = None;
+
+    fn new() -> Self {
+        Self {
+            wakers: [Self::NONE; N],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, waker: Waker) {
+        // There is never more than one waker per slot.
+        self.wakers[self.len] = Some(waker);
+        self.len += 1;
+    }
+
+    fn wake(self) {
+        for waker in self.wakers.into_iter().flatten() {
+            waker.wake();
+        }
+    }
+}
+
+/// The state of a listener.
+enum State<T> {
+    /// The listener was just created.
+    Created,
+
+    /// The listener has received a notification.
+    Notified {
+        /// Whether or not this is an "additional" notification.
+        additional: bool,
+
+        /// The tag associated with the notification.
+        tag: T,
+    },
+
+    /// A task is waiting for a notification.
+    Waiting(Waker),
+}
+
+impl<T> State<T> {
+    fn is_notified(&self) -> bool {
+        matches!(self, Self::Notified { .. })
+    }
+}
+
+/// A slot in the list.
+enum Slot<T> {
+    /// A registered listener.
+    Occupied {
+        /// The state of the listener.
+        state: State<T>,
+
+        /// The previous listener in the list.
+        prev: Option<usize>,
+
+        /// The next listener in the list.
+        next: Option<usize>,
+    },
+
+    /// An empty slot that contains the index of the next empty slot.
+    Vacant(Option<usize>),
+}
+
+impl<T> Slot<T> {
+    const VACANT: Self = Slot::Vacant(None);
+}
+
+/// A linked list of listeners, stored in an array.
+struct List<const N: usize, T> {
+    /// The raw list of slots.
+    slots: [Slot<T>; N],
+
+    /// The number of slots that have ever been used.
+    ///
+    /// Slots past this index are vacant without being part of the vacant list.
+    used: usize,
+
+    /// First entry in the list.
+    head: Option<usize>,
+
+    /// Last entry in the list.
+    tail: Option<usize>,
+
+    /// The first unnotified entry in the list.
+    start: Option<usize>,
+
+    /// The first vacant slot.
+    first_vacant: Option<usize>,
+
+    /// The total number of listeners.
+    len: usize,
+
+    /// The number of notified listeners.
+    notified: usize,
+
+    /// A permit stored by a sticky notification, waiting for the next listener.
+    sticky: Option<T>,
+}
+
+impl<const N: usize, T> List<N, T> {
+    #[cfg(not(loom))]
+    const fn new() -> Self {
+        Self {
+            slots: [Slot::VACANT; N],
+            used: 0,
+            head: None,
+            tail: None,
+            start: None,
+            first_vacant: None,
+            len: 0,
+            notified: 0,
+            sticky: None,
+        }
+    }
+
+    #[cfg(loom)]
+    fn new() -> Self {
+        Self {
+            slots: [Slot::VACANT; N],
+            used: 0,
+            head: None,
+            tail: None,
+            start: None,
+            first_vacant: None,
+            len: 0,
+            notified: 0,
+            sticky: None,
+        }
+    }
+
+    fn state_mut(&mut self, key: usize) -> &mut State<T> {
+        match &mut self.slots[key] {
+            Slot::Occupied { state, .. } => state,
+            Slot::Vacant(_) => unreachable!(),
+        }
+    }
+
+    fn links_mut(&mut self, key: usize) -> (&mut Option<usize>, &mut Option<usize>) {
+        match &mut self.slots[key] {
+            Slot::Occupied { prev, next, .. } => (prev, next),
+            Slot::Vacant(_) => unreachable!(),
+        }
+    }
+
+    /// Inserts a new entry at the end of the list.
+    fn insert(&mut self) -> Result<usize, CapacityError> {
+        // Find a vacant slot before touching anything else.
+        let key = match self.first_vacant {
+            Some(key) => key,
+            None if self.used < N => self.used,
+            None => return Err(CapacityError),
+        };
+
+        // If a sticky notification left a permit behind, this listener takes it.
+        let state = match self.sticky.take() {
+            Some(tag) => State::Notified {
+                additional: false,
+                tag,
+            },
+            None => State::Created,
+        };
+        let notified = state.is_notified();
+
+        let slot = Slot::Occupied {
+            state,
+            prev: self.tail,
+            next: None,
+        };
+
+        match mem::replace(&mut self.slots[key], slot) {
+            Slot::Vacant(next) if self.first_vacant == Some(key) => self.first_vacant = next,
+            Slot::Vacant(_) => self.used += 1,
+            Slot::Occupied { .. } => unreachable!(),
+        }
+
+        // Replace the tail with the new entry.
+        match self.tail.replace(key) {
+            None => self.head = Some(key),
+            Some(tail) => *self.links_mut(tail).1 = Some(key),
+        }
+
+        if notified {
+            self.notified += 1;
+        } else if self.start.is_none() {
+            self.start = Some(key);
+        }
+
+        self.len += 1;
+        Ok(key)
+    }
+
+    /// Removes an entry from the list and returns its state.
+    fn remove(&mut self, key: usize, propagate: bool, wakers: &mut Wakers<N>) -> State<T> {
+        let slot = mem::replace(&mut self.slots[key], Slot::Vacant(self.first_vacant));
+        self.first_vacant = Some(key);
+
+        let (state, prev, next) = match slot {
+            Slot::Occupied { state, prev, next } => (state, prev, next),
+            Slot::Vacant(_) => unreachable!(),
+        };
+
+        // Unlink from the previous entry.
+        match prev {
+            None => self.head = next,
+            Some(p) => *self.links_mut(p).1 = next,
+        }
+
+        // Unlink from the next entry.
+        match next {
+            None => self.tail = prev,
+            Some(n) => *self.links_mut(n).0 = prev,
+        }
+
+        // If this was the first unnotified entry, move the pointer to the next one.
+        if self.start == Some(key) {
+            self.start = next;
+        }
+
+        self.len -= 1;
+
+        match state {
+            State::Notified { additional, tag } if propagate => {
+                self.notified -= 1;
+
+                // Propagate the notification to the next entry.
+                let mut tag = Some(tag);
+                self.notify(
+                    GenericNotify::new(1, additional, move || {
+                        tag.take().expect("tag already taken")
+                    }),
+                    wakers,
+                );
+
+                State::Created
+            }
+
+            state => {
+                if state.is_notified() {
+                    self.notified -= 1;
+                }
+
+                state
+            }
+        }
+    }
+
+    /// Notifies a number of listeners.
+    fn notify(
+        &mut self,
+        mut notify: impl NotificationPrivate<Tag = T>,
+        wakers: &mut Wakers<N>,
+    ) -> usize {
+        let mut n = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if self.start.is_none() {
+            // Nobody is waiting for a notification, so store a permit if requested.
+            if n > 0 && self.sticky.is_none() && notify.is_sticky(Internal::new()) {
+                self.sticky = Some(notify.next_tag(Internal::new()));
+            }
+
+            return 0;
+        }
+
+        if !is_additional {
+            if n <= self.notified {
+                return 0;
+            }
+            n -= self.notified;
+        }
+
+        let mut count = 0;
+        while count < n {
+            let key = match self.start {
+                Some(key) => key,
+                None => break,
+            };
+
+            // Move the pointer forwards.
+            self.start = *self.links_mut(key).1;
+
+            // Set the state to `Notified` and queue the wakeup.
+            let tag = notify.next_tag(Internal::new());
+            let old = mem::replace(
+                self.state_mut(key),
+                State::Notified {
+                    additional: is_additional,
+                    tag,
+                },
+            );
+            if let State::Waiting(waker) = old {
+                wakers.push(waker);
+            }
+
+            self.notified += 1;
+            count += 1;
+        }
+
+        count
+    }
+}
@@ -0,0 +1,160 @@
+//! Forwarding notifications from one event to another.
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Mutex;
+use crate::{Event, IntoNotification};
+
+use core::fmt;
+
+use std::boxed::Box;
+use std::vec::Vec;
+
+/// Passes a notification with the given count and strategy on to a target.
+pub(crate) type Target = Box<dyn Fn(usize, bool) + Send + Sync>;
+
+/// The events that notifications of an [`Event`] are forwarded to.
+pub(crate) struct Forwards {
+    /// The number of targets, so that notifying doesn't take the lock when there are none.
+    len: AtomicUsize,
+
+    /// The targets, along with the ID of the [`Forward`] guard that added them.
+    targets: Mutex<Targets>,
+}
+
+/// The targets of a [`Forwards`] list.
+struct Targets {
+    /// The ID of the next target.
+    next_id: usize,
+
+    /// The targets, in the order they were added.
+    events: Vec<(usize, Target)>,
+}
+
+impl Forwards {
+    /// Creates an empty list of targets.
+    pub(crate) fn new() -> Self {
+        Self {
+            len: AtomicUsize::new(0),
+            targets: Mutex::new(Targets {
+                next_id: 0,
+                events: Vec::new(),
+            }),
+        }
+    }
+
+    /// Adds a target and returns its ID.
+    fn insert(&self, target: Target) -> usize {
+        let mut targets = self.targets.lock().unwrap_or_else(|e| e.into_inner());
+        let id = targets.next_id;
+        targets.next_id = targets.next_id.wrapping_add(1);
+        targets.events.push((id, target));
+        self.len.store(targets.events.len(), Ordering::SeqCst);
+        id
+    }
+
+    /// Removes the target with this ID.
+    fn remove(&self, id: usize) {
+        let mut targets = self.targets.lock().unwrap_or_else(|e| e.into_inner());
+        targets.events.retain(|(target, _)| *target != id);
+        self.len.store(targets.events.len(), Ordering::SeqCst);
+    }
+
+    /// Forwards a notification to every target.
+    #[inline]
+    pub(crate) fn notify(&self, count: usize, additional: bool) {
+        if self.len.load(Ordering::Acquire) == 0 {
+            return;
+        }
+
+        let targets = self.targets.lock().unwrap_or_else(|e| e.into_inner());
+        for (_, target) in &targets.events {
+            target(count, additional);
+        }
+    }
+}
+
+/// A guard that forwards the notifications of one event to another.
+///
+/// This is created by [`Event::forward_to()`]. Notifications stop being forwarded once the guard
+/// is dropped.
+#[must_use = "notifications are only forwarded while the guard is alive"]
+pub struct Forward<T = ()> {
+    /// The event whose notifications are forwarded.
+    source: Event<T>,
+
+    /// The ID of the target in the source's list.
+    id: usize,
+}
+
+impl<T> fmt::Debug for Forward<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Forward")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for Forward<T> {
+    fn drop(&mut self) {
+        // SAFETY: `source` is initialized by `Event::clone()`.
+        let inner = unsafe { &*self.source.inner() };
+        inner.forwards.remove(self.id);
+    }
+}
+
+impl<T> Event<T> {
+    /// Forwards the notifications of this event to `target` until the returned guard is dropped.
+    ///
+    /// Every [`Event::notify()`] and [`Event::notify_count()`] on this event is repeated on
+    /// `target` with the same count, and as an [`additional`] notification if the original was
+    /// one. Tags are not forwarded. This bridges an internal event to an externally visible one
+    /// without spawning a task that just relays wakeups.
+    ///
+    /// Notifications must not be forwarded in a cycle, since notifying any event in it would then
+    /// never return.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// [`additional`]: IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let internal = Event::new();
+    /// let external = Event::new();
+    ///
+    /// let forward = internal.forward_to(&external);
+    /// let listener = external.listen();
+    /// internal.notify(1);
+    /// assert!(external.is_notified());
+    ///
+    /// drop(forward);
+    /// drop(listener);
+    /// let listener = external.listen();
+    /// internal.notify(1);
+    /// assert!(!external.is_notified());
+    /// ```
+    pub fn forward_to(&self, target: &Event) -> Forward<T> {
+        let target = target.clone();
+        self.forward_with(Box::new(move |count, additional| {
+            if additional {
+                target.notify(count.additional());
+            } else {
+                target.notify(count);
+            }
+        }))
+    }
+
+    /// Passes the notifications of this event on to `target` until the returned guard is dropped.
+    pub(crate) fn forward_with(&self, target: Target) -> Forward<T> {
+        let source = self.clone();
+
+        // SAFETY: `source` is initialized by `Event::clone()`.
+        let inner = unsafe { &*source.inner() };
+        let id = inner.forwards.insert(target);
+
+        Forward { source, id }
+    }
+}
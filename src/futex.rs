@@ -0,0 +1,142 @@
+//! A thread parker built directly on Linux futexes.
+//!
+//! This is a drop-in replacement for the parts of the `parking` crate that this crate uses. A
+//! parker is a single atomic word that the parked thread waits on with `FUTEX_WAIT`.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Nobody is parked and no notification is pending.
+const EMPTY: u32 = 0;
+
+/// A notification is pending.
+const NOTIFIED: u32 = 1;
+
+/// The thread is parked, or about to be.
+const PARKED: u32 = u32::MAX;
+
+/// Creates a parker and an unparker for it.
+pub(crate) fn pair() -> (Parker, Unparker) {
+    let parker = Parker::new();
+    let unparker = parker.unparker();
+    (parker, unparker)
+}
+
+/// Parks a thread until it is unparked by its [`Unparker`].
+#[derive(Debug)]
+pub(crate) struct Parker {
+    state: Arc<AtomicU32>,
+}
+
+impl Parker {
+    /// Creates a new parker.
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(AtomicU32::new(EMPTY)),
+        }
+    }
+
+    /// Returns an unparker for this parker.
+    pub(crate) fn unparker(&self) -> Unparker {
+        Unparker {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Blocks until a notification is received.
+    pub(crate) fn park(&self) {
+        // Consume a pending notification, or mark ourselves as parked.
+        if self.state.fetch_sub(1, Ordering::Acquire) == NOTIFIED {
+            return;
+        }
+
+        loop {
+            futex_wait(&self.state, PARKED, None);
+
+            // Wakeups may be spurious, so only return once we've been notified.
+            if self
+                .state
+                .compare_exchange(NOTIFIED, EMPTY, Ordering::Acquire, Ordering::Acquire)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Blocks until a notification is received or the deadline is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    pub(crate) fn park_deadline(&self, deadline: Instant) -> bool {
+        // Consume a pending notification, or mark ourselves as parked.
+        if self.state.fetch_sub(1, Ordering::Acquire) == NOTIFIED {
+            return true;
+        }
+
+        if let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            futex_wait(&self.state, PARKED, Some(timeout));
+        }
+
+        // The wakeup may have been spurious or a timeout, so check whether we were notified.
+        self.state.swap(EMPTY, Ordering::Acquire) == NOTIFIED
+    }
+}
+
+/// Unparks the thread of a [`Parker`].
+#[derive(Debug, Clone)]
+pub(crate) struct Unparker {
+    state: Arc<AtomicU32>,
+}
+
+impl Unparker {
+    /// Notifies the parker.
+    ///
+    /// Returns `true` if this call delivered a new notification.
+    pub(crate) fn unpark(&self) -> bool {
+        match self.state.swap(NOTIFIED, Ordering::Release) {
+            PARKED => {
+                futex_wake(&self.state);
+                true
+            }
+            EMPTY => true,
+            _ => false,
+        }
+    }
+}
+
+/// Waits on the futex as long as it holds `expected`, or until the timeout elapses.
+fn futex_wait(futex: &AtomicU32, expected: u32, timeout: Option<Duration>) {
+    let timespec = timeout.map(|timeout| libc::timespec {
+        tv_sec: timeout.as_secs().try_into().unwrap_or(libc::time_t::MAX),
+        tv_nsec: timeout.subsec_nanos() as _,
+    });
+    let timespec = timespec
+        .as_ref()
+        .map_or(core::ptr::null(), |t| t as *const libc::timespec);
+
+    // SAFETY: The futex is a valid `u32` for the duration of the call. Errors such as `EAGAIN`,
+    // `EINTR` and `ETIMEDOUT` are handled by the caller re-checking the state.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex as *const AtomicU32,
+            libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+            expected,
+            timespec,
+        );
+    }
+}
+
+/// Wakes a thread waiting on the futex.
+fn futex_wake(futex: &AtomicU32) {
+    // SAFETY: The futex is a valid `u32` for the duration of the call.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex as *const AtomicU32,
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            1,
+        );
+    }
+}
@@ -63,12 +63,50 @@
 //! # Features
 //!
 //! - The `std` feature (enabled by default) enables the use of the Rust standard library. Disable it for `no_std`
-//! support
+//!   support
 //!
 //! - The `portable-atomic` feature enables the use of the [`portable-atomic`] crate to provide
 //!   atomic operations on platforms that don't support them.
 //!
+//! - The `listener-pool` feature makes every [`Event`] keep a small pool of listener allocations,
+//!   so that repeatedly creating and dropping [`EventListener`]s doesn't hit the allocator every
+//!   time. It implies the `std` feature.
+//!
+//! - The `futex` feature makes blocking waits on Linux and Android park the thread with a raw
+//!   futex instead of the [`parking`] crate. It implies the `std` feature.
+//!
+//! - The `cortex-m` feature adds `wfe()`, which puts a Cortex-M core to sleep until an event or
+//!   interrupt arrives, for use with [`Listener::wait_with()`] on bare-metal targets. Listeners
+//!   waiting this way are woken up with `sev` when notified, so that notifications from another
+//!   core wake them up as well.
+//!
+//! - The `critical-section` feature makes the `no_std` implementation lock its list of listeners
+//!   inside a critical section from the [`critical-section`] crate. Nothing else can hold the lock
+//!   while a critical section is active, so operations wait for the critical section instead of
+//!   spinning or falling back to the backup queue. This makes them deterministic on single-core
+//!   microcontrollers and usable with interrupts disabled. An implementation of `critical-section` has to be provided, usually by
+//!   the HAL. This feature has no effect if the `std` feature is enabled.
+//!
+//! - The `defmt` feature implements [`defmt::Format`] for [`Event`] and [`EventListener`], showing
+//!   the number of listeners and the state of a listener like their `Debug` output does, and for
+//!   the error types of this crate.
+//!
+//! - The `snapshot` feature adds `Event::snapshot()`, which records the state of every listener
+//!   of an event for diagnostics.
+//!
+//! - The `stream` feature adds `Event::stream()`, which returns a [`Stream`] of notifications
+//!   from a listener that stays registered.
+//!
+//! - The `loom` feature, together with `RUSTFLAGS="--cfg loom"`, routes the atomics, `Arc`,
+//!   `Mutex` and spin loops used by this crate through [`loom`], so that code built on top of it
+//!   can be model-checked. [`WeakEvent`] is not available under `loom`.
+//!
 //! [`portable-atomic`]: https://crates.io/crates/portable-atomic
+//! [`critical-section`]: https://crates.io/crates/critical-section
+//! [`defmt::Format`]: https://docs.rs/defmt/latest/defmt/trait.Format.html
+//! [`parking`]: https://crates.io/crates/parking
+//! [`loom`]: https://crates.io/crates/loom
+//! [`Stream`]: https://docs.rs/futures-core/latest/futures_core/stream/trait.Stream.html
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::multiple_bound_locations)] // This is a WONTFIX issue with pin-project-lite
@@ -89,7 +127,49 @@ extern crate std as alloc;
 #[cfg_attr(not(feature = "std"), path = "no_std.rs")]
 mod sys;
 
+#[cfg(feature = "std")]
+mod batch;
+mod builder;
+mod clock;
+mod dual;
+mod epoch;
+#[cfg(feature = "std")]
+mod event_map;
+mod fixed;
+#[cfg(feature = "std")]
+mod forward;
+#[cfg(all(
+    feature = "futex",
+    any(target_os = "linux", target_os = "android"),
+    not(loom)
+))]
+mod futex;
+mod local;
+mod map;
+#[cfg(feature = "std")]
+mod merge;
+#[cfg(feature = "std")]
+mod meta;
 mod notify;
+mod pair;
+mod pause;
+pub mod primitives;
+mod race;
+mod raw;
+mod rendezvous;
+mod sharded;
+mod single;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+mod static_event;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+mod throttle;
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+mod timeout;
+mod wait_all;
+mod wait_many;
 
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
@@ -103,19 +183,67 @@ use core::ptr;
 use core::task::{Context, Poll, Waker};
 
 #[cfg(all(feature = "std", not(target_family = "wasm")))]
-use {
-    parking::{Parker, Unparker},
-    std::time::{Duration, Instant},
-};
-
-use sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(all(
+    feature = "std",
+    not(target_family = "wasm"),
+    not(all(
+        feature = "futex",
+        any(target_os = "linux", target_os = "android"),
+        not(loom)
+    ))
+))]
+use parking::{pair, Parker, Unparker};
+
+#[cfg(all(
+    feature = "futex",
+    any(target_os = "linux", target_os = "android"),
+    not(loom)
+))]
+use futex::{pair, Parker, Unparker};
+
+use sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 use sync::Arc;
 
 #[cfg(not(loom))]
 use sync::WithMut;
 
+#[cfg(feature = "std")]
+pub use batch::NotifyBatch;
+pub use builder::{EventBuilder, QueueOverflow, WakeOrder};
+#[cfg(feature = "cortex-m")]
+pub use clock::wfe;
+pub use clock::Clock;
+pub use dual::{DualEvent, DualListener};
+pub use epoch::WaitTicket;
+#[cfg(feature = "std")]
+pub use event_map::EventMap;
+pub use fixed::{CapacityError, FixedEvent, FixedListener};
+#[cfg(feature = "std")]
+pub use forward::Forward;
+pub use local::{LocalEvent, LocalEventListener};
+pub use map::{ListenerExt, Map};
+#[cfg(feature = "std")]
+pub use merge::{merge, Merge};
 use notify::NotificationPrivate;
-pub use notify::{IntoNotification, Notification};
+pub use notify::{DynNotification, IntoNotification, Notification};
+pub use pair::{Notifier, Waiter};
+pub use race::{race, select_events, Race, SelectEvents};
+pub use raw::RawEvent;
+pub use sharded::{ShardedEvent, ShardedListener};
+pub use single::{SingleEvent, SingleListener};
+#[cfg(feature = "snapshot")]
+pub use snapshot::{ListenerSnapshot, Snapshot};
+pub use static_event::{StaticEvent, StaticListener};
+#[cfg(feature = "stream")]
+pub use stream::EventStream;
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+pub use throttle::ThrottledEvent;
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+pub use timeout::Timeout;
+pub use wait_all::{wait_all, WaitAll};
+pub use wait_many::WaitMany;
 
 /// Inner state of [`Event`].
 struct Inner<T> {
@@ -124,19 +252,173 @@ struct Inner<T> {
     /// If there are no entries, this value is set to `usize::MAX`.
     notified: AtomicUsize,
 
+    /// The number of entries in the list, updated every time the list is unlocked.
+    listeners: AtomicUsize,
+
+    /// Allocations of dropped listeners, kept around for reuse.
+    #[cfg(feature = "listener-pool")]
+    pool: sync::Mutex<alloc::vec::Vec<Box<PooledListener<T>>>>,
+
+    /// Whether the event has been closed.
+    closed: AtomicBool,
+
+    /// The largest notification sent by [`Event::notify_from_isr()`] that has not been delivered
+    /// yet, delivered by whoever unlocks the list next.
+    pending: AtomicUsize,
+
+    /// The notifications held back by [`Event::pause()`].
+    pause: pause::Pause,
+
+    /// The epoch returned by [`Event::epoch()`], shifted left by one, with the lowest bit set if
+    /// it has been observed.
+    epoch: AtomicUsize,
+
+    /// Notified when the list is unlocked, for [`Event::notify_sync()`],
+    /// [`Event::wait_for_listeners()`] and [`Event::listen_bounded()`].
+    ///
+    /// Like any [`Event`], this is a single pointer until somebody listens to it.
+    changed: Event,
+
+    /// The event that notifications are passed on to, set by [`Event::with_parent()`].
+    ///
+    /// Boxed, since few events have one.
+    parent: Option<Box<Parent>>,
+
+    /// The events that notifications are forwarded to, added by [`Event::forward_to()`].
+    #[cfg(feature = "std")]
+    forwards: forward::Forwards,
+
+    /// How many times a blocking wait checks for a notification before parking.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    spin_before_park: u32,
+
     /// Inner queue of event listeners.
     ///
     /// On `std` platforms, this is an intrusive linked list. On `no_std` platforms, this is a
     /// more traditional `Vec` of listeners, with an atomic queue used as a backup for high
     /// contention.
-    list: sys::List<T>,
+    ///
+    /// Threads registering listeners keep writing to its lock, while every call to
+    /// [`Event::notify()`] reads `notified`. The list gets cache lines of its own, so that the
+    /// two don't fight over one.
+    list: CachePadded<sys::List<T>>,
+}
+
+/// A value aligned to the length of a cache line, so that it doesn't share one with values
+/// written by other threads.
+///
+/// The line lengths are the ones used by `crossbeam-utils`, except on x86_64. Its prefetcher
+/// pulls in pairs of 64-byte lines, but that is not worth doubling the size of every event.
+#[cfg_attr(
+    any(target_arch = "aarch64", target_arch = "powerpc64"),
+    repr(align(128))
+)]
+#[cfg_attr(
+    any(
+        target_arch = "arm",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "riscv32",
+    ),
+    repr(align(32))
+)]
+#[cfg_attr(
+    not(any(
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+        target_arch = "arm",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "riscv32",
+    )),
+    repr(align(64))
+)]
+struct CachePadded<T>(T);
+
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// The parent of an [`Event`] created with [`Event::with_parent()`].
+struct Parent {
+    /// The parent event.
+    event: Event,
+
+    /// The number of listeners of the parent to notify.
+    count: usize,
 }
 
 impl<T> Inner<T> {
     fn new() -> Self {
+        Self::with_config(&EventBuilder::new())
+    }
+
+    fn with_config(config: &EventBuilder) -> Self {
         Self {
             notified: AtomicUsize::new(usize::MAX),
-            list: sys::List::new(),
+            listeners: AtomicUsize::new(0),
+            #[cfg(feature = "listener-pool")]
+            pool: sync::Mutex::new(alloc::vec::Vec::new()),
+            closed: AtomicBool::new(false),
+            pending: AtomicUsize::new(0),
+            pause: pause::Pause::new(),
+            epoch: AtomicUsize::new(0),
+            changed: Event::new(),
+            parent: None,
+            #[cfg(feature = "std")]
+            forwards: forward::Forwards::new(),
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            spin_before_park: config.spin_before_park,
+            list: CachePadded(sys::List::new(config)),
+        }
+    }
+
+    /// Notifies the listeners with `f`, and passes the notification on to the parent event and
+    /// the forwards, unless the event is paused.
+    ///
+    /// Every notification goes through here, so that pausing, epochs and hooks apply to all of
+    /// them alike.
+    fn deliver<N: Notification<Tag = T>>(
+        &self,
+        mut notify: N,
+        f: impl FnOnce(&Self, &mut N) -> usize,
+    ) -> usize {
+        #[cfg(feature = "std")]
+        let forwarded = (
+            notify.count(notify::Internal::new()),
+            notify.is_additional(notify::Internal::new()),
+        );
+
+        notify.before_notify(notify::Internal::new());
+
+        let count = if self.hold(
+            notify.count(notify::Internal::new()),
+            notify.is_additional(notify::Internal::new()),
+        ) {
+            0
+        } else {
+            self.advance_epoch();
+            let count = f(self, &mut notify);
+            self.notify_parent();
+            #[cfg(feature = "std")]
+            self.forwards.notify(forwarded.0, forwarded.1);
+            count
+        };
+
+        notify.after_notify(count, notify::Internal::new());
+        count
+    }
+
+    /// Passes a notification on to the parent event, if there is one.
+    #[inline]
+    fn notify_parent(&self) {
+        if let Some(parent) = &self.parent {
+            parent.event.notify(parent.count);
         }
     }
 }
@@ -148,7 +430,12 @@ impl<T> Inner<T> {
 /// 1. [`Event::notify()`] notifies a number of listeners.
 /// 2. [`Event::notify_additional()`] notifies a number of previously unnotified listeners.
 ///
-/// If there are no active listeners at the time a notification is sent, it simply gets lost.
+/// If there are no active listeners at the time a notification is sent, it simply gets lost,
+/// unless it is a [`sticky`] notification or the event was built with a
+/// [`permit_capacity`].
+///
+/// [`sticky`]: IntoNotification::sticky
+/// [`permit_capacity`]: EventBuilder::permit_capacity
 ///
 /// There are two ways for a listener to wait for a notification:
 ///
@@ -179,9 +466,8 @@ impl<T> fmt::Debug for Event<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.try_inner() {
             Some(inner) => {
-                let notified_count = inner.notified.load(Ordering::Relaxed);
-                let total_count = match inner.list.try_total_listeners() {
-                    Some(total_count) => total_count,
+                let (total_count, notified_count) = match inner.list.try_counts() {
+                    Some(counts) => counts,
                     None => {
                         return f
                             .debug_tuple("Event")
@@ -203,6 +489,24 @@ impl<T> fmt::Debug for Event<T> {
     }
 }
 
+#[cfg(feature = "defmt")]
+impl<T> defmt::Format for Event<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self.try_inner() {
+            Some(inner) => match inner.list.try_counts() {
+                Some((total_count, notified_count)) => defmt::write!(
+                    f,
+                    "Event {{ listeners_notified: {=usize}, listeners_total: {=usize} }}",
+                    notified_count,
+                    total_count
+                ),
+                None => defmt::write!(f, "Event(<locked>)"),
+            },
+            None => defmt::write!(f, "Event(<uninitialized>)"),
+        }
+    }
+}
+
 impl Default for Event {
     #[inline]
     fn default() -> Self {
@@ -210,6 +514,37 @@ impl Default for Event {
     }
 }
 
+/// Creates another handle to the same event.
+///
+/// Both handles share one list of listeners, so notifying through either of them wakes listeners
+/// registered through the other. This makes it unnecessary to wrap an `Event` in an [`Arc`] just
+/// to share it.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, Listener};
+///
+/// let event = Event::new();
+/// let handle = event.clone();
+///
+/// let listener = event.listen();
+/// assert!(listener.listens_to(&handle));
+///
+/// assert_eq!(handle.notify(1), 1);
+/// ```
+impl<T> Clone for Event<T> {
+    fn clone(&self) -> Self {
+        // Make sure the state is initialized, so that both handles point to it.
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+        let inner = Arc::into_raw(Arc::clone(&inner)) as *mut Inner<T>;
+
+        Self {
+            inner: AtomicPtr::new(inner),
+        }
+    }
+}
+
 impl<T> Event<T> {
     /// Creates a new `Event` with a tag type.
     ///
@@ -230,6 +565,19 @@ impl<T> Event<T> {
             inner: AtomicPtr::new(ptr::null_mut()),
         }
     }
+
+    /// Creates a new `Event` with a tag type.
+    ///
+    /// Tagging cannot be implemented efficiently on `no_std`, so this is only available when the
+    /// `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::<usize>::with_tag();
+    /// ```
     #[cfg(all(feature = "std", loom))]
     #[inline]
     pub fn with_tag() -> Self {
@@ -238,6 +586,40 @@ impl<T> Event<T> {
         }
     }
 
+    /// Creates a new [`Event`] whose notifications are passed on to a parent event.
+    ///
+    /// Every time this event is notified, `parent` is notified with `parent.notify(count)` as
+    /// well, whether or not this event had any listeners. Listeners of the parent therefore wake
+    /// up when any of its children is notified, which makes it possible to wait for any of a
+    /// group of sub-resources without notifying two events everywhere.
+    ///
+    /// Children can be parents of other events in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let any_ready = Event::new();
+    /// let rx_ready: Event = Event::with_parent(&any_ready, 1);
+    /// let tx_ready: Event = Event::with_parent(&any_ready, 1);
+    ///
+    /// let listener = any_ready.listen();
+    /// tx_ready.notify(1);
+    /// assert!(any_ready.is_notified());
+    /// ```
+    pub fn with_parent(parent: &Event, count: usize) -> Self {
+        let mut inner: Inner<T> = Inner::new();
+        inner.parent = Some(Box::new(Parent {
+            event: parent.clone(),
+            count,
+        }));
+
+        Self {
+            inner: AtomicPtr::new(Arc::into_raw(Arc::new(inner)) as *mut Inner<T>),
+        }
+    }
+
     /// Tell whether any listeners are currently notified.
     ///
     /// # Examples
@@ -258,6 +640,88 @@ impl<T> Event<T> {
             .map_or(false, |inner| inner.notified.load(Ordering::Acquire) > 0)
     }
 
+    /// Returns the number of listeners currently registered to this [`Event`].
+    ///
+    /// Unlike [`Event::total_listeners()`], this does not acquire the internal lock, and it is
+    /// available on `no_std` as well. The count is updated every time the listener list is
+    /// modified, so it should only ever be used as a hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert_eq!(event.listener_count(), 0);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// assert_eq!(event.listener_count(), 2);
+    ///
+    /// drop(listener1);
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.try_inner()
+            .map_or(0, |inner| inner.listeners.load(Ordering::Acquire))
+    }
+
+    /// Returns the number of registered listeners that have been notified but have not yet
+    /// received their notification.
+    ///
+    /// Like [`Event::listener_count()`], this should only ever be used as a hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// assert_eq!(event.notified_count(), 0);
+    ///
+    /// event.notify(1);
+    /// assert_eq!(event.notified_count(), 1);
+    ///
+    /// event.notify(2);
+    /// assert_eq!(event.notified_count(), 2);
+    /// ```
+    #[inline]
+    pub fn notified_count(&self) -> usize {
+        self.try_inner().map_or(0, |inner| {
+            let listeners = inner.listeners.load(Ordering::Acquire);
+
+            // `usize::MAX` means that every listener is notified.
+            match inner.notified.load(Ordering::Acquire) {
+                usize::MAX => listeners,
+                notified => notified.min(listeners),
+            }
+        })
+    }
+
+    /// Tells whether there are no listeners registered to this [`Event`].
+    ///
+    /// This can be used to skip work that is only needed to wake someone up. Like
+    /// [`Event::listener_count()`], this should only ever be used as a hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert!(event.is_empty());
+    ///
+    /// let listener = event.listen();
+    /// assert!(!event.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.listener_count() == 0
+    }
+
     /// Returns a guard listening for a notification.
     ///
     /// This method emits a `SeqCst` fence after registering a listener. For now, this method
@@ -291,250 +755,367 @@ impl<T> Event<T> {
     /// directly, which allows for greater control over where the [`EventListener`] is
     /// allocated. However, users of this `new` method must be careful to ensure that the
     /// [`EventListener`] is `listen`ing before waiting on it; panics may occur otherwise.
+    ///
+    /// This doesn't check the bound set by [`EventBuilder::listen_bound()`], although the
+    /// listener counts towards it. Use [`Event::try_listen_bounded()`] or
+    /// [`Event::listen_bounded()`] to respect the bound.
     #[cold]
     pub fn listen(&self) -> EventListener<T> {
         let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
 
         // Allocate the listener on the heap and insert it.
-        let mut listener = Box::pin(InnerListener {
-            event: Arc::clone(&inner),
-            listener: None,
-        });
+        let mut listener = ListenerBox::new(Arc::clone(&inner));
         listener.as_mut().listen();
 
         // Return the listener.
         EventListener { listener }
     }
 
-    /// Notifies a number of active listeners.
-    ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
-    ///
-    /// The [`Notification`] trait is used to define what kind of notification is delivered.
-    /// The default implementation (implemented on `usize`) is a notification that only notifies
-    /// *at least* the specified number of listeners.
+    /// Waits for the next notification.
     ///
-    /// In certain cases, this function emits a `SeqCst` fence before notifying listeners.
-    ///
-    /// This function returns the number of [`EventListener`]s that were notified by this call.
-    ///
-    /// # Caveats
-    ///
-    /// If the `std` feature is disabled, the notification will be delayed under high contention,
-    /// such as when another thread is taking a while to `notify` the event. In this circumstance,
-    /// this function will return `0` instead of the number of listeners actually notified. Therefore
-    /// if the `std` feature is disabled the return value of this function should not be relied upon
-    /// for soundness and should be used only as a hint.
+    /// This is a shorthand for `event.listen().await` for call sites that just want to be woken
+    /// up the next time the event is notified. The listener is registered when this method is
+    /// called rather than when the future is first polled, so a notification sent in between is
+    /// not missed.
     ///
-    /// If the `std` feature is enabled, no spurious returns are possible, since the `std`
-    /// implementation uses system locking primitives to ensure there is no unavoidable
-    /// contention.
+    /// Registration is followed by a full fence. A condition that is checked after calling this
+    /// method, and set before calling [`Event::notify()`], is therefore either seen by the check
+    /// or followed by a notification to the returned listener.
     ///
     /// # Examples
     ///
-    /// Use the default notification strategy:
-    ///
     /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
     /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
     ///
     /// let event = Event::new();
+    /// let flag = AtomicBool::new(false);
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1);
+    /// let notified = event.notified();
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// flag.store(true, Ordering::SeqCst);
+    /// event.notify(1);
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify(2);
+    /// if !flag.load(Ordering::SeqCst) {
+    ///     notified.await;
+    /// }
+    /// # });
     /// ```
+    pub fn notified(&self) -> EventListener<T> {
+        // Unlocking the list after registering the listener emits the fence.
+        self.listen()
+    }
+
+    /// Starts listening, and then checks a condition.
     ///
-    /// Notify without emitting a `SeqCst` fence. This uses the [`relaxed`] notification strategy.
-    /// This is equivalent to calling [`Event::notify_relaxed()`].
+    /// The listener is registered and followed by a full fence before `pred` is called, which is
+    /// the order that the check has to be done in to avoid missing a notification. If `pred`
+    /// returns `true`, the listener is dropped, which passes any notification it received on to
+    /// another listener, and [`Checked::Ready`] is returned. Otherwise, the registered listener is
+    /// returned in [`Checked::Listening`].
     ///
-    /// [`relaxed`]: IntoNotification::relaxed
+    /// # Examples
     ///
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// use std::sync::atomic::{self, Ordering};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use event_listener::{Checked, Event};
+    /// # futures_lite::future::block_on(async {
     ///
     /// let event = Event::new();
+    /// let flag = AtomicBool::new(false);
+    ///
+    /// loop {
+    ///     if flag.load(Ordering::SeqCst) {
+    ///         break;
+    ///     }
+    ///
+    ///     match event.listen_and_check(|| flag.load(Ordering::SeqCst)) {
+    ///         Checked::Ready => break,
+    ///         Checked::Listening(listener) => {
+    ///             flag.store(true, Ordering::SeqCst);
+    ///             event.notify(1);
+    ///             listener.await;
+    ///         }
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn listen_and_check(&self, pred: impl FnOnce() -> bool) -> Checked<T> {
+        let listener = self.notified();
+
+        if pred() {
+            drop(listener);
+            Checked::Ready
+        } else {
+            Checked::Listening(listener)
+        }
+    }
+
+    /// Blocks until the next notification and returns its tag.
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1.relaxed());
-    ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// This is a shorthand for listening and then calling [`Listener::wait()`]. The listener lives
+    /// on the stack, so this doesn't allocate. Only notifications sent after this method is called
+    /// are received.
     ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// # Examples
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify(2.relaxed());
     /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use event_listener::Event;
     ///
-    /// Notify additional listeners. In contrast to [`Event::notify()`], this method will notify `n`
-    /// *additional* listeners that were previously unnotified. This uses the [`additional`]
-    /// notification strategy. This is equivalent to calling [`Event::notify_additional()`].
+    /// let event = Arc::new(Event::new());
     ///
-    /// [`additional`]: IntoNotification::additional
+    /// thread::spawn({
+    ///     let event = event.clone();
+    ///     move || loop {
+    ///         // Keep notifying until the waiter has registered.
+    ///         if event.notify(1) > 0 {
+    ///             break;
+    ///         }
+    ///         thread::sleep(Duration::from_millis(1));
+    ///     }
+    /// });
     ///
+    /// event.wait();
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    ///
-    /// let event = Event::new();
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(&self) -> T {
+        listener!(self => listener);
+        listener.wait()
+    }
+
+    /// Blocks until the next notification or until the timeout has elapsed.
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1.additional());
+    /// Returns an error if the timeout elapsed first. See [`Event::wait()`] for more details.
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// # Examples
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify(1.additional());
-    /// event.notify(1.additional());
     /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, WaitTimeoutError};
     ///
-    /// Notifies with the [`additional`] and [`relaxed`] strategies at the same time. This is
-    /// equivalent to calling [`Event::notify_additional_relaxed()`].
+    /// let event = Event::new();
     ///
+    /// // There is no notification, so this times out.
+    /// assert_eq!(
+    ///     event.wait_timeout(Duration::from_millis(10)),
+    ///     Err(WaitTimeoutError)
+    /// );
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// use std::sync::atomic::{self, Ordering};
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<T, WaitTimeoutError> {
+        listener!(self => listener);
+        listener.try_wait_timeout(timeout)
+    }
+
+    /// Blocks until the next notification or until the deadline is reached.
     ///
-    /// let event = Event::new();
+    /// Returns an error if the deadline was reached first. See [`Event::wait()`] for more
+    /// details.
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1.additional().relaxed());
+    /// # Examples
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Event, WaitTimeoutError};
     ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// let event = Event::new();
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify(1.additional().relaxed());
-    /// event.notify(1.additional().relaxed());
+    /// // There is no notification, so this times out.
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert_eq!(event.wait_deadline(deadline), Err(WaitTimeoutError));
     /// ```
-    #[inline]
-    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
-        let notify = notify.into_notification();
-
-        // Make sure the notification comes after whatever triggered it.
-        notify.fence(notify::Internal::new());
-
-        let inner = unsafe { &*self.inner() };
-        inner.notify(notify)
-    }
-
-    /// Return a reference to the inner state if it has been initialized.
-    #[inline]
-    fn try_inner(&self) -> Option<&Inner<T>> {
-        let inner = self.inner.load(Ordering::Acquire);
-        unsafe { inner.as_ref() }
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_deadline(&self, deadline: Instant) -> Result<T, WaitTimeoutError> {
+        listener!(self => listener);
+        listener.try_wait_deadline(deadline)
     }
 
-    /// Returns a raw, initialized pointer to the inner state.
+    /// Blocks until `pred` returns `true`.
     ///
-    /// This returns a raw pointer instead of reference because `from_raw`
-    /// requires raw/mut provenance: <https://github.com/rust-lang/rust/pull/67339>.
-    fn inner(&self) -> *const Inner<T> {
-        let mut inner = self.inner.load(Ordering::Acquire);
-
-        // If this is the first use, initialize the state.
-        if inner.is_null() {
-            // Allocate the state on the heap.
-            let new = Arc::new(Inner::<T>::new());
-
-            // Convert the state to a raw pointer.
-            let new = Arc::into_raw(new) as *mut Inner<T>;
+    /// This runs the usual loop for waiting on a condition: check it, start listening, check it
+    /// again, and only then block until the next notification. Checking again after starting to
+    /// listen is what makes sure that a notification sent right after the first check is not
+    /// missed. Whoever makes the condition true has to notify the event afterwards.
+    ///
+    /// The predicate is called at least once, and again after every notification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::Event;
+    ///
+    /// let flag = Arc::new(AtomicBool::new(false));
+    /// let event = Arc::new(Event::new());
+    ///
+    /// thread::spawn({
+    ///     let flag = flag.clone();
+    ///     let event = event.clone();
+    ///     move || {
+    ///         flag.store(true, Ordering::SeqCst);
+    ///         event.notify(usize::MAX);
+    ///     }
+    /// });
+    ///
+    /// event.wait_until(|| flag.load(Ordering::SeqCst));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_until(&self, mut pred: impl FnMut() -> bool) {
+        loop {
+            // Check the condition.
+            if pred() {
+                return;
+            }
 
-            // Replace the null pointer with the new state pointer.
-            inner = self
-                .inner
-                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
-                .unwrap_or_else(|x| x);
+            // Set up an event listener.
+            listener!(self => listener);
 
-            // Check if the old pointer value was indeed null.
-            if inner.is_null() {
-                // If yes, then use the new state pointer.
-                inner = new;
-            } else {
-                // If not, that means a concurrent operation has initialized the state.
-                // In that case, use the old pointer and deallocate the new one.
-                unsafe {
-                    drop(Arc::from_raw(new));
-                }
+            // Check again now that we're listening.
+            if pred() {
+                return;
             }
-        }
 
-        inner
+            // Wait for a notification and try again.
+            listener.wait();
+        }
     }
 
-    /// Get the number of listeners currently listening to this [`Event`].
+    /// Waits until `pred` returns `true`.
     ///
-    /// This call returns the number of [`EventListener`]s that are currently listening to
-    /// this event. It does this by acquiring the internal event lock and reading the listener
-    /// count. Therefore it is only available for `std`-enabled platforms.
+    /// This is the async version of [`Event::wait_until()`]: the condition is checked, checked
+    /// again after starting to listen, and only then does the future wait for the next
+    /// notification. Whoever makes the condition true has to notify the event afterwards.
     ///
-    /// # Caveats
+    /// The predicate is called at least once, and again after every notification.
     ///
-    /// This function returns just a snapshot of the number of listeners at this point in time.
-    /// Due to the nature of multi-threaded CPUs, it is possible that this number will be
-    /// inaccurate by the time that this function returns.
+    /// # Examples
     ///
-    /// It is possible for the actual number to change at any point. Therefore, the number should
-    /// only ever be used as a hint.
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let flag = Arc::new(AtomicBool::new(false));
+    /// let event = Arc::new(Event::new());
+    ///
+    /// thread::spawn({
+    ///     let flag = flag.clone();
+    ///     let event = event.clone();
+    ///     move || {
+    ///         flag.store(true, Ordering::SeqCst);
+    ///         event.notify(usize::MAX);
+    ///     }
+    /// });
+    ///
+    /// event.wait_until_async(|| flag.load(Ordering::SeqCst)).await;
+    /// # });
+    /// ```
+    pub async fn wait_until_async(&self, mut pred: impl FnMut() -> bool) {
+        loop {
+            // Check the condition.
+            if pred() {
+                return;
+            }
+
+            // Set up an event listener.
+            listener!(self => listener);
+
+            // Check again now that we're listening.
+            if pred() {
+                return;
+            }
+
+            // Wait for a notification and try again.
+            listener.await;
+        }
+    }
+
+    /// Blocks until `pred` returns `true` or until the timeout has elapsed.
+    ///
+    /// Returns the last result of the predicate, which is `false` if the timeout elapsed first.
+    /// See [`Event::wait_until()`] for more details.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::time::Duration;
     /// use event_listener::Event;
     ///
     /// let event = Event::new();
     ///
-    /// assert_eq!(event.total_listeners(), 0);
+    /// // The condition never becomes true, so this times out.
+    /// assert!(!event.wait_until_timeout(Duration::from_millis(10), || false));
+    /// assert!(event.wait_until_timeout(Duration::from_millis(10), || true));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_until_timeout(&self, timeout: Duration, pred: impl FnMut() -> bool) -> bool {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_until_deadline(deadline, pred),
+            None => {
+                self.wait_until(pred);
+                true
+            }
+        }
+    }
+
+    /// Blocks until `pred` returns `true` or until the deadline is reached.
     ///
-    /// let listener1 = event.listen();
-    /// assert_eq!(event.total_listeners(), 1);
+    /// Returns the last result of the predicate, which is `false` if the deadline was reached
+    /// first. See [`Event::wait_until()`] for more details.
     ///
-    /// let listener2 = event.listen();
-    /// assert_eq!(event.total_listeners(), 2);
+    /// # Examples
     ///
-    /// drop(listener1);
-    /// drop(listener2);
-    /// assert_eq!(event.total_listeners(), 0);
     /// ```
-    #[cfg(feature = "std")]
-    #[inline]
-    pub fn total_listeners(&self) -> usize {
-        if let Some(inner) = self.try_inner() {
-            inner.list.total_listeners()
-        } else {
-            0
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// // The condition never becomes true, so this times out.
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert!(!event.wait_until_deadline(deadline, || false));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_until_deadline(&self, deadline: Instant, mut pred: impl FnMut() -> bool) -> bool {
+        loop {
+            // Check the condition.
+            if pred() {
+                return true;
+            }
+
+            // Set up an event listener.
+            listener!(self => listener);
+
+            // Check again now that we're listening.
+            if pred() {
+                return true;
+            }
+
+            // Wait for a notification and try again, or give up once the deadline is reached.
+            if listener.try_wait_deadline(deadline).is_err() {
+                return pred();
+            }
         }
     }
-}
 
-impl Event<()> {
-    /// Creates a new [`Event`].
+    /// Returns a guard listening for a notification, or an error if memory for it could not be
+    /// allocated.
+    ///
+    /// This works like [`Event::listen()`], but reports allocation failure instead of aborting.
+    /// This is mainly useful on `no_std` targets with small heaps, where the listener list itself
+    /// has to grow as well.
+    ///
+    /// The shared state of the event is allocated the first time the event is used, and that
+    /// allocation is not fallible. Call this once the event has already been used to make sure
+    /// that allocation failure is always reported.
     ///
     /// # Examples
     ///
@@ -542,399 +1123,2374 @@ impl Event<()> {
     /// use event_listener::Event;
     ///
     /// let event = Event::new();
+    /// let listener = event.try_listen().expect("out of memory");
+    ///
+    /// assert_eq!(event.notify(1), 1);
     /// ```
-    #[inline]
-    #[cfg(not(loom))]
-    pub const fn new() -> Self {
-        Self {
-            inner: AtomicPtr::new(ptr::null_mut()),
-        }
-    }
+    #[cold]
+    pub fn try_listen(&self) -> Result<EventListener<T>, AllocError> {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
 
-    #[inline]
-    #[cfg(loom)]
-    pub fn new() -> Self {
-        Self {
-            inner: AtomicPtr::new(ptr::null_mut()),
-        }
+        // Allocate the listener on the heap and insert it.
+        let mut listener = ListenerBox::try_new(Arc::clone(&inner))?;
+        listener.as_mut().try_listen()?;
+
+        // Return the listener.
+        Ok(EventListener { listener })
     }
 
-    /// Notifies a number of active listeners without emitting a `SeqCst` fence.
+    /// Returns a guard listening for a notification, unless the event already has as many
+    /// listeners as it admits.
     ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
+    /// This works like [`Event::try_listen()`], but fails with [`TryListenError::Full`] if the
+    /// event already has as many listeners as set by [`EventBuilder::listen_bound()`]. The bound is
+    /// checked under the lock, so concurrent callers can't exceed it.
     ///
-    /// In contrast to [`Event::notify_additional()`], this method only makes sure *at least* `n`
-    /// listeners among the active ones are notified.
+    /// # Examples
     ///
-    /// Unlike [`Event::notify()`], this method does not emit a `SeqCst` fence.
+    /// ```
+    /// use event_listener::{Event, TryListenError};
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    /// let event = Event::builder().listen_bound(2).build();
     ///
+    /// let listener1 = event.try_listen_bounded().unwrap();
+    /// let listener2 = event.try_listen_bounded().unwrap();
+    /// assert_eq!(event.try_listen_bounded().unwrap_err(), TryListenError::Full);
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// let event = Event::new();
+    #[cold]
+    pub fn try_listen_bounded(&self) -> Result<EventListener<T>, TryListenError> {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        // Allocate the listener on the heap and insert it.
+        let mut listener = ListenerBox::try_new(Arc::clone(&inner))?;
+        listener.as_mut().try_listen_bounded()?;
+
+        // Return the listener.
+        Ok(EventListener { listener })
+    }
+
+    /// Waits until the event admits another listener, and returns a guard listening for a
+    /// notification.
     ///
-    /// // Old way:
-    /// event.notify_relaxed(1);
+    /// This works like [`Event::listen()`], but if the event already has as many listeners as set
+    /// by [`EventBuilder::listen_bound()`], the returned future waits until one of them goes
+    /// away. Like with [`Event::try_listen_bounded()`], the cap is checked under the lock.
+    /// Listeners waiting for room are not admitted in any particular order.
+    ///
+    /// # Examples
     ///
-    /// // New way:
-    /// event.notify(1.relaxed());
     /// ```
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
     ///
-    /// # Examples
+    /// let event = Event::builder().listen_bound(1).build();
+    ///
+    /// let listener = event.listen_bounded().await;
+    /// event.notify(1);
+    /// listener.await;
     ///
+    /// // The first listener is gone, so there is room for another one.
+    /// let listener = event.listen_bounded().await;
+    /// # });
     /// ```
-    /// use event_listener::{Event, IntoNotification};
-    /// use std::sync::atomic::{self, Ordering};
+    pub async fn listen_bounded(&self) -> EventListener<T> {
+        let max = unsafe { &*self.inner() }.list.listen_bound();
+        let mut listener = {
+            let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+            EventListener {
+                listener: ListenerBox::new(Arc::clone(&inner)),
+            }
+        };
+
+        loop {
+            // Try to take a place in the list.
+            if listener.listener.as_mut().listen_bounded().is_ok() {
+                return listener;
+            }
+
+            // Set up an event listener.
+            listener!(unsafe { &*self.inner() }.changed => changed);
+
+            // Check again now that we're listening.
+            if self.listener_count() < max {
+                continue;
+            }
+
+            // Wait until the list changes.
+            changed.await;
+        }
+    }
+
+    /// Returns a listener that borrows this event instead of allocating.
     ///
-    /// let event = Event::new();
+    /// The returned [`EventListenerRef`] has to be pinned before it can be used, and it doesn't
+    /// start listening until [`EventListenerRef::listen()`] is called or it is first polled. This
+    /// makes it suitable for hot paths, such as lock loops, where allocating a listener for every
+    /// wait would be too expensive.
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify_relaxed(1);
+    /// # Examples
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// ```
+    /// use event_listener::Event;
+    /// use futures_lite::pin;
+    /// # futures_lite::future::block_on(async {
     ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// let event = Event::new();
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify_relaxed(2);
+    /// let listener = event.listen_ref();
+    /// pin!(listener);
+    /// listener.as_mut().listen();
+    ///
+    /// event.notify(1);
+    /// listener.await;
+    /// # });
     /// ```
     #[inline]
-    pub fn notify_relaxed(&self, n: usize) -> usize {
-        self.notify(n.relaxed())
+    pub fn listen_ref(&self) -> EventListenerRef<'_, T> {
+        EventListenerRef {
+            listener: InnerListener {
+                event: unsafe { &*self.inner() },
+                listener: None,
+            },
+        }
     }
 
-    /// Notifies a number of active and still unnotified listeners.
-    ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
-    ///
-    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
-    /// were previously unnotified.
+    /// Runs a closure with a listener for this event.
     ///
-    /// This method emits a `SeqCst` fence before notifying listeners.
+    /// The listener is allocated on the stack, pinned and inserted into the list before the
+    /// closure is called, and it is removed from the list when the closure returns or unwinds.
+    /// This works like the [`listener!`] macro, without the need to pin anything by hand. The
+    /// listener implements [`Listener`], so it can be waited on, polled or discarded.
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    /// # Examples
     ///
     /// ```
-    /// use event_listener::{IntoNotification, Event};
+    /// use event_listener::{Event, Listener};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
     /// let event = Event::new();
+    /// let flag = AtomicBool::new(false);
     ///
-    /// // Old way:
-    /// event.notify_additional(1);
+    /// flag.store(true, Ordering::SeqCst);
+    /// event.notify(1);
     ///
-    /// // New way:
-    /// event.notify(1.additional());
+    /// event.with_listener(|listener| {
+    ///     if !flag.load(Ordering::SeqCst) {
+    ///         listener.wait();
+    ///     }
+    /// });
+    /// assert_eq!(event.listener_count(), 0);
     /// ```
+    pub fn with_listener<R>(&self, f: impl FnOnce(__private::StackListener<'_, '_, T>) -> R) -> R {
+        let mut slot = __private::StackSlot::new(self);
+        // SAFETY: `slot` is shadowed, so it can't be moved after being pinned.
+        let slot = unsafe { Pin::new_unchecked(&mut slot) };
+        f(slot.listen())
+    }
+
+    /// Returns a guard listening for a notification or for the event to be closed.
+    ///
+    /// This works like [`Event::listen()`], except that the returned listener completes with
+    /// `Err(Closed)` once [`Event::close()`] has been called, instead of with a tag. Like
+    /// [`Event::listen()`], this doesn't check the bound set by [`EventBuilder::listen_bound()`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::Event;
+    /// use event_listener::{Closed, Event};
+    /// # futures_lite::future::block_on(async {
     ///
     /// let event = Event::new();
+    /// let listener = event.listen_closeable();
+    ///
+    /// event.close();
+    /// assert_eq!(listener.await, Err(Closed));
+    /// # });
+    /// ```
+    #[cold]
+    pub fn listen_closeable(&self) -> CloseableListener<T> {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        // Allocate the listener on the heap and insert it.
+        let mut listener = ListenerBox::new(Arc::clone(&inner));
+        listener.as_mut().listen();
+
+        // Return the listener.
+        CloseableListener { listener }
+    }
+
+    /// Notifies a number of active listeners.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// The [`Notification`] trait is used to define what kind of notification is delivered.
+    /// The default implementation (implemented on `usize`) is a notification that only notifies
+    /// *at least* the specified number of listeners.
+    ///
+    /// In certain cases, this function emits a `SeqCst` fence before notifying listeners.
+    ///
+    /// This function returns the number of [`EventListener`]s that were notified by this call.
+    ///
+    /// # Caveats
+    ///
+    /// If the `std` feature is disabled, the notification will be delayed under high contention,
+    /// such as when another thread is taking a while to `notify` the event. In this circumstance,
+    /// this function will return `0` instead of the number of listeners actually notified. Therefore
+    /// if the `std` feature is disabled the return value of this function should not be relied upon
+    /// for soundness and should be used only as a hint. Use [`Event::notify_count()`] if the exact
+    /// number is needed.
+    ///
+    /// If the `std` feature is enabled, no spurious returns are possible, since the `std`
+    /// implementation uses system locking primitives to ensure there is no unavoidable
+    /// contention.
+    ///
+    /// # Examples
+    ///
+    /// Use the default notification strategy:
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify_additional(1);
-    ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
-    ///
     /// // Notifies two listeners.
     /// //
     /// // Listener queueing is fair, which means `listener1` and `listener2`
     /// // get notified here since they start listening before `listener3`.
-    /// event.notify_additional(1);
-    /// event.notify_additional(1);
+    /// event.notify(2);
+    /// ```
+    ///
+    /// Notify without emitting a `SeqCst` fence. This uses the [`relaxed`] notification strategy.
+    /// This is equivalent to calling [`Event::notify_relaxed()`].
+    ///
+    /// [`relaxed`]: IntoNotification::relaxed
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1.relaxed());
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify(2.relaxed());
+    /// ```
+    ///
+    /// Notify additional listeners. In contrast to [`Event::notify()`], this method will notify `n`
+    /// *additional* listeners that were previously unnotified. This uses the [`additional`]
+    /// notification strategy. This is equivalent to calling [`Event::notify_additional()`].
+    ///
+    /// [`additional`]: IntoNotification::additional
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1.additional());
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify(1.additional());
+    /// event.notify(1.additional());
+    /// ```
+    ///
+    /// Notifies with the [`additional`] and [`relaxed`] strategies at the same time. This is
+    /// equivalent to calling [`Event::notify_additional_relaxed()`].
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1.additional().relaxed());
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify(1.additional().relaxed());
+    /// event.notify(1.additional().relaxed());
     /// ```
     #[inline]
-    pub fn notify_additional(&self, n: usize) -> usize {
-        self.notify(n.additional())
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        unsafe { &*self.inner() }.deliver(notify, |inner, notify| inner.notify(notify))
     }
 
-    /// Notifies a number of active and still unnotified listeners without emitting a `SeqCst`
-    /// fence.
+    /// Notifies a number of active listeners and returns how many of them actually received the
+    /// notification.
+    ///
+    /// This works like [`Event::notify()`], but the returned count is always exact, even when the
+    /// `std` feature is disabled. This makes it possible to tell whether a notification was handed
+    /// off to a listener or whether nobody was around to receive it. A [`sticky`] notification
+    /// that is stored for a future listener counts as not received.
+    ///
+    /// [`sticky`]: IntoNotification::sticky
+    ///
+    /// # Caveats
+    ///
+    /// If the `std` feature is enabled, this is the same as [`Event::notify()`]. Otherwise, instead
+    /// of deferring the notification under contention, this spins until the internal lock is
+    /// available. It must not be called from a context that can preempt another user of the same
+    /// `Event`, such as an interrupt handler, since that would spin forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// // Nobody is listening, so the notification is not received.
+    /// assert_eq!(event.notify_count(1), 0);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify_count(1), 1);
+    ///
+    /// // `listener1` is already notified, so only `listener2` receives this one.
+    /// assert_eq!(event.notify_count(5), 1);
+    /// ```
+    #[inline]
+    pub fn notify_count(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        unsafe { &*self.inner() }.deliver(notify, |inner, notify| inner.notify_exact(notify))
+    }
+
+    /// Notifies a number of active listeners, starting with the one that started listening last.
+    ///
+    /// This works like [`Event::notify()`], but unnotified listeners are picked in last-in,
+    /// first-out order instead of first-in, first-out order. Handing a contended lock to the
+    /// listener that started waiting most recently tends to improve throughput, since its thread
+    /// is the most likely one to still have the relevant data in its cache. The price is fairness:
+    /// a listener may keep waiting for as long as newer listeners keep arriving.
+    ///
+    /// Listeners that are already notified count towards a notification that is not
+    /// [`additional`], no matter when they started listening.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// [`additional`]: IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let first = event.listen();
+    /// let last = event.listen();
+    ///
+    /// assert_eq!(event.notify_last(1), 1);
+    /// last.wait();
+    ///
+    /// assert_eq!(event.notify_last(1), 1);
+    /// first.wait();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn notify_last(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        unsafe { &*self.inner() }.deliver(notify, |inner, notify| inner.notify_last(notify))
+    }
+
+    /// Notifies all listeners, handing each one a clone of `tag`.
+    ///
+    /// This is the same as `notify(usize::MAX.tag(tag))`. Listeners that are already notified keep
+    /// the tag they were notified with. Returns the number of listeners that were notified.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::<&str>::with_tag();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify_all_with_tag("shutdown"), 2);
+    /// assert_eq!(listener1.wait(), "shutdown");
+    /// assert_eq!(listener2.wait(), "shutdown");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn notify_all_with_tag(&self, tag: T) -> usize
+    where
+        T: Clone,
+    {
+        self.notify(usize::MAX.tag(tag))
+    }
+
+    /// Notifies one listener that isn't notified yet with `tag`, or hands `tag` back.
+    ///
+    /// Unlike [`Event::notify()`], which drops the tag when nobody receives it, this returns
+    /// `Err(tag)` if there is no unnotified listener, so that a resource carried by the tag can be
+    /// kept somewhere else. The tag is never stored as a permit, even if the event was built with
+    /// [`EventBuilder::permit_capacity()`]. This waits for the lock instead of deferring the
+    /// notification, like [`Event::notify_count()`].
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::<Vec<u8>>::with_tag();
+    ///
+    /// // Nobody is listening, so the buffer is handed back.
+    /// let buffer = event.try_notify(vec![1, 2, 3]).unwrap_err();
+    ///
+    /// let listener = event.listen();
+    /// assert_eq!(event.try_notify(buffer), Ok(()));
+    /// assert_eq!(listener.wait(), [1, 2, 3]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_notify(&self, tag: T) -> Result<(), T> {
+        let mut tags = Some(tag).into_iter();
+        self.notify_with_tags(&mut tags);
+
+        match tags.next() {
+            Some(tag) => Err(tag),
+            None => Ok(()),
+        }
+    }
+
+    /// Notifies one listener per tag, handing each one its own tag.
+    ///
+    /// Listeners that are not notified yet receive the tags in the order they started listening,
+    /// as if by a series of `notify(1.additional().tag(tag))` calls under a single lock. This stops
+    /// once either the tags or the unnotified listeners run out, and returns the number of
+    /// listeners that were notified.
+    ///
+    /// A tag is only taken from the iterator once there is a listener to hand it to, so tags that
+    /// are left over stay in the iterator. Pass it by mutable reference to keep them, for example
+    /// to return unused permits.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::<usize>::with_tag();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// let mut permits = vec![10, 20, 30].into_iter();
+    /// assert_eq!(event.notify_with_tags(&mut permits), 2);
+    ///
+    /// assert_eq!(listener1.wait(), 10);
+    /// assert_eq!(listener2.wait(), 20);
+    /// assert_eq!(permits.next(), Some(30));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn notify_with_tags<I>(&self, tags: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Make sure the notification comes after whatever triggered it.
+        notify::full_fence();
+
+        let inner = unsafe { &*self.inner() };
+        inner.advance_epoch();
+        let count = inner.notify_with_tags(tags.into_iter());
+        inner.notify_parent();
+        inner.forwards.notify(count, true);
+        count
+    }
+
+    /// Creates a [`WeakEvent`] handle that does not keep the listener list alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let weak = event.downgrade();
+    /// assert!(weak.upgrade().is_some());
+    ///
+    /// drop(event);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[cfg(not(loom))]
+    pub fn downgrade(&self) -> WeakEvent<T> {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        WeakEvent {
+            inner: Arc::downgrade(&inner),
+        }
+    }
+
+    /// Closes the event.
+    ///
+    /// Every listener created with [`Event::listen_closeable()`] that has not been notified yet
+    /// completes with `Err(Closed)`, and so does every such listener created afterwards.
+    /// Listeners that were already notified still receive their notification.
+    ///
+    /// Listeners created with [`Event::listen()`] or [`listener!`] have no way to report that the
+    /// event was closed, so they complete with `T::default()` instead, as if they had been
+    /// notified. Use [`Event::close_with()`] to pick another tag.
+    ///
+    /// Closing cannot be undone. Returns `true` if this call closed the event, or `false` if it
+    /// was already closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Closed, Event};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    /// let first = event.listen_closeable();
+    /// let second = event.listen_closeable();
+    ///
+    /// event.notify(1);
+    /// assert!(event.close());
+    /// assert!(!event.close());
+    ///
+    /// // The first listener was notified before the event was closed.
+    /// assert_eq!(first.await, Ok(()));
+    /// assert_eq!(second.await, Err(Closed));
+    ///
+    /// // New listeners complete immediately.
+    /// assert_eq!(event.listen_closeable().await, Err(Closed));
+    /// event.listen().await;
+    /// # });
+    /// ```
+    pub fn close(&self) -> bool
+    where
+        T: Default,
+    {
+        self.close_with(T::default)
+    }
+
+    /// Closes the event, completing plain listeners with tags made by `tag`.
+    ///
+    /// This works like [`Event::close()`], except that listeners created with
+    /// [`Event::listen()`] or [`listener!`] complete with the result of `tag` instead of
+    /// `T::default()`. It is called once for every such listener.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Closed, Event};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::<Option<u32>>::with_tag();
+    /// let plain = event.listen();
+    /// let closeable = event.listen_closeable();
+    ///
+    /// assert!(event.close_with(|| None));
+    ///
+    /// assert_eq!(plain.await, None);
+    /// assert_eq!(closeable.await, Err(Closed));
+    /// # });
+    /// ```
+    pub fn close_with(&self, tag: fn() -> T) -> bool {
+        let inner = unsafe { &*self.inner() };
+
+        if inner.closed.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        inner.close(tag);
+        true
+    }
+
+    /// Tells whether the event has been closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert!(!event.is_closed());
+    ///
+    /// event.close();
+    /// assert!(event.is_closed());
+    /// ```
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.try_inner()
+            .map_or(false, |inner| inner.closed.load(Ordering::SeqCst))
+    }
+
+    /// Return a reference to the inner state if it has been initialized.
+    #[inline]
+    fn try_inner(&self) -> Option<&Inner<T>> {
+        let inner = self.inner.load(Ordering::Acquire);
+        unsafe { inner.as_ref() }
+    }
+
+    /// Returns a raw, initialized pointer to the inner state.
+    ///
+    /// This returns a raw pointer instead of reference because `from_raw`
+    /// requires raw/mut provenance: <https://github.com/rust-lang/rust/pull/67339>.
+    fn inner(&self) -> *const Inner<T> {
+        let mut inner = self.inner.load(Ordering::Acquire);
+
+        // If this is the first use, initialize the state.
+        if inner.is_null() {
+            // Allocate the state on the heap.
+            let new = Arc::new(Inner::<T>::new());
+
+            // Convert the state to a raw pointer.
+            let new = Arc::into_raw(new) as *mut Inner<T>;
+
+            // Replace the null pointer with the new state pointer.
+            inner = self
+                .inner
+                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
+                .unwrap_or_else(|x| x);
+
+            // Check if the old pointer value was indeed null.
+            if inner.is_null() {
+                // If yes, then use the new state pointer.
+                inner = new;
+            } else {
+                // If not, that means a concurrent operation has initialized the state.
+                // In that case, use the old pointer and deallocate the new one.
+                unsafe {
+                    drop(Arc::from_raw(new));
+                }
+            }
+        }
+
+        inner
+    }
+
+    /// Get the number of listeners currently listening to this [`Event`].
+    ///
+    /// This call returns the number of [`EventListener`]s that are currently listening to
+    /// this event. It does this by acquiring the internal event lock and reading the listener
+    /// count. Therefore it is only available for `std`-enabled platforms.
+    ///
+    /// # Caveats
+    ///
+    /// This function returns just a snapshot of the number of listeners at this point in time.
+    /// Due to the nature of multi-threaded CPUs, it is possible that this number will be
+    /// inaccurate by the time that this function returns.
+    ///
+    /// It is possible for the actual number to change at any point. Therefore, the number should
+    /// only ever be used as a hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// assert_eq!(event.total_listeners(), 0);
+    ///
+    /// let listener1 = event.listen();
+    /// assert_eq!(event.total_listeners(), 1);
+    ///
+    /// let listener2 = event.listen();
+    /// assert_eq!(event.total_listeners(), 2);
+    ///
+    /// drop(listener1);
+    /// drop(listener2);
+    /// assert_eq!(event.total_listeners(), 0);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn total_listeners(&self) -> usize {
+        if let Some(inner) = self.try_inner() {
+            inner.list.total_listeners()
+        } else {
+            0
+        }
+    }
+
+    /// Frees memory that the list of listeners no longer needs.
+    ///
+    /// Without the `std` feature, listeners are kept in a list that grows to fit the most
+    /// listeners the event ever had at once, and it doesn't shrink when they go away. This gives
+    /// the unused room back, for example after a burst of listeners. Room still taken by active
+    /// listeners can't be freed. If the list is busy, nothing is freed.
+    ///
+    /// With the `std` feature, every listener carries its own entry in the list, so this only
+    /// frees the room held for permits.
+    ///
+    /// See [`EventBuilder::capacity()`] for allocating room up front instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// let listeners = (0..100).map(|_| event.listen()).collect::<Vec<_>>();
+    /// drop(listeners);
+    ///
+    /// event.shrink_to_fit();
+    /// ```
+    pub fn shrink_to_fit(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.shrink_to_fit();
+        }
+    }
+}
+
+impl Event<()> {
+    /// Creates a new [`Event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`Event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`Event`] with room for at least `capacity` listeners.
+    ///
+    /// This is a shorthand for `Event::builder().capacity(capacity).build()`. See
+    /// [`EventBuilder::capacity()`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::with_capacity(16);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::builder().capacity(capacity).build()
+    }
+
+    /// Notifies a number of active listeners without emitting a `SeqCst` fence.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify_additional()`], this method only makes sure *at least* `n`
+    /// listeners among the active ones are notified.
+    ///
+    /// Unlike [`Event::notify()`], this method does not emit a `SeqCst` fence.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_relaxed(1);
+    ///
+    /// // New way:
+    /// event.notify(1.relaxed());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify_relaxed(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_relaxed(2);
+    /// ```
+    #[inline]
+    pub fn notify_relaxed(&self, n: usize) -> usize {
+        self.notify(n.relaxed())
+    }
+
+    /// Notifies a number of active and still unnotified listeners.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
+    /// were previously unnotified.
+    ///
+    /// This method emits a `SeqCst` fence before notifying listeners.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_additional(1);
+    ///
+    /// // New way:
+    /// event.notify(1.additional());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify_additional(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_additional(1);
+    /// event.notify_additional(1);
+    /// ```
+    #[inline]
+    pub fn notify_additional(&self, n: usize) -> usize {
+        self.notify(n.additional())
+    }
+
+    /// Notifies a number of active and still unnotified listeners without emitting a `SeqCst`
+    /// fence.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
+    /// were previously unnotified.
+    ///
+    /// Unlike [`Event::notify_additional()`], this method does not emit a `SeqCst` fence.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_additional_relaxed(1);
+    ///
+    /// // New way:
+    /// event.notify(1.additional().relaxed());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_additional_relaxed(1);
+    /// event.notify_additional_relaxed(1);
+    /// ```
+    #[inline]
+    pub fn notify_additional_relaxed(&self, n: usize) -> usize {
+        self.notify(n.additional().relaxed())
+    }
+
+    /// Notifies a number of additional listeners, unless a listener is already notified.
+    ///
+    /// This works like [`Event::notify_additional()`], but the notification is merged into a
+    /// pending one instead of spilling over to more listeners. As long as some listener has been
+    /// notified but hasn't observed its notification yet, this does nothing and returns `0`.
+    /// Producers that signal on every change can use this to avoid waking up a crowd of
+    /// consumers that will find nothing left to do.
+    ///
+    /// This method emits a `SeqCst` fence before notifying listeners.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_coalesced(1);
+    ///
+    /// // New way:
+    /// event.notify(1.additional().coalesced());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify_coalesced(1), 1);
+    ///
+    /// // `listener1` is still notified, so `listener2` is left alone.
+    /// assert_eq!(event.notify_coalesced(1), 0);
+    ///
+    /// listener1.wait();
+    /// assert_eq!(event.notify_coalesced(1), 1);
+    /// listener2.wait();
+    /// ```
+    #[inline]
+    pub fn notify_coalesced(&self, n: usize) -> usize {
+        self.notify(n.additional().coalesced())
+    }
+
+    /// Notifies a number of active listeners from an interrupt handler.
+    ///
+    /// This works like [`Event::notify()`], except that it never allocates, never waits for the
+    /// internal lock and finishes in bounded time, so it can be called from interrupt handlers
+    /// and signal handlers.
+    ///
+    /// If the list of listeners is not locked, the listeners are notified right away. Otherwise,
+    /// for example when the interrupt fired while the interrupted code was listening to the same
+    /// event, the notification is recorded and delivered as soon as the list is unlocked. Several
+    /// deferred notifications combine like calls to [`Event::notify()`] do: the largest one wins.
+    ///
+    /// Listeners are notified a few at a time, and their tasks are woken from a buffer on the
+    /// stack. Notifications that would need to store a permit for a future listener are left for
+    /// the next regular operation on the event. With [`WakeOrder::Random`], picking a listener
+    /// walks the list, so the time taken grows with the number of listeners.
+    ///
+    /// Notifications from other threads that are waiting in the `no_std` backup queue are left
+    /// for the next regular operation on the event to process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // Called from an interrupt handler.
+    /// event.notify_from_isr(1);
+    /// assert!(event.is_notified());
+    /// ```
+    pub fn notify_from_isr(&self, n: usize) {
+        if let Some(inner) = self.try_inner() {
+            // Record the notification first, so that a concurrent lock holder delivers it if we
+            // can't lock the list ourselves.
+            inner.pending.fetch_max(n, Ordering::SeqCst);
+            sync::atomic::fence(Ordering::SeqCst);
+            inner.advance_epoch();
+            inner.notify_pending();
+
+            if let Some(parent) = &inner.parent {
+                parent.event.notify_from_isr(parent.count);
+            }
+        }
+    }
+}
+
+impl<T> Drop for Event<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.with_mut(|&mut inner| {
+            // If the state pointer has been initialized, drop it.
+            if !inner.is_null() {
+                unsafe {
+                    drop(Arc::from_raw(inner));
+                }
+            }
+        })
+    }
+}
+
+/// A weak handle to an [`Event`].
+///
+/// This is created by [`Event::downgrade()`]. It does not keep the listener list alive, so it can
+/// be stored in long-lived registries without preventing the event from being torn down once every
+/// [`Event`] handle has been dropped.
+#[cfg(not(loom))]
+pub struct WeakEvent<T = ()> {
+    inner: sync::Weak<Inner<T>>,
+}
+
+#[cfg(not(loom))]
+unsafe impl<T: Send> Send for WeakEvent<T> {}
+#[cfg(not(loom))]
+unsafe impl<T: Send> Sync for WeakEvent<T> {}
+
+#[cfg(not(loom))]
+impl<T> core::panic::UnwindSafe for WeakEvent<T> {}
+#[cfg(not(loom))]
+impl<T> core::panic::RefUnwindSafe for WeakEvent<T> {}
+
+#[cfg(not(loom))]
+impl<T> fmt::Debug for WeakEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakEvent").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(loom))]
+impl<T> Clone for WeakEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(not(loom))]
+impl<T> WeakEvent<T> {
+    /// Attempts to get an [`Event`] handle back.
+    ///
+    /// Returns `None` if the listener list has been torn down, which happens once every [`Event`]
+    /// handle and every listener of the event has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let weak = event.downgrade();
+    ///
+    /// let listener = event.listen();
+    /// assert_eq!(weak.upgrade().unwrap().notify(1), 1);
+    /// ```
+    pub fn upgrade(&self) -> Option<Event<T>> {
+        self.inner.upgrade().map(|inner| Event {
+            inner: AtomicPtr::new(Arc::into_raw(inner) as *mut Inner<T>),
+        })
+    }
+}
+
+/// A handle that is listening to an [`Event`].
+///
+/// This trait represents a type waiting for a notification from an [`Event`]. See the
+/// [`EventListener`] type for more documentation on this trait's usage.
+pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
+    /// Blocks until a notification is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// // Notify `listener`.
+    /// event.notify(1);
+    ///
+    /// // Receive the notification.
+    /// listener.wait();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait(self) -> T;
+
+    /// Blocks until a notification is received or a timeout is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert!(listener.wait_timeout(Duration::from_secs(1)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[deprecated(
+        since = "5.4.0",
+        note = "use `try_wait_timeout()`, which can't be confused with an `Option` tag"
+    )]
+    fn wait_timeout(self, timeout: Duration) -> Option<T>;
+
+    /// Blocks until a notification is received or a timeout is reached.
+    ///
+    /// Returns the tag of the notification, or an error if the timeout was reached first. Unlike
+    /// [`Listener::wait_timeout()`], the result can't be confused with a tag that is itself an
+    /// [`Option`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener, WaitTimeoutError};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert_eq!(
+    ///     listener.try_wait_timeout(Duration::from_millis(10)),
+    ///     Err(WaitTimeoutError)
+    /// );
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn try_wait_timeout(self, timeout: Duration) -> Result<T, WaitTimeoutError>;
+
+    /// Blocks until a notification is received or a timeout is reached, and returns how much of
+    /// the timeout was left.
+    ///
+    /// This works like [`Listener::try_wait_timeout()`], but also returns the part of `timeout`
+    /// that had not elapsed when the notification was received. Code that spreads one timeout
+    /// over several waits can pass it on to the next wait, without measuring the time again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut timeout = Duration::from_secs(1);
+    ///
+    /// for _ in 0..3 {
+    ///     let listener = event.listen();
+    ///     event.notify(1);
+    ///
+    ///     let ((), remaining) = listener.wait_timeout_remaining(timeout).unwrap();
+    ///     assert!(remaining <= timeout);
+    ///     timeout = remaining;
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_timeout_remaining(self, timeout: Duration) -> Result<(T, Duration), WaitTimeoutError>;
+
+    /// Blocks until a notification is received or a deadline is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert!(listener.wait_deadline(Instant::now() + Duration::from_secs(1)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[deprecated(
+        since = "5.4.0",
+        note = "use `try_wait_deadline()`, which can't be confused with an `Option` tag"
+    )]
+    fn wait_deadline(self, deadline: Instant) -> Option<T>;
+
+    /// Blocks until a notification is received or a deadline is reached.
+    ///
+    /// Returns the tag of the notification, or an error if the deadline was reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<Option<u32>>::with_tag();
+    /// let listener = event.listen();
+    ///
+    /// // A `None` tag is told apart from a timeout.
+    /// event.notify(1.tag(None));
+    /// let deadline = Instant::now() + Duration::from_secs(1);
+    /// assert_eq!(listener.try_wait_deadline(deadline), Ok(None));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn try_wait_deadline(self, deadline: Instant) -> Result<T, WaitTimeoutError>;
+
+    /// Blocks until a notification is received or a deadline on the given [`Clock`] is reached.
+    ///
+    /// Returns the tag of the notification, or an error if the deadline was reached first.
+    /// Unlike [`Listener::try_wait_deadline()`], this doesn't need `std`. The listener is polled
+    /// in a loop, with [`Clock::idle()`] called in between.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use event_listener::{Clock, Event, Listener, WaitTimeoutError};
+    ///
+    /// struct StdClock;
+    ///
+    /// impl Clock for StdClock {
+    ///     type Instant = Instant;
+    ///
+    ///     fn now(&self) -> Instant {
+    ///         Instant::now()
+    ///     }
+    /// }
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert_eq!(
+    ///     listener.wait_deadline_with(&StdClock, Instant::now()),
+    ///     Err(WaitTimeoutError)
+    /// );
+    /// ```
+    fn wait_deadline_with<C: Clock>(
+        self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<T, WaitTimeoutError>
+    where
+        Self: Sized;
+
+    /// Blocks until a notification is received or a timeout on the given [`Clock`] is reached.
+    ///
+    /// Returns the tag of the notification, or an error if the timeout was reached first. The
+    /// deadline is computed by adding `timeout` to the current time of the clock. See
+    /// [`Listener::wait_deadline_with()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Clock, Event, Listener, WaitTimeoutError};
+    ///
+    /// struct StdClock;
+    ///
+    /// impl Clock for StdClock {
+    ///     type Instant = Instant;
+    ///
+    ///     fn now(&self) -> Instant {
+    ///         Instant::now()
+    ///     }
+    /// }
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert_eq!(
+    ///     listener.wait_timeout_with(&StdClock, Duration::from_millis(10)),
+    ///     Err(WaitTimeoutError)
+    /// );
+    /// ```
+    fn wait_timeout_with<C: Clock, D>(self, clock: &C, timeout: D) -> Result<T, WaitTimeoutError>
+    where
+        Self: Sized,
+        C::Instant: core::ops::Add<D, Output = C::Instant>;
+
+    /// Blocks until a notification is received, calling `idle` while waiting.
+    ///
+    /// Unlike [`Listener::wait()`], this doesn't need `std` or an operating system. The listener
+    /// is polled in a loop, with `idle` called in between. On bare-metal targets, `idle` can put
+    /// the core to sleep until the next interrupt or event, for example with `wfe()` and the
+    /// `cortex-m` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // Stand in for an interrupt handler that fires while we're idle.
+    /// let mut idles = 0;
+    /// let tag = listener.wait_with(|| {
+    ///     idles += 1;
+    ///     event.notify(1);
+    /// });
+    ///
+    /// assert_eq!(tag, ());
+    /// assert_eq!(idles, 1);
+    /// ```
+    fn wait_with<F: FnMut()>(self, idle: F) -> T
+    where
+        Self: Sized;
+
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// event.notify(1);
+    ///
+    /// assert!(listener1.discard());
+    /// assert!(!listener2.discard());
+    /// ```
+    fn discard(self) -> bool;
+
+    /// Drops this listener and returns the tag of its notification (if any) without notifying
+    /// another active listener.
+    ///
+    /// A listener that is dropped while notified passes its notification, tag included, on to the
+    /// next listener. If there is none, the tag is dropped. When tags carry resources such as
+    /// permits or buffers, this method can be used instead to recover the tag and recycle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<Vec<u8>>::with_tag();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// event.notify(1.tag_with(|| Vec::with_capacity(1024)));
+    ///
+    /// let buffer = listener1.discard_tag().unwrap();
+    /// assert!(buffer.capacity() >= 1024);
+    /// assert_eq!(listener2.discard_tag(), None);
+    /// ```
+    fn discard_tag(self) -> Option<T>;
+
+    /// Checks whether this listener has been notified, without blocking.
+    ///
+    /// If the listener has been notified, this returns the tag of the notification and the
+    /// listener is done, as if it had been awaited. Otherwise, this returns `None` and the listener
+    /// stays registered, without changing which task it wakes up. This lets polling-style
+    /// integrations probe a listener without a [`Context`] or a dummy waker.
+    ///
+    /// A listener that is done returns `None`. On `no_std` targets, a listener may be reported as
+    /// not notified yet while another thread holds the internal lock.
+    ///
+    /// [`Context`]: core::task::Context
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// assert_eq!(listener.check(), None);
+    /// event.notify(1);
+    /// assert_eq!(listener.check(), Some(()));
+    ///
+    /// // The notification has been consumed.
+    /// assert_eq!(listener.check(), None);
+    /// ```
+    fn check(&mut self) -> Option<T>;
+
+    /// Calls `f` with the tag of this listener's notification, without consuming it.
+    ///
+    /// Returns `None` if the listener hasn't been notified. Otherwise, the listener stays notified
+    /// and can be awaited to take the tag, or dropped to pass the notification on to another
+    /// listener. This lets routing logic decide whether a notification
+    /// is meant for this listener without taking ownership of the tag.
+    ///
+    /// The closure is called with the list of listeners locked, so it should be cheap and must
+    /// not use the event. On `no_std` targets, a listener may be reported as not notified yet
+    /// while another thread holds the internal lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<String>::with_tag();
+    /// let mut listener = event.listen();
+    ///
+    /// assert_eq!(listener.peek_tag(|tag| tag.len()), None);
+    ///
+    /// event.notify(1.tag("for someone else".to_string()));
+    /// if listener.peek_tag(|tag| tag.starts_with("for me")) == Some(false) {
+    ///     // Not our business, let another listener have it.
+    ///     let other = event.listen();
+    ///     drop(listener);
+    ///     assert_eq!(other.wait(), "for someone else");
+    /// }
+    /// ```
+    fn peek_tag<R, F: FnOnce(&T) -> R>(&mut self, f: F) -> Option<R>;
+
+    /// Registers a waker to be woken up once this listener is notified.
+    ///
+    /// This is what polling the listener does, minus the [`Context`]: hand-written futures and
+    /// executors can call it directly with the waker they already have. The waker is only cloned
+    /// if it wouldn't wake the same task as the one that is already registered.
+    ///
+    /// If the listener has been notified, this returns [`Registration::Notified`] with the tag of
+    /// the notification, and the listener is done, as if it had been awaited. Otherwise, this
+    /// returns [`Registration::Registered`].
+    ///
+    /// [`Context`]: core::task::Context
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener is already done.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, Registration};
+    /// # let waker = waker_fn::waker_fn(|| ());
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// assert_eq!(listener.register(&waker), Registration::Registered);
+    /// event.notify(1);
+    /// assert_eq!(listener.register(&waker), Registration::Notified(()));
+    /// ```
+    fn register(&mut self, waker: &Waker) -> Registration<T>;
+
+    /// Registers this listener to its event again after it has received a notification.
+    ///
+    /// This reuses the listener, including its allocation, instead of creating a new one in every
+    /// iteration of a wait loop. If the listener is still registered, this does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// for _ in 0..3 {
+    ///     event.notify(1);
+    ///     (&mut listener).await;
+    ///     listener.rearm();
+    /// }
+    /// # });
+    /// ```
+    fn rearm(&mut self);
+
+    /// Returns `true` if this listener listens to the given `Event`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// assert!(listener.listens_to(&event));
+    /// ```
+    fn listens_to(&self, event: &Event<T>) -> bool;
+
+    /// Returns `true` if both listeners listen to the same `Event`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert!(listener1.same_event(&listener2));
+    /// ```
+    fn same_event(&self, other: &Self) -> bool;
+}
+
+/// Implement the `Listener` trait using the underlying `InnerListener`.
+macro_rules! forward_impl_to_listener {
+    ($gen:ident => $ty:ty) => {
+        impl<$gen> crate::Listener<$gen> for $ty {
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn wait(mut self) -> $gen {
+                self.listener_mut()
+                    .wait_internal(None, false)
+                    .map(crate::expect_open)
+                    .unwrap()
+            }
+
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn wait_timeout(self, timeout: std::time::Duration) -> Option<$gen> {
+                self.try_wait_timeout(timeout).ok()
+            }
+
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn try_wait_timeout(
+                mut self,
+                timeout: std::time::Duration,
+            ) -> Result<$gen, crate::WaitTimeoutError> {
+                self.listener_mut()
+                    .wait_internal(std::time::Instant::now().checked_add(timeout), false)
+                    .map(crate::expect_open)
+                    .ok_or(crate::WaitTimeoutError)
+            }
+
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn wait_timeout_remaining(
+                mut self,
+                timeout: std::time::Duration,
+            ) -> Result<($gen, std::time::Duration), crate::WaitTimeoutError> {
+                let start = std::time::Instant::now();
+                let tag = self
+                    .listener_mut()
+                    .wait_internal(start.checked_add(timeout), false)
+                    .map(crate::expect_open)
+                    .ok_or(crate::WaitTimeoutError)?;
+                Ok((tag, timeout.saturating_sub(start.elapsed())))
+            }
+
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn wait_deadline(self, deadline: std::time::Instant) -> Option<$gen> {
+                self.try_wait_deadline(deadline).ok()
+            }
+
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            fn try_wait_deadline(
+                mut self,
+                deadline: std::time::Instant,
+            ) -> Result<$gen, crate::WaitTimeoutError> {
+                self.listener_mut()
+                    .wait_internal(Some(deadline), false)
+                    .map(crate::expect_open)
+                    .ok_or(crate::WaitTimeoutError)
+            }
+
+            fn wait_deadline_with<C: crate::Clock>(
+                mut self,
+                clock: &C,
+                deadline: C::Instant,
+            ) -> Result<$gen, crate::WaitTimeoutError> {
+                self.listener_mut()
+                    .wait_with_clock(clock, deadline, false)
+                    .map(crate::expect_open)
+                    .ok_or(crate::WaitTimeoutError)
+            }
+
+            fn wait_timeout_with<C: crate::Clock, D>(
+                self,
+                clock: &C,
+                timeout: D,
+            ) -> Result<$gen, crate::WaitTimeoutError>
+            where
+                C::Instant: core::ops::Add<D, Output = C::Instant>,
+            {
+                let deadline = clock.now() + timeout;
+                self.wait_deadline_with(clock, deadline)
+            }
+
+            fn wait_with<F: FnMut()>(mut self, mut idle: F) -> $gen {
+                self.listener_mut()
+                    .wait_polling(
+                        || {
+                            idle();
+                            true
+                        },
+                        false,
+                    )
+                    .map(crate::expect_open)
+                    .unwrap()
+            }
+
+            fn discard(mut self) -> bool {
+                self.listener_mut().discard()
+            }
+
+            fn discard_tag(mut self) -> Option<$gen> {
+                self.listener_mut().discard_tag()
+            }
+
+            fn check(&mut self) -> Option<$gen> {
+                self.listener_mut().check(false).map(crate::expect_open)
+            }
+
+            fn peek_tag<R, F: FnOnce(&$gen) -> R>(&mut self, f: F) -> Option<R> {
+                self.listener_mut().peek_tag(f)
+            }
+
+            fn register(&mut self, waker: &core::task::Waker) -> crate::Registration<$gen> {
+                self.listener_mut().register_waker(waker, false)
+            }
+
+            fn rearm(&mut self) {
+                self.listener_mut().rearm();
+            }
+
+            #[inline]
+            fn listens_to(&self, event: &Event<$gen>) -> bool {
+                core::ptr::eq::<Inner<$gen>>(
+                    &*self.listener().event,
+                    event.inner.load(core::sync::atomic::Ordering::Acquire),
+                )
+            }
+
+            #[inline]
+            fn same_event(&self, other: &$ty) -> bool {
+                core::ptr::eq::<Inner<$gen>>(&*self.listener().event, &*other.listener().event)
+            }
+        }
+
+        impl<$gen> Future for $ty {
+            type Output = $gen;
+
+            #[inline]
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<$gen> {
+                self.listener_mut()
+                    .poll_internal(cx, false)
+                    .map(crate::expect_open)
+            }
+        }
+    };
+}
+
+/// A guard waiting for a notification from an [`Event`].
+///
+/// There are two ways for a listener to wait for a notification:
+///
+/// 1. In an asynchronous manner using `.await`.
+/// 2. In a blocking manner by calling [`EventListener::wait()`] on it.
+///
+/// If a notified listener is dropped without receiving a notification, dropping will notify
+/// another active listener. Whether one *additional* listener will be notified depends on what
+/// kind of notification was delivered.
+///
+/// See the [`Listener`] trait for the functionality exposed by this type.
+///
+/// This structure allocates the listener on the heap.
+pub struct EventListener<T = ()> {
+    listener: ListenerBox<T>,
+}
+
+unsafe impl<T: Send> Send for EventListener<T> {}
+unsafe impl<T: Send> Sync for EventListener<T> {}
+
+impl<T> core::panic::UnwindSafe for EventListener<T> {}
+impl<T> core::panic::RefUnwindSafe for EventListener<T> {}
+impl<T> Unpin for EventListener<T> {}
+
+impl<T> fmt::Debug for EventListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventListener")
+            .field("state", &format_args!("{}", self.listener.state_name()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T> defmt::Format for EventListener<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "EventListener {{ state: {=str} }}",
+            self.listener.state_name()
+        )
+    }
+}
+
+impl<T> EventListener<T> {
+    /// Creates a listener that is not attached to any event.
+    ///
+    /// Use [`EventListener::listen_to()`] to start listening to an event. Waiting on the listener
+    /// before that panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, EventListener, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = EventListener::empty();
+    /// assert!(!listener.listens_to(&event));
+    ///
+    /// listener.listen_to(&event);
+    /// assert!(listener.listens_to(&event));
+    /// ```
+    pub fn empty() -> Self {
+        Self {
+            listener: ListenerBox::new(Arc::new(Inner::new())),
+        }
+    }
+
+    /// Starts listening to the given event, reusing this listener's allocation.
+    ///
+    /// If the listener is currently registered to an event, it is removed from it first. A
+    /// notification that it has received but not yet consumed is passed on to another listener of
+    /// that event, just like when the listener is dropped.
+    ///
+    /// This can be called again after the listener completes, so that a long-lived future can
+    /// keep one listener and attach it to whichever event it needs next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, EventListener};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let first = Event::new();
+    /// let second = Event::new();
+    /// let mut listener = EventListener::empty();
+    ///
+    /// listener.listen_to(&first);
+    /// first.notify(1);
+    /// (&mut listener).await;
+    ///
+    /// listener.listen_to(&second);
+    /// second.notify(1);
+    /// listener.await;
+    /// # });
+    /// ```
+    pub fn listen_to(&mut self, event: &Event<T>) {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(event.inner()) });
+        let mut this = self.listener.as_mut().project();
+
+        // Leave the current event, passing on any unconsumed notification.
+        this.event.remove(this.listener.as_mut(), true);
+
+        // Join the new event.
+        *this.event = Arc::clone(&inner);
+        this.event.insert(this.listener);
+    }
+
+    #[inline]
+    fn listener(&self) -> &InnerListener<T, Arc<Inner<T>>> {
+        &self.listener
+    }
+
+    #[inline]
+    fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, Arc<Inner<T>>>> {
+        self.listener.as_mut()
+    }
+}
+
+forward_impl_to_listener! { T => EventListener<T> }
+forward_impl_to_listener! { T => DualListener<T> }
+forward_impl_to_listener! { T => ShardedListener<T> }
+
+/// A guard waiting for a notification from an [`Event`], or for the event to be closed.
+///
+/// This is created by [`Event::listen_closeable()`]. It works like [`EventListener`], except that
+/// waiting on it completes with `Err(Closed)` once [`Event::close()`] has been called.
+pub struct CloseableListener<T = ()> {
+    listener: ListenerBox<T>,
+}
+
+unsafe impl<T: Send> Send for CloseableListener<T> {}
+unsafe impl<T: Send> Sync for CloseableListener<T> {}
+
+impl<T> core::panic::UnwindSafe for CloseableListener<T> {}
+impl<T> core::panic::RefUnwindSafe for CloseableListener<T> {}
+impl<T> Unpin for CloseableListener<T> {}
+
+impl<T> fmt::Debug for CloseableListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CloseableListener")
+            .field("state", &format_args!("{}", self.listener.state_name()))
+            .finish()
+    }
+}
+
+impl<T> CloseableListener<T> {
+    /// Blocks until a notification is received or the event is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Closed, Event};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_closeable();
+    ///
+    /// event.close();
+    /// assert_eq!(listener.wait(), Err(Closed));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(mut self) -> Result<T, Closed> {
+        self.listener.as_mut().wait_internal(None, true).unwrap()
+    }
+
+    /// Blocks until a notification is received, the event is closed or a timeout is reached.
+    ///
+    /// Returns `None` if the timeout was reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_closeable();
+    ///
+    /// // There are no notification so this times out.
+    /// assert!(listener.wait_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(mut self, timeout: Duration) -> Option<Result<T, Closed>> {
+        self.listener
+            .as_mut()
+            .wait_internal(Instant::now().checked_add(timeout), true)
+    }
+
+    /// Blocks until a notification is received, the event is closed or a deadline is reached.
+    ///
+    /// Returns `None` if the deadline was reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Closed, Event};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_closeable();
+    ///
+    /// event.close();
+    /// assert_eq!(
+    ///     listener.wait_deadline(Instant::now() + Duration::from_secs(1)),
+    ///     Some(Err(Closed))
+    /// );
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_deadline(mut self, deadline: Instant) -> Option<Result<T, Closed>> {
+        self.listener.as_mut().wait_internal(Some(deadline), true)
+    }
+
+    /// Blocks until a notification is received, the event is closed or a deadline on the given
+    /// [`Clock`] is reached.
+    ///
+    /// Returns `None` if the deadline was reached. See [`Listener::wait_deadline_with()`] for
+    /// more details.
+    ///
+    /// # Examples
     ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
+    /// ```
+    /// use std::time::Instant;
+    /// use event_listener::{Clock, Closed, Event};
     ///
-    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
-    /// were previously unnotified.
+    /// struct StdClock;
     ///
-    /// Unlike [`Event::notify_additional()`], this method does not emit a `SeqCst` fence.
+    /// impl Clock for StdClock {
+    ///     type Instant = Instant;
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    ///     fn now(&self) -> Instant {
+    ///         Instant::now()
+    ///     }
+    /// }
     ///
-    /// ```
-    /// use event_listener::{IntoNotification, Event};
     /// let event = Event::new();
+    /// let listener = event.listen_closeable();
     ///
-    /// // Old way:
-    /// event.notify_additional_relaxed(1);
-    ///
-    /// // New way:
-    /// event.notify(1.additional().relaxed());
+    /// event.close();
+    /// assert_eq!(
+    ///     listener.wait_deadline_with(&StdClock, Instant::now()),
+    ///     Some(Err(Closed))
+    /// );
     /// ```
+    pub fn wait_deadline_with<C: Clock>(
+        mut self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Option<Result<T, Closed>> {
+        self.listener
+            .as_mut()
+            .wait_with_clock(clock, deadline, true)
+    }
+
+    /// Blocks until a notification is received, the event is closed or a timeout on the given
+    /// [`Clock`] is reached.
+    ///
+    /// Returns `None` if the timeout was reached. See [`Listener::wait_timeout_with()`] for
+    /// more details.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::Event;
-    /// use std::sync::atomic::{self, Ordering};
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Clock, Event};
     ///
-    /// let event = Event::new();
+    /// struct StdClock;
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1);
+    /// impl Clock for StdClock {
+    ///     type Instant = Instant;
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    ///     fn now(&self) -> Instant {
+    ///         Instant::now()
+    ///     }
+    /// }
     ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// let event = Event::new();
+    /// let listener = event.listen_closeable();
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify_additional_relaxed(1);
-    /// event.notify_additional_relaxed(1);
+    /// event.notify(1);
+    /// assert_eq!(
+    ///     listener.wait_timeout_with(&StdClock, Duration::from_secs(1)),
+    ///     Some(Ok(()))
+    /// );
     /// ```
-    #[inline]
-    pub fn notify_additional_relaxed(&self, n: usize) -> usize {
-        self.notify(n.additional().relaxed())
+    pub fn wait_timeout_with<C: Clock, D>(self, clock: &C, timeout: D) -> Option<Result<T, Closed>>
+    where
+        C::Instant: core::ops::Add<D, Output = C::Instant>,
+    {
+        let deadline = clock.now() + timeout;
+        self.wait_deadline_with(clock, deadline)
     }
-}
 
-impl<T> Drop for Event<T> {
-    #[inline]
-    fn drop(&mut self) {
-        self.inner.with_mut(|&mut inner| {
-            // If the state pointer has been initialized, drop it.
-            if !inner.is_null() {
-                unsafe {
-                    drop(Arc::from_raw(inner));
-                }
-            }
-        })
+    /// Blocks until a notification is received or the event is closed, calling `idle` while
+    /// waiting.
+    ///
+    /// See [`Listener::wait_with()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Closed, Event};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_closeable();
+    ///
+    /// assert_eq!(listener.wait_with(|| { event.close(); }), Err(Closed));
+    /// ```
+    pub fn wait_with<F: FnMut()>(mut self, mut idle: F) -> Result<T, Closed> {
+        self.listener
+            .as_mut()
+            .wait_polling(
+                || {
+                    idle();
+                    true
+                },
+                true,
+            )
+            .unwrap()
     }
-}
 
-/// A handle that is listening to an [`Event`].
-///
-/// This trait represents a type waiting for a notification from an [`Event`]. See the
-/// [`EventListener`] type for more documentation on this trait's usage.
-pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
-    /// Blocks until a notification is received.
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::{Event, Listener};
+    /// use event_listener::Event;
     ///
     /// let event = Event::new();
-    /// let mut listener = event.listen();
+    /// let listener1 = event.listen_closeable();
+    /// let listener2 = event.listen_closeable();
     ///
-    /// // Notify `listener`.
     /// event.notify(1);
     ///
-    /// // Receive the notification.
-    /// listener.wait();
+    /// assert!(listener1.discard());
+    /// assert!(!listener2.discard());
     /// ```
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait(self) -> T;
+    pub fn discard(mut self) -> bool {
+        self.listener.as_mut().discard()
+    }
 
-    /// Blocks until a notification is received or a timeout is reached.
-    ///
-    /// Returns `true` if a notification was received.
+    /// Drops this listener and returns the tag of its notification (if any) without notifying
+    /// another active listener.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::time::Duration;
-    /// use event_listener::{Event, Listener};
+    /// use event_listener::{Event, IntoNotification};
     ///
-    /// let event = Event::new();
-    /// let mut listener = event.listen();
+    /// let event = Event::<u32>::with_tag();
+    /// let listener1 = event.listen_closeable();
+    /// let listener2 = event.listen_closeable();
     ///
-    /// // There are no notification so this times out.
-    /// assert!(listener.wait_timeout(Duration::from_secs(1)).is_none());
+    /// event.notify(1.tag(7));
+    ///
+    /// assert_eq!(listener1.discard_tag(), Some(7));
+    /// assert_eq!(listener2.discard_tag(), None);
     /// ```
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_timeout(self, timeout: Duration) -> Option<T>;
+    pub fn discard_tag(mut self) -> Option<T> {
+        self.listener.as_mut().discard_tag()
+    }
 
-    /// Blocks until a notification is received or a deadline is reached.
+    /// Checks whether this listener has been notified or the event has been closed, without
+    /// blocking.
     ///
-    /// Returns `true` if a notification was received.
+    /// Returns `None` if neither has happened yet. See [`Listener::check()`] for more details.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::time::{Duration, Instant};
-    /// use event_listener::{Event, Listener};
+    /// use event_listener::{Closed, Event};
     ///
     /// let event = Event::new();
-    /// let mut listener = event.listen();
+    /// let mut listener = event.listen_closeable();
     ///
-    /// // There are no notification so this times out.
-    /// assert!(listener.wait_deadline(Instant::now() + Duration::from_secs(1)).is_none());
+    /// assert_eq!(listener.check(), None);
+    /// event.close();
+    /// assert_eq!(listener.check(), Some(Err(Closed)));
     /// ```
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_deadline(self, deadline: Instant) -> Option<T>;
+    pub fn check(&mut self) -> Option<Result<T, Closed>> {
+        self.listener.as_mut().check(true)
+    }
 
-    /// Drops this listener and discards its notification (if any) without notifying another
-    /// active listener.
+    /// Calls `f` with the tag of this listener's notification, without consuming it.
     ///
-    /// Returns `true` if a notification was discarded.
+    /// Returns `None` if the listener hasn't been notified. See [`Listener::peek_tag()`] for more
+    /// details.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::{Event, Listener};
-    ///
-    /// let event = Event::new();
-    /// let mut listener1 = event.listen();
-    /// let mut listener2 = event.listen();
+    /// use event_listener::{Event, IntoNotification};
     ///
-    /// event.notify(1);
+    /// let event = Event::<u32>::with_tag();
+    /// let mut listener = event.listen_closeable();
     ///
-    /// assert!(listener1.discard());
-    /// assert!(!listener2.discard());
+    /// event.notify(1.tag(7));
+    /// assert_eq!(listener.peek_tag(|&tag| tag == 7), Some(true));
+    /// assert_eq!(listener.check(), Some(Ok(7)));
     /// ```
-    fn discard(self) -> bool;
+    pub fn peek_tag<R>(&mut self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.listener.as_mut().peek_tag(f)
+    }
 
-    /// Returns `true` if this listener listens to the given `Event`.
+    /// Registers a waker to be woken up once this listener is notified or the event is closed.
+    ///
+    /// See [`Listener::register()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the listener is already done.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::{Event, Listener};
+    /// use event_listener::{Event, Registration};
+    /// # let waker = waker_fn::waker_fn(|| ());
     ///
     /// let event = Event::new();
-    /// let listener = event.listen();
+    /// let mut listener = event.listen_closeable();
     ///
-    /// assert!(listener.listens_to(&event));
+    /// assert_eq!(listener.register(&waker), Registration::Registered);
+    /// event.close();
+    /// assert_eq!(listener.register(&waker), Registration::Closed);
     /// ```
-    fn listens_to(&self, event: &Event<T>) -> bool;
+    pub fn register(&mut self, waker: &Waker) -> Registration<T> {
+        self.listener.as_mut().register_waker(waker, true)
+    }
 
-    /// Returns `true` if both listeners listen to the same `Event`.
+    /// Returns `true` if this listener listens to the given `Event`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::{Event, Listener};
+    /// use event_listener::Event;
     ///
     /// let event = Event::new();
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
+    /// let listener = event.listen_closeable();
     ///
-    /// assert!(listener1.same_event(&listener2));
+    /// assert!(listener.listens_to(&event));
     /// ```
-    fn same_event(&self, other: &Self) -> bool;
+    #[inline]
+    pub fn listens_to(&self, event: &Event<T>) -> bool {
+        ptr::eq::<Inner<T>>(&*self.listener.event, event.inner.load(Ordering::Acquire))
+    }
 }
 
-/// Implement the `Listener` trait using the underlying `InnerListener`.
-macro_rules! forward_impl_to_listener {
-    ($gen:ident => $ty:ty) => {
-        impl<$gen> crate::Listener<$gen> for $ty {
-            #[cfg(all(feature = "std", not(target_family = "wasm")))]
-            fn wait(mut self) -> $gen {
-                self.listener_mut().wait_internal(None).unwrap()
-            }
+impl<T> Future for CloseableListener<T> {
+    type Output = Result<T, Closed>;
 
-            #[cfg(all(feature = "std", not(target_family = "wasm")))]
-            fn wait_timeout(mut self, timeout: std::time::Duration) -> Option<$gen> {
-                self.listener_mut()
-                    .wait_internal(std::time::Instant::now().checked_add(timeout))
-            }
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.listener.as_mut().poll_internal(cx, true)
+    }
+}
 
-            #[cfg(all(feature = "std", not(target_family = "wasm")))]
-            fn wait_deadline(mut self, deadline: std::time::Instant) -> Option<$gen> {
-                self.listener_mut().wait_internal(Some(deadline))
-            }
+/// An error returned by a [`CloseableListener`] when its [`Event`] has been closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Closed;
 
-            fn discard(mut self) -> bool {
-                self.listener_mut().discard()
-            }
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("event closed")
+    }
+}
 
-            #[inline]
-            fn listens_to(&self, event: &Event<$gen>) -> bool {
-                core::ptr::eq::<Inner<$gen>>(
-                    &*self.listener().event,
-                    event.inner.load(core::sync::atomic::Ordering::Acquire),
-                )
-            }
+#[cfg(feature = "std")]
+impl std::error::Error for Closed {}
 
-            #[inline]
-            fn same_event(&self, other: &$ty) -> bool {
-                core::ptr::eq::<Inner<$gen>>(&*self.listener().event, &*other.listener().event)
-            }
-        }
+/// An error returned when waiting for a notification timed out.
+///
+/// This is returned by [`Listener::try_wait_timeout()`] and similar methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WaitTimeoutError;
 
-        impl<$gen> Future for $ty {
-            type Output = $gen;
+impl fmt::Display for WaitTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out waiting for a notification")
+    }
+}
 
-            #[inline]
-            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<$gen> {
-                self.listener_mut().poll_internal(cx)
-            }
+#[cfg(feature = "std")]
+impl std::error::Error for WaitTimeoutError {}
+
+/// The outcome of registering a waker with [`Listener::register()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registration<T> {
+    /// The listener has been notified with this tag, and is done.
+    Notified(T),
+
+    /// The waker will be woken up once the listener is notified.
+    Registered,
+
+    /// The event has been closed.
+    ///
+    /// This is only reported by [`CloseableListener::register()`].
+    Closed,
+}
+
+/// The outcome of [`Event::listen_and_check()`].
+#[derive(Debug)]
+pub enum Checked<T = ()> {
+    /// The condition holds, and the listener has been dropped.
+    Ready,
+
+    /// The condition doesn't hold, and this listener is registered.
+    Listening(EventListener<T>),
+}
+
+/// An error returned by [`Event::try_listen_bounded()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TryListenError {
+    /// The event already has as many listeners as [`EventBuilder::listen_bound()`] allows.
+    Full,
+
+    /// Memory for the listener could not be allocated.
+    Alloc,
+}
+
+impl From<AllocError> for TryListenError {
+    #[inline]
+    fn from(_: AllocError) -> Self {
+        Self::Alloc
+    }
+}
+
+impl fmt::Display for TryListenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => f.write_str("too many listeners"),
+            Self::Alloc => f.write_str("memory allocation failed"),
         }
-    };
+    }
 }
 
-/// A guard waiting for a notification from an [`Event`].
-///
-/// There are two ways for a listener to wait for a notification:
-///
-/// 1. In an asynchronous manner using `.await`.
-/// 2. In a blocking manner by calling [`EventListener::wait()`] on it.
-///
-/// If a notified listener is dropped without receiving a notification, dropping will notify
-/// another active listener. Whether one *additional* listener will be notified depends on what
-/// kind of notification was delivered.
-///
-/// See the [`Listener`] trait for the functionality exposed by this type.
+#[cfg(feature = "std")]
+impl std::error::Error for TryListenError {}
+
+/// An error returned by [`Event::try_listen()`] when memory for the listener could not be
+/// allocated.
 ///
-/// This structure allocates the listener on the heap.
-pub struct EventListener<T = ()> {
-    listener: Pin<Box<InnerListener<T, Arc<Inner<T>>>>>,
+/// Without the `std` feature, this is also returned when the backup queue is full and its
+/// overflow behavior is [`QueueOverflow::Fail`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
 }
 
-unsafe impl<T: Send> Send for EventListener<T> {}
-unsafe impl<T: Send> Sync for EventListener<T> {}
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
 
-impl<T> core::panic::UnwindSafe for EventListener<T> {}
-impl<T> core::panic::RefUnwindSafe for EventListener<T> {}
-impl<T> Unpin for EventListener<T> {}
+pin_project_lite::pin_project! {
+    /// A listener that borrows its [`Event`] and can be pinned anywhere, such as on the stack.
+    ///
+    /// This is created by [`Event::listen_ref()`]. Unlike [`EventListener`], it does not allocate.
+    /// Unlike the [`listener!`] macro, it is a nameable type that can be stored in hand-rolled
+    /// futures.
+    ///
+    /// The listener starts listening once [`EventListenerRef::listen()`] is called, or when it is
+    /// first polled, whichever comes first. To avoid missing notifications in the usual
+    /// check-listen-check loop, call `listen()` before the second check.
+    #[project(!Unpin)]
+    pub struct EventListenerRef<'a, T = ()> {
+        #[pin]
+        listener: InnerListener<T, &'a Inner<T>>,
+    }
+}
 
-impl<T> fmt::Debug for EventListener<T> {
+unsafe impl<T: Send> Send for EventListenerRef<'_, T> {}
+unsafe impl<T: Send> Sync for EventListenerRef<'_, T> {}
+
+impl<T> core::panic::UnwindSafe for EventListenerRef<'_, T> {}
+impl<T> core::panic::RefUnwindSafe for EventListenerRef<'_, T> {}
+
+impl<T> fmt::Debug for EventListenerRef<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("EventListener").finish_non_exhaustive()
+        f.debug_struct("EventListenerRef")
+            .field("listening", &self.is_listening())
+            .finish_non_exhaustive()
     }
 }
 
-impl<T> EventListener<T> {
-    #[inline]
-    fn listener(&self) -> &InnerListener<T, Arc<Inner<T>>> {
-        &self.listener
+impl<T> EventListenerRef<'_, T> {
+    /// Starts listening for notifications.
+    ///
+    /// Does nothing if the listener is already listening.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// use futures_lite::pin;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_ref();
+    /// pin!(listener);
+    ///
+    /// listener.as_mut().listen();
+    /// assert!(listener.is_listening());
+    /// ```
+    pub fn listen(self: Pin<&mut Self>) {
+        if !self.is_listening() {
+            self.project().listener.listen();
+        }
     }
 
+    /// Tells whether the listener is currently registered to the event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_ref();
+    /// assert!(!listener.is_listening());
+    /// ```
     #[inline]
-    fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, Arc<Inner<T>>>> {
-        self.listener.as_mut()
+    pub fn is_listening(&self) -> bool {
+        self.listener.listener.is_some()
     }
 }
 
-forward_impl_to_listener! { T => EventListener<T> }
+impl<T> Future for EventListenerRef<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.as_mut().listen();
+        self.project()
+            .listener
+            .poll_internal(cx, false)
+            .map(expect_open)
+    }
+}
 
 /// Create a stack-based event listener for an [`Event`].
 ///
@@ -962,6 +3518,9 @@ forward_impl_to_listener! { T => EventListener<T> }
 /// The type created by this macro implements [`Listener`], allowing it to be used in cases where
 /// [`EventListener`] would normally be used.
 ///
+/// Like [`Event::listen()`], this doesn't check the bound set by [`EventBuilder::listen_bound()`],
+/// although the listener counts towards it.
+///
 /// ## Example
 ///
 /// To use this macro, replace cases where you would normally use this...
@@ -1026,14 +3585,135 @@ forward_impl_to_listener! { T => EventListener<T> }
 ///     listener.wait();
 /// }
 /// ```
+///
+/// Several listeners can be created at once by separating them with commas. The events may have
+/// different tag types, and each listener completes with the tag of its own event.
+///
+/// ```
+/// use event_listener::{Event, listener, IntoNotification, Listener};
+///
+/// let readable = Event::<&str>::with_tag();
+/// let writable = Event::new();
+///
+/// listener!(readable => on_read, writable => on_write);
+/// assert_eq!(readable.listener_count(), 1);
+/// assert_eq!(writable.listener_count(), 1);
+///
+/// readable.notify(1.tag("data"));
+/// assert_eq!(on_read.wait(), "data");
+/// assert!(!on_write.discard());
+/// ```
 #[macro_export]
 macro_rules! listener {
-    ($event:expr => $listener:ident) => {
-        let mut $listener = $crate::__private::StackSlot::new(&$event);
-        // SAFETY: We shadow $listener so it can't be moved after.
-        let mut $listener = unsafe { $crate::__private::Pin::new_unchecked(&mut $listener) };
-        #[allow(unused_mut)]
-        let mut $listener = $listener.listen();
+    ($($event:expr => $listener:ident),+ $(,)?) => {
+        $(
+            let mut $listener = $crate::__private::StackSlot::new(&$event);
+            // SAFETY: We shadow $listener so it can't be moved after.
+            let $listener = unsafe { $crate::__private::Pin::new_unchecked(&mut $listener) };
+            #[allow(unused_mut)]
+            let mut $listener = $listener.listen();
+        )+
+    };
+}
+
+/// Waits on an [`Event`] until a condition holds.
+///
+/// This expands to the usual loop for waiting on a condition: check it, start listening, emit a
+/// `SeqCst` fence, check it again, and only then wait for the next notification. Whoever makes
+/// the condition true has to notify the event afterwards.
+///
+/// The condition is written as a closure without arguments, `|| cond`, whose body is inlined
+/// into the loop. This lets it borrow local variables mutably, or leave the surrounding function
+/// with `return` or `?`. Any other expression is called as a function instead. The condition is
+/// evaluated at least once, and again after every notification. The listener lives on the stack,
+/// like the ones created by [`listener!`].
+///
+/// By default, the current thread is blocked, which requires the `std` feature. Prefix the event
+/// with `async` to await the notifications instead, inside of an `async` block or function.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use std::thread;
+/// use event_listener::{wait_until, Event};
+///
+/// let flag = Arc::new(AtomicBool::new(false));
+/// let event = Arc::new(Event::new());
+///
+/// thread::spawn({
+///     let flag = flag.clone();
+///     let event = event.clone();
+///     move || {
+///         flag.store(true, Ordering::SeqCst);
+///         event.notify(usize::MAX);
+///     }
+/// });
+///
+/// wait_until!(event, || flag.load(Ordering::SeqCst));
+/// ```
+///
+/// The async flavor:
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use event_listener::{wait_until, Event};
+/// # futures_lite::future::block_on(async {
+///
+/// let count = AtomicUsize::new(1);
+/// let event = Event::new();
+///
+/// wait_until!(async event, || count.load(Ordering::SeqCst) > 0);
+/// # });
+/// ```
+#[macro_export]
+macro_rules! wait_until {
+    (async $event:expr, || $cond:expr $(,)?) => {
+        loop {
+            // Check the condition.
+            if $cond {
+                break;
+            }
+
+            // Set up an event listener.
+            $crate::listener!($event => listener);
+            $crate::__private::full_fence();
+
+            // Check again now that we're listening.
+            if $cond {
+                break;
+            }
+
+            // Wait for a notification and try again.
+            listener.await;
+        }
+    };
+    (async $event:expr, $pred:expr $(,)?) => {
+        $crate::wait_until!(async $event, || ($pred)())
+    };
+    ($event:expr, || $cond:expr $(,)?) => {
+        loop {
+            // Check the condition.
+            if $cond {
+                break;
+            }
+
+            // Set up an event listener.
+            $crate::listener!($event => listener);
+            $crate::__private::full_fence();
+
+            // Check again now that we're listening.
+            if $cond {
+                break;
+            }
+
+            // Wait for a notification and try again.
+            $crate::Listener::wait(listener);
+        }
+    };
+    ($event:expr, $pred:expr $(,)?) => {
+        $crate::wait_until!($event, || ($pred)())
     };
 }
 
@@ -1067,10 +3747,147 @@ pin_project_lite::pin_project! {
     }
 }
 
+/// The maximum number of allocations kept in an event's listener pool.
+#[cfg(feature = "listener-pool")]
+const POOL_CAPACITY: usize = 16;
+
+/// A listener that owns a reference to its event.
+type HeapListener<T> = InnerListener<T, Arc<Inner<T>>>;
+
+/// A pooled allocation for a heap-allocated listener.
+#[cfg(feature = "listener-pool")]
+type PooledListener<T> = core::mem::MaybeUninit<HeapListener<T>>;
+
+/// A heap-allocated listener.
+///
+/// With the `listener-pool` feature, the allocation is taken from the event's pool and returned
+/// to it on drop.
+struct ListenerBox<T> {
+    listener: ManuallyDrop<Pin<Box<HeapListener<T>>>>,
+}
+
+impl<T> ListenerBox<T> {
+    /// Allocate a new listener for the event.
+    fn new(event: Arc<Inner<T>>) -> Self {
+        match Self::from_pool(event) {
+            Ok(this) => this,
+            Err(listener) => Self {
+                listener: ManuallyDrop::new(Box::pin(listener)),
+            },
+        }
+    }
+
+    /// Allocate a new listener for the event, reporting allocation failure.
+    fn try_new(event: Arc<Inner<T>>) -> Result<Self, AllocError> {
+        let listener = match Self::from_pool(event) {
+            Ok(this) => return Ok(this),
+            Err(listener) => listener,
+        };
+
+        let layout = core::alloc::Layout::new::<HeapListener<T>>();
+
+        // SAFETY: `InnerListener` is never zero-sized, since it contains an `Arc`.
+        let ptr = unsafe { alloc::alloc::alloc(layout) }.cast::<HeapListener<T>>();
+        if ptr.is_null() {
+            return Err(AllocError);
+        }
+
+        // SAFETY: The pointer was allocated with the layout of `HeapListener<T>`.
+        let listener = unsafe {
+            ptr.write(listener);
+            Box::from_raw(ptr)
+        };
+
+        Ok(Self {
+            listener: ManuallyDrop::new(Pin::from(listener)),
+        })
+    }
+
+    /// Take an allocation out of the event's pool, or return the unallocated listener.
+    fn from_pool(event: Arc<Inner<T>>) -> Result<Self, HeapListener<T>> {
+        #[cfg(feature = "listener-pool")]
+        let slot = event.pool.try_lock().ok().and_then(|mut pool| pool.pop());
+
+        let listener = InnerListener {
+            event,
+            listener: None,
+        };
+
+        #[cfg(feature = "listener-pool")]
+        if let Some(mut slot) = slot {
+            slot.write(listener);
+
+            // SAFETY: The slot is now initialized.
+            let listener = unsafe { Box::from_raw(Box::into_raw(slot).cast()) };
+            return Ok(Self {
+                listener: ManuallyDrop::new(Pin::from(listener)),
+            });
+        }
+
+        Err(listener)
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut HeapListener<T>> {
+        self.listener.as_mut()
+    }
+}
+
+impl<T> core::ops::Deref for ListenerBox<T> {
+    type Target = HeapListener<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.listener
+    }
+}
+
+impl<T> Drop for ListenerBox<T> {
+    fn drop(&mut self) {
+        // SAFETY: The listener is never used again.
+        let listener = unsafe { ManuallyDrop::take(&mut self.listener) };
+
+        #[cfg(not(feature = "listener-pool"))]
+        drop(listener);
+
+        #[cfg(feature = "listener-pool")]
+        {
+            let event = listener.event.clone();
+
+            // SAFETY: The listener is dropped in place before its memory is reused, which upholds
+            // the pinning guarantees.
+            let slot = unsafe {
+                let raw = Box::into_raw(Pin::into_inner_unchecked(listener));
+                ptr::drop_in_place(raw);
+                Box::from_raw(raw.cast::<PooledListener<T>>())
+            };
+
+            // Don't wait for the pool if somebody else is using it.
+            let mut pool = match event.pool.try_lock() {
+                Ok(pool) => pool,
+                Err(_) => return,
+            };
+
+            if pool.len() < POOL_CAPACITY {
+                pool.push(slot);
+            }
+        }
+    }
+}
+
 unsafe impl<T: Send, B: Borrow<Inner<T>> + Unpin + Send> Send for InnerListener<T, B> {}
 unsafe impl<T: Send, B: Borrow<Inner<T>> + Unpin + Sync> Sync for InnerListener<T, B> {}
 
 impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
+    /// Get the name of this listener's state for `Debug` output.
+    fn state_name(&self) -> &'static str {
+        self.event
+            .borrow()
+            .list
+            .try_state_name(self.listener.as_ref())
+            .unwrap_or("<locked>")
+    }
+
     /// Insert this listener into the linked list.
     #[inline]
     fn listen(self: Pin<&mut Self>) {
@@ -1078,9 +3895,45 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
         (*this.event).borrow().insert(this.listener);
     }
 
+    /// Insert this listener into the linked list, reporting allocation failure.
+    #[inline]
+    fn try_listen(self: Pin<&mut Self>) -> Result<(), AllocError> {
+        let this = self.project();
+        (*this.event).borrow().try_insert(this.listener)
+    }
+
+    /// Insert this listener into the linked list, unless the list is full.
+    #[inline]
+    fn listen_bounded(self: Pin<&mut Self>) -> Result<(), TryListenError> {
+        let this = self.project();
+        (*this.event).borrow().insert_bounded(this.listener)
+    }
+
+    /// Insert this listener into the linked list, unless the list is full, reporting allocation
+    /// failure.
+    #[inline]
+    fn try_listen_bounded(self: Pin<&mut Self>) -> Result<(), TryListenError> {
+        let this = self.project();
+        (*this.event).borrow().try_insert_bounded(this.listener)
+    }
+
+    /// Insert this listener into the linked list again, if it is not in there already.
+    fn rearm(self: Pin<&mut Self>) {
+        let this = self.project();
+        if this.listener.is_none() {
+            (*this.event).borrow().insert(this.listener);
+        }
+    }
+
     /// Wait until the provided deadline.
+    ///
+    /// If `closeable` is `false`, closing the event completes the wait with the close tag.
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_internal(mut self: Pin<&mut Self>, deadline: Option<Instant>) -> Option<T> {
+    fn wait_internal(
+        mut self: Pin<&mut Self>,
+        deadline: Option<Instant>,
+        closeable: bool,
+    ) -> Option<Result<T, Closed>> {
         fn parker_and_task() -> (Parker, Task) {
             let parker = Parker::new();
             let unparker = parker.unparker();
@@ -1096,14 +3949,20 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
         PARKER
             .try_with({
                 let this = self.as_mut();
-                |(parker, unparker)| this.wait_with_parker(deadline, parker, unparker.as_task_ref())
+                |(parker, unparker)| {
+                    this.wait_with_parker(deadline, closeable, parker, unparker.as_task_ref())
+                }
             })
             .unwrap_or_else(|_| {
                 // If the pair isn't accessible, we may be being called in a destructor.
                 // Just create a new pair.
-                let (parker, unparker) = parking::pair();
-                self.as_mut()
-                    .wait_with_parker(deadline, &parker, TaskRef::Unparker(&unparker))
+                let (parker, unparker) = pair();
+                self.as_mut().wait_with_parker(
+                    deadline,
+                    closeable,
+                    &parker,
+                    TaskRef::Unparker(&unparker),
+                )
             })
     }
 
@@ -1112,16 +3971,38 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
     fn wait_with_parker(
         self: Pin<&mut Self>,
         deadline: Option<Instant>,
+        closeable: bool,
         parker: &Parker,
         unparker: TaskRef<'_>,
-    ) -> Option<T> {
+    ) -> Option<Result<T, Closed>> {
         let mut this = self.project();
         let inner = (*this.event).borrow();
 
+        // Spin for a while, in case the notification is about to arrive.
+        for round in 0..inner.spin_before_park {
+            if round < SPIN_LIMIT {
+                for _ in 0..1 << round {
+                    crate::sync::spin_loop();
+                }
+            } else {
+                crate::sync::yield_now();
+            }
+
+            // Only lock the list if someone may have been notified.
+            if inner.notified.load(Ordering::Acquire) != 0 || inner.closed.load(Ordering::Acquire) {
+                if let Some(result) = inner.check(this.listener.as_mut()).completed(closeable) {
+                    return Some(result);
+                }
+            }
+        }
+
         // Set the listener's state to `Task`.
-        if let Some(tag) = inner.register(this.listener.as_mut(), unparker).notified() {
+        if let Some(result) = inner
+            .register(this.listener.as_mut(), unparker)
+            .completed(closeable)
+        {
             // We were already notified, so we don't need to park.
-            return Some(tag);
+            return Some(result);
         }
 
         // Wait until a notification is received or the timeout is reached.
@@ -1143,15 +4024,18 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
                         return inner
                             .remove(this.listener.as_mut(), false)
                             .expect("We never removed ourself from the list")
-                            .notified();
+                            .completed(closeable);
                     }
                     parker.park_deadline(deadline);
                 }
             }
 
             // See if we were notified.
-            if let Some(tag) = inner.register(this.listener.as_mut(), unparker).notified() {
-                return Some(tag);
+            if let Some(result) = inner
+                .register(this.listener.as_mut(), unparker)
+                .completed(closeable)
+            {
+                return Some(result);
             }
         }
     }
@@ -1166,19 +4050,114 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
             .map_or(false, |state| state.is_notified())
     }
 
+    /// Register a waker to be woken when this listener is notified.
+    ///
+    /// If `closeable` is `false`, closing the event completes the listener with the close tag.
+    fn register_waker(self: Pin<&mut Self>, waker: &Waker, closeable: bool) -> Registration<T> {
+        let this = self.project();
+        match (*this.event)
+            .borrow()
+            .register(this.listener, TaskRef::Waker(waker))
+        {
+            RegisterResult::Notified(tag) => Registration::Notified(tag),
+            RegisterResult::Closed(_) if closeable => Registration::Closed,
+            RegisterResult::Closed(close_tag) => Registration::Notified(close_tag()),
+            RegisterResult::Registered => Registration::Registered,
+            RegisterResult::NeverInserted => panic!("{}", NEVER_INSERTED_PANIC),
+        }
+    }
+
+    /// Call `f` with the tag of this listener's notification, if it was notified.
+    fn peek_tag<R>(self: Pin<&mut Self>, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let this = self.project();
+        (*this.event).borrow().peek(this.listener, f)
+    }
+
+    /// Check whether this listener was notified, without registering a task or blocking.
+    ///
+    /// If `closeable` is `false`, closing the event completes the listener with the close tag.
+    fn check(self: Pin<&mut Self>, closeable: bool) -> Option<Result<T, Closed>> {
+        let this = self.project();
+        match (*this.event).borrow().check(this.listener) {
+            RegisterResult::NeverInserted => None,
+            result => result.completed(closeable),
+        }
+    }
+
+    #[inline]
+    fn discard_tag(self: Pin<&mut Self>) -> Option<T> {
+        let this = self.project();
+        match (*this.event).borrow().remove(this.listener, false) {
+            Some(State::Notified { tag, .. }) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Poll the listener until it completes or the deadline on `clock` is reached.
+    ///
+    /// If `closeable` is `false`, closing the event completes the wait with the close tag.
+    fn wait_with_clock<C: Clock>(
+        self: Pin<&mut Self>,
+        clock: &C,
+        deadline: C::Instant,
+        closeable: bool,
+    ) -> Option<Result<T, Closed>> {
+        self.wait_polling(
+            || {
+                if clock.now() >= deadline {
+                    return false;
+                }
+
+                clock.idle();
+                true
+            },
+            closeable,
+        )
+    }
+
+    /// Poll the listener in a loop until it completes, calling `idle` in between.
+    ///
+    /// Gives up if `idle` returns `false`. If `closeable` is `false`, closing the event does not
+    /// complete the wait.
+    fn wait_polling(
+        mut self: Pin<&mut Self>,
+        mut idle: impl FnMut() -> bool,
+        closeable: bool,
+    ) -> Option<Result<T, Closed>> {
+        // Nobody needs to wake us up, since we poll in a loop anyway.
+        let waker = clock::idle_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(result) = self.as_mut().poll_internal(&mut cx, closeable) {
+                return Some(result);
+            }
+
+            if !idle() {
+                return None;
+            }
+        }
+    }
+
     /// Poll this listener for a notification.
-    fn poll_internal(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+    ///
+    /// If `closeable` is `false`, closing the event completes the listener with the close tag.
+    fn poll_internal(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        closeable: bool,
+    ) -> Poll<Result<T, Closed>> {
         let this = self.project();
         let inner = (*this.event).borrow();
 
         // Try to register the listener.
         match inner
             .register(this.listener, TaskRef::Waker(cx.waker()))
-            .notified()
+            .completed(closeable)
         {
-            Some(tag) => {
+            Some(result) => {
                 // We were already notified, so we don't need to park.
-                Poll::Ready(tag)
+                Poll::Ready(result)
             }
 
             None => {
@@ -1187,10 +4166,25 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
             }
         }
     }
+
+    /// Like `poll_internal`, but a notified listener stays in the list, waiting for the next
+    /// notification.
+    #[cfg(feature = "stream")]
+    fn poll_rearm(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Closed>> {
+        let this = self.project();
+        let inner = (*this.event).borrow();
+
+        match inner
+            .register_with(this.listener, TaskRef::Waker(cx.waker()), true)
+            .completed(true)
+        {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
 }
 
 /// The state of a listener.
-#[derive(PartialEq)]
 enum State<T> {
     /// The listener was just created.
     Created,
@@ -1211,6 +4205,30 @@ enum State<T> {
 
     /// Empty hole used to replace a notified listener.
     NotifiedTaken,
+
+    /// The event was closed before the listener was notified.
+    ///
+    /// Listeners that don't observe closing complete with the tag made by this function.
+    Closed(fn() -> T),
+}
+
+impl<T: PartialEq> PartialEq for State<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Created, Self::Created)
+            | (Self::NotifiedTaken, Self::NotifiedTaken)
+            | (Self::Closed(_), Self::Closed(_)) => true,
+            (
+                Self::Notified { additional, tag },
+                Self::Notified {
+                    additional: other_additional,
+                    tag: other_tag,
+                },
+            ) => additional == other_additional && tag == other_tag,
+            (Self::Task(task), Self::Task(other_task)) => task == other_task,
+            _ => false,
+        }
+    }
 }
 
 impl<T> fmt::Debug for State<T> {
@@ -1223,28 +4241,44 @@ impl<T> fmt::Debug for State<T> {
                 .finish(),
             Self::Task(_) => f.write_str("Task(_)"),
             Self::NotifiedTaken => f.write_str("NotifiedTaken"),
+            Self::Closed(_) => f.write_str("Closed"),
         }
     }
 }
 
 impl<T> State<T> {
+    /// The name of this state, as shown in the `Debug` output of listeners.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Created => "Created",
+            Self::Task(_) => "Registered",
+            Self::Notified { .. } | Self::NotifiedTaken => "Notified",
+            Self::Closed(_) => "Closed",
+        }
+    }
+
     fn is_notified(&self) -> bool {
         matches!(self, Self::Notified { .. } | Self::NotifiedTaken)
     }
 
     /// If this state was notified, return the tag associated with the notification.
+    ///
+    /// If `closeable` is `true`, a closed state is reported as `Err(Closed)`. Otherwise, it is
+    /// reported as a notification with the close tag.
     #[allow(unused)]
-    fn notified(self) -> Option<T> {
+    fn completed(self, closeable: bool) -> Option<Result<T, Closed>> {
         match self {
-            Self::Notified { tag, .. } => Some(tag),
+            Self::Notified { tag, .. } => Some(Ok(tag)),
             Self::NotifiedTaken => panic!("listener was already notified but taken"),
+            Self::Closed(_) if closeable => Some(Err(Closed)),
+            Self::Closed(close_tag) => Some(Ok(close_tag())),
             _ => None,
         }
     }
 }
 
 /// The result of registering a listener.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 enum RegisterResult<T> {
     /// The listener was already notified.
     Notified(T),
@@ -1252,23 +4286,52 @@ enum RegisterResult<T> {
     /// The listener has been registered.
     Registered,
 
+    /// The event was closed before the listener was notified.
+    Closed(fn() -> T),
+
     /// The listener was never inserted into the list.
     NeverInserted,
 }
 
+impl<T: PartialEq> PartialEq for RegisterResult<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Notified(tag), Self::Notified(other_tag)) => tag == other_tag,
+            (Self::Registered, Self::Registered)
+            | (Self::Closed(_), Self::Closed(_))
+            | (Self::NeverInserted, Self::NeverInserted) => true,
+            _ => false,
+        }
+    }
+}
+
 impl<T> RegisterResult<T> {
     /// Whether or not the listener was notified.
     ///
+    /// If `closeable` is `true`, a closed event is reported as `Err(Closed)`. Otherwise, it is
+    /// reported as a notification with the close tag.
+    ///
     /// Panics if the listener was never inserted into the list.
-    fn notified(self) -> Option<T> {
+    fn completed(self, closeable: bool) -> Option<Result<T, Closed>> {
         match self {
-            Self::Notified(tag) => Some(tag),
+            Self::Notified(tag) => Some(Ok(tag)),
+            Self::Closed(_) if closeable => Some(Err(Closed)),
+            Self::Closed(close_tag) => Some(Ok(close_tag())),
             Self::Registered => None,
             Self::NeverInserted => panic!("{}", NEVER_INSERTED_PANIC),
         }
     }
 }
 
+/// Unwraps the result of a listener that does not observe closing.
+#[inline]
+fn expect_open<T>(result: Result<T, Closed>) -> T {
+    match result {
+        Ok(tag) => tag,
+        Err(Closed) => unreachable!("listener does not observe closing"),
+    }
+}
+
 /// A task that can be woken up.
 #[derive(Debug, Clone)]
 enum Task {
@@ -1342,6 +4405,11 @@ impl TaskRef<'_> {
     }
 }
 
+/// The number of rounds a blocking wait spins before it starts yielding to other threads
+/// instead, see [`EventBuilder::spin_before_park()`]. Spinning doubles every round.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+const SPIN_LIMIT: u32 = 6;
+
 const NEVER_INSERTED_PANIC: &str = "\
 EventListener was not inserted into the linked list, make sure you're not polling \
 EventListener/listener! after it has finished";
@@ -1350,19 +4418,21 @@ EventListener/listener! after it has finished";
 /// Synchronization primitive implementation.
 mod sync {
     #[cfg(not(feature = "portable-atomic"))]
-    pub(super) use alloc::sync::Arc;
+    pub(super) use alloc::sync::{Arc, Weak};
     #[cfg(not(feature = "portable-atomic"))]
     pub(super) use core::sync::atomic;
 
+    pub(super) use core::hint::spin_loop;
+
     #[cfg(feature = "portable-atomic")]
     pub(super) use portable_atomic_crate as atomic;
     #[cfg(feature = "portable-atomic")]
-    pub(super) use portable_atomic_util::Arc;
+    pub(super) use portable_atomic_util::{Arc, Weak};
 
     #[cfg(all(feature = "std", not(loom)))]
-    pub(super) use std::sync::{Mutex, MutexGuard};
+    pub(super) use std::sync::{Mutex, MutexGuard, TryLockError};
     #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
-    pub(super) use std::thread_local;
+    pub(super) use std::{thread::yield_now, thread_local};
 
     pub(super) trait WithMut {
         type Output;
@@ -1389,14 +4459,23 @@ mod sync {
 
         /// This newtype around *mut T exists for interoperability with loom::cell::ConstPtr,
         /// which works as a guard and performs additional logic to track access scope.
+        #[allow(unused)] // no_std code does not need this
         pub(crate) struct ConstPtr<T>(*mut T);
+        #[allow(unused)] // no_std code does not need this
         impl<T> ConstPtr<T> {
             pub(crate) unsafe fn deref(&self) -> &T {
                 &*self.0
             }
+        }
 
-            #[allow(unused)] // std code does not need this
-            pub(crate) unsafe fn deref_mut(&mut self) -> &mut T {
+        /// This newtype around *mut T exists for interoperability with loom::cell::MutPtr,
+        /// which works as a guard and performs additional logic to track access scope.
+        #[allow(unused)] // std code does not need this
+        pub(crate) struct MutPtr<T>(*mut T);
+        #[allow(unused)] // std code does not need this
+        impl<T> MutPtr<T> {
+            #[allow(clippy::mut_from_ref)]
+            pub(crate) unsafe fn deref(&self) -> &mut T {
                 &mut *self.0
             }
         }
@@ -1411,10 +4490,16 @@ mod sync {
                 UnsafeCell(core::cell::UnsafeCell::new(data))
             }
 
+            #[allow(unused)] // no_std code does not need this
             pub(crate) fn get(&self) -> ConstPtr<T> {
                 ConstPtr(self.0.get())
             }
 
+            #[allow(unused)] // std code does not need this
+            pub(crate) fn get_mut(&self) -> MutPtr<T> {
+                MutPtr(self.0.get())
+            }
+
             #[allow(dead_code)] // no_std does not need this
             pub(crate) fn into_inner(self) -> T {
                 self.0.into_inner()
@@ -1426,8 +4511,16 @@ mod sync {
 #[cfg(loom)]
 /// Synchronization primitive implementation.
 mod sync {
-    pub(super) use loom::sync::{atomic, Arc, Mutex, MutexGuard};
-    pub(super) use loom::{cell, thread_local};
+    pub(super) use loom::cell;
+    pub(super) use loom::hint::spin_loop;
+    pub(super) use loom::sync::{atomic, Arc};
+
+    #[cfg(feature = "std")]
+    pub(super) use loom::sync::{Mutex, MutexGuard};
+    #[cfg(feature = "std")]
+    pub(super) use loom::{thread::yield_now, thread_local};
+    #[cfg(feature = "std")]
+    pub(super) use std::sync::TryLockError;
 }
 
 fn __test_send_and_sync() {
@@ -1442,14 +4535,107 @@ fn __test_send_and_sync() {
     _assert_sync::<Event<()>>();
     _assert_send::<EventListener<()>>();
     _assert_sync::<EventListener<()>>();
+    _assert_send::<CloseableListener<()>>();
+    _assert_send::<Registration<()>>();
+    _assert_send::<WaitTimeoutError>();
+    _assert_sync::<WaitTimeoutError>();
+    _assert_sync::<Registration<()>>();
+    _assert_sync::<CloseableListener<()>>();
+    _assert_send::<EventListenerRef<'_, ()>>();
+    _assert_send::<DualEvent<()>>();
+    _assert_sync::<DualEvent<()>>();
+    _assert_send::<DualListener<()>>();
+    _assert_sync::<DualListener<()>>();
+    _assert_send::<ShardedEvent<()>>();
+    _assert_sync::<ShardedEvent<()>>();
+    _assert_send::<ShardedListener<()>>();
+    _assert_sync::<ShardedListener<()>>();
+    _assert_sync::<EventListenerRef<'_, ()>>();
+    #[cfg(feature = "std")]
+    _assert_send::<Forward<()>>();
+    #[cfg(feature = "std")]
+    _assert_sync::<Forward<()>>();
+    #[cfg(feature = "std")]
+    _assert_send::<Merge<()>>();
+    #[cfg(feature = "std")]
+    _assert_sync::<Merge<()>>();
+    _assert_send::<EventBuilder>();
+    _assert_sync::<EventBuilder>();
+    _assert_send::<RawEvent<()>>();
+    _assert_sync::<RawEvent<()>>();
+    _assert_send::<SingleEvent>();
+    _assert_sync::<SingleEvent>();
+    _assert_send::<SingleListener<'_>>();
+    _assert_sync::<SingleListener<'_>>();
+    _assert_send::<FixedEvent<1, ()>>();
+    _assert_sync::<FixedEvent<1, ()>>();
+    _assert_send::<FixedListener<'_, 1, ()>>();
+    _assert_sync::<FixedListener<'_, 1, ()>>();
+    _assert_send::<StaticEvent<()>>();
+    _assert_sync::<StaticEvent<()>>();
+    _assert_send::<StaticListener<'_, ()>>();
+    _assert_sync::<StaticListener<'_, ()>>();
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    _assert_send::<Timeout<()>>();
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    _assert_sync::<Timeout<()>>();
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    _assert_send::<ThrottledEvent>();
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    _assert_sync::<ThrottledEvent>();
+    _assert_send::<Notifier<()>>();
+    _assert_sync::<Notifier<()>>();
+    _assert_send::<Waiter<()>>();
+    _assert_sync::<Waiter<()>>();
+    _assert_send::<Race<(), EventListener<()>, EventListener<()>>>();
+    _assert_sync::<Race<(), EventListener<()>, EventListener<()>>>();
+    _assert_send::<SelectEvents<()>>();
+    _assert_sync::<SelectEvents<()>>();
+    _assert_send::<WaitAll<(), EventListener<()>>>();
+    _assert_sync::<WaitAll<(), EventListener<()>>>();
+    _assert_send::<Map<EventListener<()>, fn(())>>();
+    _assert_sync::<Map<EventListener<()>, fn(())>>();
+    _assert_send::<WaitTicket>();
+    _assert_sync::<WaitTicket>();
+    _assert_send::<WaitMany<()>>();
+    _assert_sync::<WaitMany<()>>();
+    #[cfg(feature = "std")]
+    _assert_send::<EventMap<usize, ()>>();
+    #[cfg(feature = "std")]
+    _assert_sync::<EventMap<usize, ()>>();
+    #[cfg(feature = "snapshot")]
+    _assert_send::<Snapshot>();
+    #[cfg(feature = "snapshot")]
+    _assert_sync::<Snapshot>();
+    #[cfg(feature = "stream")]
+    _assert_send::<EventStream<()>>();
+    #[cfg(feature = "stream")]
+    _assert_sync::<EventStream<()>>();
+}
+
+#[cfg(feature = "defmt")]
+fn __test_defmt_format() {
+    fn _assert_format<T: defmt::Format>() {}
+
+    _assert_format::<Event<()>>();
+    _assert_format::<EventListener<()>>();
+    _assert_format::<Closed>();
+    _assert_format::<WaitTimeoutError>();
+    _assert_format::<TryListenError>();
+    _assert_format::<AllocError>();
+    _assert_format::<CapacityError>();
+    _assert_format::<primitives::oneshot::RecvError>();
+    _assert_format::<primitives::oneshot::TryRecvError>();
 }
 
 #[doc(hidden)]
 mod __sealed {
-    use super::{EventListener, __private::StackListener};
+    use super::{__private::StackListener, DualListener, EventListener, ShardedListener};
 
     pub trait Sealed {}
     impl<T> Sealed for EventListener<T> {}
+    impl<T> Sealed for DualListener<T> {}
+    impl<T> Sealed for ShardedListener<T> {}
     impl<T> Sealed for StackListener<'_, '_, T> {}
 }
 
@@ -1458,6 +4644,13 @@ mod __sealed {
 pub mod __private {
     pub use core::pin::Pin;
 
+    /// Emits a `SeqCst` fence.
+    #[inline]
+    #[doc(hidden)]
+    pub fn full_fence() {
+        crate::notify::full_fence();
+    }
+
     use super::{Event, Inner, InnerListener};
     use core::fmt;
     use core::future::Future;
@@ -0,0 +1,572 @@
+//! A single-threaded variant of [`Event`].
+//!
+//! [`Event`]: crate::Event
+
+use crate::notify::{GenericNotify, Internal, NotificationPrivate};
+use crate::{IntoNotification, NEVER_INSERTED_PANIC};
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+use core::cell::RefCell;
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+/// A single-threaded synchronization primitive for notifying async tasks.
+///
+/// This works like [`Event`], but it uses [`Rc`] and [`RefCell`] instead of atomics and locks,
+/// so it is neither `Send` nor `Sync`. In exchange, notifying and listening are cheaper, and tags
+/// don't need to be `Send`. This makes it a good fit for single-threaded executors and for
+/// `wasm` targets.
+///
+/// Since there is no other thread that could notify it, there are no blocking methods.
+///
+/// [`Event`]: crate::Event
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::LocalEvent;
+/// # futures_lite::future::block_on(async {
+///
+/// let event = LocalEvent::new();
+/// let listener = event.listen();
+///
+/// event.notify(1);
+/// listener.await;
+/// # });
+/// ```
+pub struct LocalEvent<T = ()> {
+    inner: Rc<RefCell<List<T>>>,
+}
+
+impl<T> fmt::Debug for LocalEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_borrow() {
+            Ok(list) => f
+                .debug_struct("LocalEvent")
+                .field("listeners_notified", &list.notified)
+                .field("listeners_total", &list.len)
+                .finish(),
+            Err(_) => f
+                .debug_tuple("LocalEvent")
+                .field(&format_args!("<locked>"))
+                .finish(),
+        }
+    }
+}
+
+impl Default for LocalEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for LocalEvent<T> {
+    /// Creates another handle to the same event.
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl LocalEvent {
+    /// Creates a new [`LocalEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_tag()
+    }
+}
+
+impl<T> LocalEvent<T> {
+    /// Creates a new [`LocalEvent`] with a tag type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use event_listener::LocalEvent;
+    ///
+    /// // Tags don't need to be `Send`.
+    /// let event = LocalEvent::<Rc<str>>::with_tag();
+    /// ```
+    pub fn with_tag() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(List::new())),
+        }
+    }
+
+    /// Returns a guard listening for a notification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// let listener = event.listen();
+    /// ```
+    pub fn listen(&self) -> LocalEventListener<T> {
+        let key = self.inner.borrow_mut().insert();
+
+        LocalEventListener {
+            inner: self.inner.clone(),
+            key: Some(key),
+        }
+    }
+
+    /// Notifies a number of active listeners.
+    ///
+    /// This works like [`Event::notify()`] and accepts the same kinds of notifications, except
+    /// that no fence is ever needed.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, LocalEvent};
+    ///
+    /// let event = LocalEvent::new();
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify(1), 1);
+    /// assert_eq!(event.notify(1), 0);
+    /// assert_eq!(event.notify(1.additional()), 1);
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let mut notify = notify.into_notification();
+        notify.before_notify(Internal::new());
+        let count = self.with_list(|list| list.notify(&mut notify));
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Tell whether any listeners are currently notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// let listener = event.listen();
+    /// assert!(!event.is_notified());
+    ///
+    /// event.notify(1);
+    /// assert!(event.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.inner.borrow().notified > 0
+    }
+
+    /// Returns the number of listeners currently registered to this [`LocalEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// let listener = event.listen();
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.inner.borrow().len
+    }
+
+    /// Runs an operation on the list, then wakes the tasks that it notified.
+    ///
+    /// Waking happens after the list is released, so that wakers which touch this event again
+    /// don't cause a double borrow.
+    fn with_list<R>(&self, f: impl FnOnce(&mut List<T>) -> R) -> R {
+        with_list(&self.inner, f)
+    }
+}
+
+/// A guard waiting for a notification from a [`LocalEvent`].
+///
+/// If a notified listener is dropped without receiving a notification, dropping will notify
+/// another active listener.
+pub struct LocalEventListener<T = ()> {
+    /// The event this listener belongs to.
+    inner: Rc<RefCell<List<T>>>,
+
+    /// The key of our entry, or `None` once the notification has been received.
+    key: Option<usize>,
+}
+
+impl<T> fmt::Debug for LocalEventListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalEventListener").finish_non_exhaustive()
+    }
+}
+
+impl<T> LocalEventListener<T> {
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// event.notify(1);
+    ///
+    /// assert!(listener1.discard());
+    /// assert!(!listener2.discard());
+    /// ```
+    pub fn discard(mut self) -> bool {
+        match self.key.take() {
+            Some(key) => with_list(&self.inner, |list| list.remove(key, false)).is_notified(),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if this listener listens to the given [`LocalEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::LocalEvent;
+    ///
+    /// let event = LocalEvent::new();
+    /// let listener = event.listen();
+    ///
+    /// assert!(listener.listens_to(&event));
+    /// assert!(!listener.listens_to(&LocalEvent::new()));
+    /// ```
+    #[inline]
+    pub fn listens_to(&self, event: &LocalEvent<T>) -> bool {
+        Rc::ptr_eq(&self.inner, &event.inner)
+    }
+}
+
+impl<T> Future for LocalEventListener<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let key = self.key.expect(NEVER_INSERTED_PANIC);
+        let mut list = self.inner.borrow_mut();
+
+        match mem::replace(list.state_mut(key), State::Created) {
+            State::Notified { tag, .. } => {
+                // We have been notified, remove the listener.
+                list.remove(key, false);
+                drop(list);
+                self.key = None;
+                Poll::Ready(tag)
+            }
+
+            State::Waiting(waker) if waker.will_wake(cx.waker()) => {
+                *list.state_mut(key) = State::Waiting(waker);
+                Poll::Pending
+            }
+
+            _ => {
+                *list.state_mut(key) = State::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for LocalEventListener<T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            with_list(&self.inner, |list| list.remove(key, true));
+        }
+    }
+}
+
+/// Runs an operation on the list, then wakes the tasks that it notified.
+fn with_list<T, R>(inner: &RefCell<List<T>>, f: impl FnOnce(&mut List<T>) -> R) -> R {
+    let (result, wakers) = {
+        let mut list = inner.borrow_mut();
+        let result = f(&mut list);
+        (result, mem::take(&mut list.wakers))
+    };
+
+    for waker in wakers {
+        waker.wake();
+    }
+
+    result
+}
+
+/// The state of a listener.
+enum State<T> {
+    /// The listener was just created.
+    Created,
+
+    /// The listener has received a notification.
+    Notified {
+        /// Whether or not this is an "additional" notification.
+        additional: bool,
+
+        /// The tag associated with the notification.
+        tag: T,
+    },
+
+    /// A task is waiting for a notification.
+    Waiting(Waker),
+}
+
+impl<T> State<T> {
+    fn is_notified(&self) -> bool {
+        matches!(self, Self::Notified { .. })
+    }
+}
+
+/// A slot in the list.
+enum Slot<T> {
+    /// A registered listener.
+    Occupied {
+        /// The state of the listener.
+        state: State<T>,
+
+        /// The previous listener in the list.
+        prev: Option<usize>,
+
+        /// The next listener in the list.
+        next: Option<usize>,
+    },
+
+    /// An empty slot that contains the index of the next empty slot.
+    Vacant(Option<usize>),
+}
+
+/// A linked list of listeners, stored in a slab.
+struct List<T> {
+    /// The raw list of slots.
+    slots: Vec<Slot<T>>,
+
+    /// First entry in the list.
+    head: Option<usize>,
+
+    /// Last entry in the list.
+    tail: Option<usize>,
+
+    /// The first unnotified entry in the list.
+    start: Option<usize>,
+
+    /// The first vacant slot.
+    first_vacant: Option<usize>,
+
+    /// The total number of listeners.
+    len: usize,
+
+    /// The number of notified listeners.
+    notified: usize,
+
+    /// A permit stored by a sticky notification, waiting for the next listener.
+    sticky: Option<T>,
+
+    /// Wakers to wake once the list is released.
+    wakers: Vec<Waker>,
+}
+
+impl<T> List<T> {
+    fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            head: None,
+            tail: None,
+            start: None,
+            first_vacant: None,
+            len: 0,
+            notified: 0,
+            sticky: None,
+            wakers: Vec::new(),
+        }
+    }
+
+    fn state_mut(&mut self, key: usize) -> &mut State<T> {
+        match &mut self.slots[key] {
+            Slot::Occupied { state, .. } => state,
+            Slot::Vacant(_) => unreachable!(),
+        }
+    }
+
+    fn links_mut(&mut self, key: usize) -> (&mut Option<usize>, &mut Option<usize>) {
+        match &mut self.slots[key] {
+            Slot::Occupied { prev, next, .. } => (prev, next),
+            Slot::Vacant(_) => unreachable!(),
+        }
+    }
+
+    /// Inserts a new entry at the end of the list.
+    fn insert(&mut self) -> usize {
+        // If a sticky notification left a permit behind, this listener takes it.
+        let state = match self.sticky.take() {
+            Some(tag) => State::Notified {
+                additional: false,
+                tag,
+            },
+            None => State::Created,
+        };
+        let notified = state.is_notified();
+
+        let slot = Slot::Occupied {
+            state,
+            prev: self.tail,
+            next: None,
+        };
+
+        let key = match self.first_vacant {
+            Some(key) => {
+                match mem::replace(&mut self.slots[key], slot) {
+                    Slot::Vacant(next) => self.first_vacant = next,
+                    Slot::Occupied { .. } => unreachable!(),
+                }
+                key
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        };
+
+        // Replace the tail with the new entry.
+        match self.tail.replace(key) {
+            None => self.head = Some(key),
+            Some(tail) => *self.links_mut(tail).1 = Some(key),
+        }
+
+        if notified {
+            self.notified += 1;
+        } else if self.start.is_none() {
+            self.start = Some(key);
+        }
+
+        self.len += 1;
+        key
+    }
+
+    /// Removes an entry from the list and returns its state.
+    fn remove(&mut self, key: usize, propagate: bool) -> State<T> {
+        let slot = mem::replace(&mut self.slots[key], Slot::Vacant(self.first_vacant));
+        self.first_vacant = Some(key);
+
+        let (state, prev, next) = match slot {
+            Slot::Occupied { state, prev, next } => (state, prev, next),
+            Slot::Vacant(_) => unreachable!(),
+        };
+
+        // Unlink from the previous entry.
+        match prev {
+            None => self.head = next,
+            Some(p) => *self.links_mut(p).1 = next,
+        }
+
+        // Unlink from the next entry.
+        match next {
+            None => self.tail = prev,
+            Some(n) => *self.links_mut(n).0 = prev,
+        }
+
+        // If this was the first unnotified entry, move the pointer to the next one.
+        if self.start == Some(key) {
+            self.start = next;
+        }
+
+        self.len -= 1;
+
+        match state {
+            State::Notified { additional, tag } if propagate => {
+                self.notified -= 1;
+
+                // Propagate the notification to the next entry.
+                let mut tag = Some(tag);
+                self.notify(GenericNotify::new(1, additional, move || {
+                    tag.take().expect("tag already taken")
+                }));
+
+                State::Created
+            }
+
+            state => {
+                if state.is_notified() {
+                    self.notified -= 1;
+                }
+
+                state
+            }
+        }
+    }
+
+    /// Notifies a number of listeners.
+    fn notify(&mut self, mut notify: impl NotificationPrivate<Tag = T>) -> usize {
+        let mut n = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if self.start.is_none() {
+            // Nobody is waiting for a notification, so store a permit if requested.
+            if n > 0 && self.sticky.is_none() && notify.is_sticky(Internal::new()) {
+                self.sticky = Some(notify.next_tag(Internal::new()));
+            }
+
+            return 0;
+        }
+
+        if !is_additional {
+            if n <= self.notified {
+                return 0;
+            }
+            n -= self.notified;
+        }
+
+        let mut count = 0;
+        while count < n {
+            let key = match self.start {
+                Some(key) => key,
+                None => break,
+            };
+
+            // Move the pointer forwards.
+            self.start = *self.links_mut(key).1;
+
+            // Set the state to `Notified` and queue the wakeup.
+            let tag = notify.next_tag(Internal::new());
+            let old = mem::replace(
+                self.state_mut(key),
+                State::Notified {
+                    additional: is_additional,
+                    tag,
+                },
+            );
+            if let State::Waiting(waker) = old {
+                self.wakers.push(waker);
+            }
+
+            self.notified += 1;
+            count += 1;
+        }
+
+        count
+    }
+}
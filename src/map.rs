@@ -0,0 +1,127 @@
+//! Transforming the tag of a listener.
+
+use crate::Listener;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Extension methods for [`Listener`]s.
+///
+/// This trait is implemented for every [`Listener`].
+pub trait ListenerExt<T>: Listener<T> + Sized {
+    /// Transforms the tag of the notification with `f`.
+    ///
+    /// The returned future resolves to `f(tag)` once the listener is notified. Until then, it
+    /// behaves exactly like the wrapped listener: in particular, dropping it after the listener
+    /// was notified passes the notification on to another listener. This lets libraries expose
+    /// futures with a domain-specific output without leaking the tag type of their events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, ListenerExt};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::<u32>::with_tag();
+    /// let listener = event.listen().map(|code| code == 0);
+    ///
+    /// event.notify(1.tag(0));
+    /// assert!(listener.await);
+    /// # });
+    /// ```
+    fn map<R, F>(self, f: F) -> Map<Self, F>
+    where
+        F: FnOnce(T) -> R,
+    {
+        Map {
+            listener: self,
+            f: Some(f),
+        }
+    }
+}
+
+impl<T, L: Listener<T>> ListenerExt<T> for L {}
+
+pin_project_lite::pin_project! {
+    /// A future that transforms the tag of a listener.
+    ///
+    /// This is created by [`ListenerExt::map()`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Map<L, F> {
+        #[pin]
+        listener: L,
+        f: Option<F>,
+    }
+}
+
+impl<L: fmt::Debug, F> fmt::Debug for Map<L, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("listener", &self.listener)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<L, F, R> Future for Map<L, F>
+where
+    L: Future,
+    F: FnOnce(L::Output) -> R,
+{
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let this = self.project();
+        assert!(this.f.is_some(), "`Map` polled after completion");
+
+        match this.listener.poll(cx) {
+            Poll::Ready(tag) => Poll::Ready((this.f.take().unwrap())(tag)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<L, F> Map<L, F> {
+    /// Blocks until the listener is notified, and returns the transformed tag.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, ListenerExt};
+    ///
+    /// let event = Event::<u32>::with_tag();
+    /// let listener = event.listen().map(|n| n * 2);
+    ///
+    /// event.notify(1.tag(21));
+    /// assert_eq!(listener.wait(), 42);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait<T, R>(self) -> R
+    where
+        L: Listener<T>,
+        F: FnOnce(T) -> R,
+    {
+        let f = self.f.expect("`Map` polled after completion");
+        f(self.listener.wait())
+    }
+
+    /// Returns the wrapped listener.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, ListenerExt};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen().map(|()| 1);
+    ///
+    /// let listener = listener.into_inner();
+    /// assert!(listener.listens_to(&event));
+    /// ```
+    pub fn into_inner(self) -> L {
+        self.listener
+    }
+}
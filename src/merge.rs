@@ -0,0 +1,131 @@
+//! Listening to several events at once.
+
+use crate::forward::Forward;
+use crate::{Event, EventListener, IntoNotification};
+
+use core::fmt;
+
+use std::boxed::Box;
+use std::vec::Vec;
+
+/// Merges several events into one that can be listened to.
+///
+/// Listeners of the returned [`Merge`] are notified whenever any of `events` is notified, and
+/// receive the index of that event as the tag. Each wait needs a single listener, instead of one
+/// listener per event as with [`select_events()`].
+///
+/// A notification of a constituent event is passed on with the same count and strategy, whether
+/// or not that event had any listeners of its own. Notifications are passed on as long as the
+/// [`Merge`] is alive.
+///
+/// This function is available with the `std` feature.
+///
+/// [`select_events()`]: crate::select_events
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{merge, Event, Listener};
+///
+/// let readable = Event::new();
+/// let writable = Event::new();
+/// let merged = merge([&readable, &writable]);
+///
+/// let listener = merged.listen();
+/// writable.notify(1);
+/// assert_eq!(listener.wait(), 1);
+/// ```
+pub fn merge<'a, T: 'a, I>(events: I) -> Merge<T>
+where
+    I: IntoIterator<Item = &'a Event<T>>,
+{
+    let event = Event::with_tag();
+
+    let forwards = events
+        .into_iter()
+        .enumerate()
+        .map(|(index, source)| {
+            let event = event.clone();
+            source.forward_with(Box::new(move |count, additional| {
+                if additional {
+                    event.notify(count.additional().tag(index));
+                } else {
+                    event.notify(count.tag(index));
+                }
+            }))
+        })
+        .collect();
+
+    Merge { forwards, event }
+}
+
+/// Several events merged into one that can be listened to.
+///
+/// This is created by [`merge()`].
+pub struct Merge<T = ()> {
+    /// The guards passing notifications on to `event`.
+    forwards: Vec<Forward<T>>,
+
+    /// The event that is notified with the index of the constituent event as the tag.
+    event: Event<usize>,
+}
+
+impl<T> fmt::Debug for Merge<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Merge")
+            .field("events", &self.forwards.len())
+            .field("event", &self.event)
+            .finish()
+    }
+}
+
+impl<T> Merge<T> {
+    /// Returns a guard listening for a notification of any of the merged events.
+    ///
+    /// The listener resolves to the index of the event that was notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{merge, Event, Listener};
+    ///
+    /// let events = [Event::new(), Event::new(), Event::new()];
+    /// let merged = merge(&events);
+    ///
+    /// let listener = merged.listen();
+    /// events[2].notify(1);
+    /// assert_eq!(listener.wait(), 2);
+    /// ```
+    pub fn listen(&self) -> EventListener<usize> {
+        self.event.listen()
+    }
+
+    /// Returns the number of merged events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{merge, Event};
+    ///
+    /// let events = [Event::new(), Event::new()];
+    /// assert_eq!(merge(&events).len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.forwards.len()
+    }
+
+    /// Returns `true` if no events were merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{merge, Event};
+    ///
+    /// assert!(merge(Vec::<&Event>::new()).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.forwards.is_empty()
+    }
+}
@@ -0,0 +1,123 @@
+//! Listener metadata, priorities and conditional notification.
+
+use crate::sync::Arc;
+use crate::{notify, Event, EventListener, IntoNotification, ListenerBox, NotificationPrivate};
+
+use core::mem::ManuallyDrop;
+
+impl<T> Event<T> {
+    /// Returns a guard listening for a notification, with metadata attached to it.
+    ///
+    /// The metadata is a plain `usize`, such as a kind of waiter or a shard number. It has no
+    /// effect on regular notifications, but lets [`Event::notify_if()`] pick out the listeners
+    /// it applies to.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// const READER: usize = 0;
+    /// const WRITER: usize = 1;
+    ///
+    /// let event = Event::new();
+    /// let reader = event.listen_with_meta(READER);
+    /// let writer = event.listen_with_meta(WRITER);
+    ///
+    /// assert_eq!(event.notify_if(usize::MAX, |meta| meta == WRITER), 1);
+    /// ```
+    pub fn listen_with_meta(&self, meta: usize) -> EventListener<T> {
+        self.listen_with(meta, 0)
+    }
+
+    /// Returns a guard listening for a notification, ahead of listeners with a lower priority.
+    ///
+    /// Notifications go to listeners with a higher priority first, and to listeners with the
+    /// same priority in the order they started listening. Listeners created by
+    /// [`Event::listen()`] have the lowest priority, `0`. This lets a lock prefer writers over
+    /// readers, for example.
+    ///
+    /// A listener that is already notified keeps its notification when a listener with a higher
+    /// priority comes along.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let reader = event.listen();
+    /// let writer = event.listen_with_priority(1);
+    ///
+    /// // The writer is notified first, even though it started listening last.
+    /// event.notify(1);
+    /// writer.wait();
+    ///
+    /// event.notify(1);
+    /// reader.wait();
+    /// ```
+    pub fn listen_with_priority(&self, priority: usize) -> EventListener<T> {
+        self.listen_with(0, priority)
+    }
+
+    /// Returns a guard listening for a notification, with the given metadata and priority.
+    #[cold]
+    fn listen_with(&self, meta: usize, priority: usize) -> EventListener<T> {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        // Allocate the listener on the heap and insert it.
+        let mut listener = ListenerBox::new(Arc::clone(&inner));
+        let this = listener.as_mut().project();
+        this.event.insert_with(this.listener, meta, priority);
+
+        EventListener { listener }
+    }
+
+    /// Notifies a number of active listeners whose metadata matches a predicate.
+    ///
+    /// This works like [`Event::notify()`], but listeners whose metadata, as given to
+    /// [`Event::listen_with_meta()`], doesn't match `pred` are skipped. Listeners created without
+    /// metadata have a metadata of `0`. Matching listeners are notified in the order they started
+    /// listening, and only matching listeners that are already notified count towards a
+    /// notification that is not [`additional`].
+    ///
+    /// The predicate is called with the list of listeners locked, so it should be cheap.
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// [`additional`]: IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::new();
+    /// let shard0 = event.listen_with_meta(0);
+    /// let shard1 = event.listen_with_meta(1);
+    /// let shard1_again = event.listen_with_meta(1);
+    ///
+    /// // Only wake listeners of shard 1.
+    /// assert_eq!(event.notify_if(1, |shard| shard == 1), 1);
+    /// assert_eq!(event.notify_if(1, |shard| shard == 1), 0);
+    /// assert_eq!(event.notify_if(1.additional(), |shard| shard == 1), 1);
+    ///
+    /// shard1.wait();
+    /// shard1_again.wait();
+    /// ```
+    pub fn notify_if<P>(&self, notify: impl IntoNotification<Tag = T>, pred: P) -> usize
+    where
+        P: FnMut(usize) -> bool,
+    {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        unsafe { &*self.inner() }.deliver(notify, |inner, notify| inner.notify_if(notify, pred))
+    }
+}
@@ -12,13 +12,19 @@
 #[path = "no_std/node.rs"]
 mod node;
 
-use node::{Node, NothingProducer, TaskWaiting};
-
-use crate::notify::{GenericNotify, Internal, Notification};
-use crate::sync::atomic::{AtomicBool, Ordering};
-use crate::sync::cell::{Cell, ConstPtr, UnsafeCell};
+use node::{Node, TaskWaiting};
+
+use crate::builder::Rng;
+use crate::notify::{GenericNotify, Internal, NothingProducer, Notification};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{ListenerSnapshot, Snapshot};
+use crate::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+use crate::sync::cell::{Cell, MutPtr, UnsafeCell};
 use crate::sync::Arc;
-use crate::{RegisterResult, State, Task, TaskRef};
+use crate::{
+    AllocError, EventBuilder, QueueOverflow, RegisterResult, State, Task, TaskRef, TryListenError,
+    WakeOrder,
+};
 
 use core::fmt;
 use core::marker::PhantomData;
@@ -27,6 +33,7 @@ use core::num::NonZeroUsize;
 use core::ops;
 use core::pin::Pin;
 
+use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 
 impl<T> crate::Inner<T> {
@@ -39,6 +46,43 @@ impl<T> crate::Inner<T> {
         })
     }
 
+    /// Free unused memory in the list, unless it is busy.
+    pub(crate) fn shrink_to_fit(&self) {
+        if let Some(mut lock) = self.try_lock() {
+            lock.shrink_to_fit();
+        }
+    }
+
+    /// Push an operation to the backup queue and try to apply it.
+    ///
+    /// If the queue is full and may not grow, this waits for the lock instead.
+    fn push(&self, node: Node<T>) {
+        let node = match self.list.push(node) {
+            Ok(()) => {
+                // Pairs with the fence in `ListGuard::drop`: either the lock holder sees the node,
+                // or we see that the list was unlocked and drain the queue ourselves.
+                atomic::fence(Ordering::SeqCst);
+
+                // Force a queue update.
+                self.queue_update();
+                return;
+            }
+
+            Err(node) => node,
+        };
+
+        loop {
+            if let Some(mut guard) = self.try_lock() {
+                if let Some(task) = node.apply(&mut guard) {
+                    guard.tasks.push(task);
+                }
+                return;
+            }
+
+            crate::sync::spin_loop();
+        }
+    }
+
     /// Force a queue update.
     fn queue_update(&self) {
         // Locking and unlocking the mutex will drain the queue if there is no contention.
@@ -63,12 +107,91 @@ impl<T> crate::Inner<T> {
             None => {
                 // Push it to the queue.
                 let (node, task_waiting) = Node::listener();
-                self.list.queue.push(node).unwrap();
                 *listener = Some(Listener::Queued(task_waiting));
+                self.push(node);
+            }
+        }
+    }
 
-                // Force a queue update.
-                self.queue_update();
+    /// Add a new listener to the list, reporting allocation failure.
+    ///
+    /// Does nothing if the list is already registered. Unlike `insert`, this never falls back to
+    /// the queue, since pushing to it may allocate. If the queue is full and configured to fail,
+    /// so does this.
+    pub(crate) fn try_insert(
+        &self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), AllocError> {
+        if listener.as_ref().as_pin_ref().is_some() {
+            // Already inserted.
+            return Ok(());
+        }
+
+        loop {
+            if let Some(mut lock) = self.try_lock() {
+                lock.try_reserve()?;
+                let key = lock.insert(State::Created);
+                *listener = Some(Listener::HasNode(key));
+                return Ok(());
+            }
+
+            if self.list.overflow == QueueOverflow::Fail && self.list.queue.is_full() {
+                return Err(AllocError);
+            }
+
+            crate::sync::spin_loop();
+        }
+    }
+
+    /// Add a new listener to the list, unless it already has as many as it admits.
+    pub(crate) fn insert_bounded(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), TryListenError> {
+        self.insert_bounded_with(listener, false)
+    }
+
+    /// Add a new listener to the list, unless it already has as many as it admits, reporting
+    /// allocation failure.
+    pub(crate) fn try_insert_bounded(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), TryListenError> {
+        self.insert_bounded_with(listener, true)
+    }
+
+    /// Add a new listener to the list, unless it already has as many as it admits.
+    ///
+    /// Like `try_insert`, this never falls back to the queue, since the cap can only be checked
+    /// under the lock. Allocation failure is only reported if `fallible` is set.
+    fn insert_bounded_with(
+        &self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+        fallible: bool,
+    ) -> Result<(), TryListenError> {
+        if listener.as_ref().as_pin_ref().is_some() {
+            // Already inserted.
+            return Ok(());
+        }
+
+        loop {
+            if let Some(mut lock) = self.try_lock() {
+                if lock.len >= self.list.listen_bound {
+                    return Err(TryListenError::Full);
+                }
+                if fallible {
+                    lock.try_reserve()?;
+                }
+                let key = lock.insert(State::Created);
+                *listener = Some(Listener::HasNode(key));
+                return Ok(());
+            }
+
+            if fallible && self.list.overflow == QueueOverflow::Fail && self.list.queue.is_full() {
+                return Err(TryListenError::Alloc);
             }
+
+            crate::sync::spin_loop();
         }
     }
 
@@ -95,10 +218,7 @@ impl<T> crate::Inner<T> {
                                 propagate,
                             };
 
-                            self.list.queue.push(node).unwrap();
-
-                            // Force a queue update.
-                            self.queue_update();
+                            self.push(node);
 
                             None
                         }
@@ -136,16 +256,17 @@ impl<T> crate::Inner<T> {
 
             None => {
                 // Push it to the queue.
-                let node = Node::Notify(GenericNotify::new(
-                    notify.count(Internal::new()),
-                    notify.is_additional(Internal::new()),
-                    NothingProducer::default(),
-                ));
-
-                self.list.queue.push(node).unwrap();
-
-                // Force a queue update.
-                self.queue_update();
+                let node = Node::Notify(
+                    GenericNotify::new(
+                        notify.count(Internal::new()),
+                        notify.is_additional(Internal::new()),
+                        NothingProducer::default(),
+                    )
+                    .set_sticky(notify.is_sticky(Internal::new()))
+                    .set_coalesced(notify.is_coalesced(Internal::new())),
+                );
+
+                self.push(node);
 
                 // We haven't notified anyone yet.
                 0
@@ -153,14 +274,121 @@ impl<T> crate::Inner<T> {
         }
     }
 
+    /// Deliver notifications deferred by `Event::notify_from_isr()`, unless the list is locked.
+    ///
+    /// If it is, the lock holder delivers them once it is done. This never spins, allocates or
+    /// processes the backup queue. Listeners are notified a few at a time, so that their tasks can
+    /// be woken from the stack once the list is unlocked.
+    pub(crate) fn notify_pending(&self) {
+        while let Some(mut list) = self.list.inner.try_lock_once() {
+            let notified = list.notify_pending_bounded(&self.pending);
+            self.store_counts(&list);
+            let wakeups = IsrWakeups::take(&mut list.tasks);
+            drop(list);
+
+            wakeups.wake();
+
+            // Whatever could not be delivered without allocating is left for the next lock holder.
+            if notified == 0 {
+                return;
+            }
+
+            // Another interrupt handler may have failed to lock the list after we checked.
+            atomic::fence(Ordering::SeqCst);
+            if self.pending.load(Ordering::Relaxed) == 0 {
+                return;
+            }
+        }
+    }
+
+    /// Update the atomic counters from a locked list.
+    fn store_counts(&self, list: &ListenerSlab<T>) {
+        let notified = if list.notified < list.len {
+            list.notified
+        } else {
+            usize::MAX
+        };
+
+        self.notified.store(notified, Ordering::Release);
+        self.listeners.store(list.len, Ordering::Release);
+    }
+
+    /// Closes the list, waking every listener that has not been notified yet.
+    #[cold]
+    pub(crate) fn close(&self, tag: fn() -> T) {
+        match self.try_lock() {
+            Some(mut guard) => guard.close(tag),
+
+            None => {
+                // Push it to the queue.
+                self.push(Node::Close(tag));
+            }
+        }
+    }
+
+    /// Take a snapshot of the listener list.
+    ///
+    /// Queued operations are counted rather than applied, so that the snapshot shows them.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        loop {
+            if let Some(list) = self.list.inner.try_lock() {
+                let mut listeners = Vec::with_capacity(list.len);
+
+                let mut key = list.head;
+                while let Some(k) = key {
+                    let entry = &list.listeners[k.get()];
+                    listeners.push(ListenerSnapshot::read(entry.state()));
+                    key = entry.next().get();
+                }
+
+                return Snapshot {
+                    listeners,
+                    notified: list.notified,
+                    queued: self.list.queued(),
+                };
+            }
+
+            crate::sync::spin_loop();
+        }
+    }
+
+    /// Notifies a number of entries and returns the exact number of notified entries.
+    ///
+    /// Instead of pushing the notification to the queue under contention, this spins until the
+    /// lock is available.
+    #[cold]
+    pub(crate) fn notify_exact(&self, notify: impl Notification<Tag = T>) -> usize {
+        loop {
+            if let Some(mut guard) = self.try_lock() {
+                return guard.notify(notify);
+            }
+
+            crate::sync::spin_loop();
+        }
+    }
+
     /// Register a task to be notified when the event is triggered.
     ///
     /// Returns `true` if the listener was already notified, and `false` otherwise. If the listener
     /// isn't inserted, returns `None`.
     pub(crate) fn register(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        task: TaskRef<'_>,
+    ) -> RegisterResult<T> {
+        self.register_with(listener, task, false)
+    }
+
+    /// Register a task to be notified when the event is triggered.
+    ///
+    /// If `rearm` is `true` and the listener was notified, it is moved to the back of the list
+    /// under the same lock instead of being removed, so it keeps receiving notifications.
+    pub(crate) fn register_with(
         &self,
         mut listener: Pin<&mut Option<Listener<T>>>,
         task: TaskRef<'_>,
+        rearm: bool,
     ) -> RegisterResult<T> {
         loop {
             match listener.as_mut().take() {
@@ -169,16 +397,12 @@ impl<T> crate::Inner<T> {
                     match self.try_lock() {
                         Some(mut guard) => {
                             // Fast path registration.
-                            return guard.register(listener, task);
+                            return guard.register_with(listener, task, rearm);
                         }
 
                         None => {
                             // Wait for the lock.
-                            let node = Node::Waiting(task.into_task());
-                            self.list.queue.push(node).unwrap();
-
-                            // Force a queue update.
-                            self.queue_update();
+                            self.push(Node::Waiting(task.into_task()));
 
                             return RegisterResult::Registered;
                         }
@@ -217,6 +441,61 @@ impl<T> crate::Inner<T> {
             }
         }
     }
+
+    /// Call `f` with the tag of the listener's notification, if it was notified.
+    ///
+    /// If the list is locked, the listener is reported as not notified yet.
+    pub(crate) fn peek<R>(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        f: impl FnOnce(&T) -> R,
+    ) -> Option<R> {
+        match *listener {
+            Some(Listener::HasNode(key)) => self.try_lock()?.peek(key, f),
+            _ => None,
+        }
+    }
+
+    /// Check whether the listener was notified, without registering a task.
+    ///
+    /// If the list is locked, the listener is reported as not notified yet.
+    pub(crate) fn check(&self, mut listener: Pin<&mut Option<Listener<T>>>) -> RegisterResult<T> {
+        loop {
+            match listener.as_mut().take() {
+                Some(Listener::HasNode(key)) => {
+                    *listener = Some(Listener::HasNode(key));
+                    return match self.try_lock() {
+                        Some(mut guard) => guard.check(listener),
+                        None => RegisterResult::Registered,
+                    };
+                }
+
+                Some(Listener::Queued(task_waiting)) => {
+                    // Force a queue update.
+                    self.queue_update();
+
+                    match task_waiting.status() {
+                        Some(key) => {
+                            assert!(key.get() != usize::MAX);
+
+                            // We're inserted now, adjust state.
+                            *listener = Some(Listener::HasNode(key));
+                        }
+
+                        None => {
+                            // We're still queued, so we can't have been notified.
+                            *listener = Some(Listener::Queued(task_waiting));
+                            return RegisterResult::Registered;
+                        }
+                    }
+                }
+
+                None => return RegisterResult::NeverInserted,
+
+                _ => unreachable!(),
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -226,19 +505,142 @@ pub(crate) struct List<T> {
 
     /// The queue of pending operations.
     queue: concurrent_queue::ConcurrentQueue<Node<T>>,
+
+    /// The operations that did not fit into the queue, if it may overflow.
+    spill: Option<concurrent_queue::ConcurrentQueue<Node<T>>>,
+
+    /// What to do when the queue is full.
+    overflow: QueueOverflow,
+
+    /// The number of listeners admitted by `Event::try_listen_bounded()`.
+    listen_bound: usize,
 }
 
 impl<T> List<T> {
-    pub(super) fn new() -> List<T> {
+    pub(super) fn new(config: &EventBuilder) -> List<T> {
+        let (queue, overflow) = match config.backup_queue {
+            Some((capacity, overflow)) => (
+                concurrent_queue::ConcurrentQueue::bounded(capacity),
+                overflow,
+            ),
+            None => (
+                concurrent_queue::ConcurrentQueue::unbounded(),
+                QueueOverflow::Allocate,
+            ),
+        };
+
         List {
-            inner: Mutex::new(ListenerSlab::new()),
-            queue: concurrent_queue::ConcurrentQueue::unbounded(),
+            inner: Mutex::new(ListenerSlab::with_config(config)),
+            spill: match overflow {
+                QueueOverflow::Allocate if queue.capacity().is_some() => {
+                    Some(concurrent_queue::ConcurrentQueue::unbounded())
+                }
+                _ => None,
+            },
+            queue,
+            overflow,
+            listen_bound: config.listen_bound.unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Get the number of listeners admitted by `Event::try_listen_bounded()`.
+    #[inline]
+    pub(crate) fn listen_bound(&self) -> usize {
+        self.listen_bound
+    }
+
+    /// Push an operation to the queue, or give it back if the queue is full.
+    fn push(&self, node: Node<T>) -> Result<(), Node<T>> {
+        if let Some(spill) = &self.spill {
+            // Once operations spill over, keep them in order until the spill is drained.
+            if !spill.is_empty() {
+                spill.push(node).unwrap();
+                return Ok(());
+            }
+        }
+
+        match self.queue.push(node) {
+            Ok(()) => Ok(()),
+            Err(err) => match &self.spill {
+                Some(spill) => {
+                    spill.push(err.into_inner()).unwrap();
+                    Ok(())
+                }
+                None => Err(err.into_inner()),
+            },
         }
     }
 
-    /// Try to get the total number of listeners without blocking.
-    pub(super) fn try_total_listeners(&self) -> Option<usize> {
-        self.inner.try_lock().map(|lock| lock.listeners.len())
+    /// Pop the next queued operation.
+    fn pop(&self) -> Option<Node<T>> {
+        match self.queue.pop() {
+            Ok(node) => Some(node),
+            Err(_) => self.spill.as_ref()?.pop().ok(),
+        }
+    }
+
+    /// Get the number of queued operations.
+    fn queued(&self) -> usize {
+        self.queue.len() + self.spill.as_ref().map_or(0, |spill| spill.len())
+    }
+
+    /// Get the total number of listeners and the number of notified listeners without blocking.
+    pub(super) fn try_counts(&self) -> Option<(usize, usize)> {
+        self.inner.try_lock().map(|lock| (lock.len, lock.notified))
+    }
+
+    /// Get the name of a listener's state without blocking.
+    pub(super) fn try_state_name(&self, listener: Option<&Listener<T>>) -> Option<&'static str> {
+        let key = match listener {
+            Some(Listener::HasNode(key)) => *key,
+            // The listener is still waiting to be inserted into the list.
+            Some(_) => return Some("Created"),
+            None => return Some("Idle"),
+        };
+
+        let lock = self.inner.try_lock()?;
+        let state = lock.listeners[key.get()].state();
+        let taken = state.replace(State::NotifiedTaken);
+        let name = taken.name();
+        state.set(taken);
+        Some(name)
+    }
+}
+
+/// The number of listeners that `Event::notify_from_isr()` notifies before unlocking the list to
+/// wake their tasks.
+const ISR_WAKEUPS: usize = 8;
+
+/// Tasks taken out of the list by `Event::notify_from_isr()`, to be woken once it is unlocked.
+///
+/// They are moved to the stack, so that the buffer in the list keeps its capacity and nothing has
+/// to be allocated.
+struct IsrWakeups {
+    tasks: [Option<Task>; ISR_WAKEUPS],
+}
+
+impl IsrWakeups {
+    const NONE: Option<Task> = None;
+
+    /// Take all tasks out of `tasks`, keeping its capacity.
+    ///
+    /// `tasks` must not hold more than `ISR_WAKEUPS` tasks.
+    fn take(tasks: &mut Vec<Task>) -> Self {
+        debug_assert!(tasks.len() <= ISR_WAKEUPS);
+
+        let mut wakeups = [Self::NONE; ISR_WAKEUPS];
+        for (slot, task) in wakeups.iter_mut().zip(tasks.drain(..)) {
+            *slot = Some(task);
+        }
+
+        Self { tasks: wakeups }
+    }
+
+    /// Wake up all of the tasks, in the order they were added.
+    fn wake(self) {
+        for task in self.tasks.into_iter().flatten() {
+            task.wake();
+        }
     }
 }
 
@@ -263,7 +665,7 @@ impl<T> ListGuard<'_, T> {
         self.tasks.extend(start_node.apply(guard));
 
         // Process all remaining nodes.
-        while let Ok(node) = self.inner.list.queue.pop() {
+        while let Some(node) = self.inner.list.pop() {
             self.tasks.extend(node.apply(guard));
         }
     }
@@ -271,7 +673,7 @@ impl<T> ListGuard<'_, T> {
     #[inline]
     fn process_nodes(&mut self) {
         // Process every node left in the queue.
-        if let Ok(start_node) = self.inner.list.queue.pop() {
+        if let Some(start_node) = self.inner.list.pop() {
             self.process_nodes_slow(start_node);
         }
     }
@@ -297,20 +699,20 @@ impl<T> Drop for ListGuard<'_, T> {
             // Process every node left in the queue.
             self.process_nodes();
 
+            // Deliver notifications from interrupt handlers that could not lock the list.
+            let mut list = self.guard.take().unwrap();
+            list.notify_pending(&self.inner.pending);
+
             // Update the atomic `notified` counter.
-            let list = self.guard.take().unwrap();
-            let notified = if list.notified < list.len {
-                list.notified
-            } else {
-                usize::MAX
-            };
+            self.inner.store_counts(&list);
 
-            self.inner.notified.store(notified, Ordering::Release);
+            // Take the tasks woken up while the list was locked.
+            self.tasks.append(&mut list.tasks);
 
             // Drop the actual lock.
             drop(list);
 
-            // Wakeup all tasks.
+            // Wakeup all tasks, now that they won't run into the lock right away.
             for task in self.tasks.drain(..) {
                 task.wake();
             }
@@ -320,10 +722,15 @@ impl<T> Drop for ListGuard<'_, T> {
             // notifications not being properly delivered, or listeners not being added to the list.
             // Therefore check before we finish dropping if there is anything left in the queue, and
             // if so, lock it again and force a queue update.
-            if !self.inner.list.queue.is_empty() {
+            //
+            // The same goes for notifications from interrupt handlers.
+            atomic::fence(Ordering::SeqCst);
+            if self.inner.list.queued() > 0 || self.inner.pending.load(Ordering::Relaxed) > 0 {
                 self.guard = self.inner.list.inner.try_lock();
             }
         }
+
+        self.inner.notify_changed();
     }
 }
 
@@ -468,6 +875,24 @@ pub(crate) struct ListenerSlab<T> {
     /// The index of the first `Empty` entry, or the length of the list plus one if there
     /// are no empty entries.
     first_empty: NonZeroUsize,
+
+    /// Permits stored by notifications that found nobody to notify, waiting for new listeners.
+    permits: VecDeque<T>,
+
+    /// The number of permits that are kept, or zero if only sticky notifications keep one.
+    permit_capacity: usize,
+
+    /// Makes the tags of closed listeners, once the event has been closed.
+    close_tag: Option<fn() -> T>,
+
+    /// The order in which entries are notified.
+    wake_order: WakeOrder,
+
+    /// Picks entries for `WakeOrder::Random`.
+    rng: Rng,
+
+    /// Tasks to wake up once the list is unlocked.
+    tasks: Vec<Task>,
 }
 
 impl<T> fmt::Debug for ListenerSlab<T> {
@@ -480,26 +905,101 @@ impl<T> fmt::Debug for ListenerSlab<T> {
             .field("notified", &self.notified)
             .field("len", &self.len)
             .field("first_empty", &self.first_empty)
+            .field("permits", &self.permits.len())
+            .field("closed", &self.close_tag.is_some())
+            .field("wake_order", &self.wake_order)
             .finish()
     }
 }
 
 impl<T> ListenerSlab<T> {
     /// Create a new, empty list.
+    #[cfg(test)]
     pub(crate) fn new() -> Self {
+        Self::with_config(&EventBuilder::new())
+    }
+
+    /// Wake up the tasks that would be woken up once the list is unlocked.
+    #[cfg(test)]
+    fn wake_tasks(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.wake();
+        }
+    }
+
+    /// Create a new, empty list with the given configuration.
+    pub(crate) fn with_config(config: &EventBuilder) -> Self {
+        let mut listeners = Vec::with_capacity(config.capacity.saturating_add(1));
+        listeners.push(Entry::Sentinel);
+
         Self {
-            listeners: alloc::vec![Entry::Sentinel],
+            listeners,
             head: None,
             tail: None,
             start: None,
             notified: 0,
             len: 0,
             first_empty: unsafe { NonZeroUsize::new_unchecked(1) },
+            permits: VecDeque::new(),
+            permit_capacity: config.permit_capacity,
+            close_tag: None,
+            wake_order: config.wake_order,
+            rng: Rng::new(),
+            tasks: Vec::with_capacity(ISR_WAKEUPS),
+        }
+    }
+
+    /// Makes sure that the next call to `insert` does not need to allocate.
+    pub(crate) fn try_reserve(&mut self) -> Result<(), AllocError> {
+        if self.first_empty.get() == self.listeners.len() {
+            self.listeners.try_reserve(1).map_err(|_| AllocError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Frees the empty slots at the end of the list and any unused capacity.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        // Drop the empty slots after the last listener.
+        let len = self
+            .listeners
+            .iter()
+            .rposition(|entry| matches!(entry, Entry::Listener { .. }))
+            .map_or(1, |last| last + 1);
+        self.listeners.truncate(len);
+
+        // Rebuild the chain of empty slots, since it may point into the truncated part.
+        let mut next = unsafe { NonZeroUsize::new_unchecked(len) };
+        for (index, entry) in self.listeners.iter_mut().enumerate().skip(1).rev() {
+            if let Entry::Empty(slot) = entry {
+                *slot = next;
+                next = unsafe { NonZeroUsize::new_unchecked(index) };
+            }
         }
+        self.first_empty = next;
+
+        self.listeners.shrink_to_fit();
+        self.permits.shrink_to_fit();
+
+        // Keep room for the tasks woken by `Event::notify_from_isr()`.
+        self.tasks.shrink_to(ISR_WAKEUPS);
     }
 
     /// Inserts a new entry into the list.
-    pub(crate) fn insert(&mut self, state: State<T>) -> NonZeroUsize {
+    pub(crate) fn insert(&mut self, mut state: State<T>) -> NonZeroUsize {
+        // Listeners on a closed list are closed right away. Otherwise, if a notification left a
+        // permit behind, this listener takes it.
+        if let Some(close_tag) = self.close_tag {
+            state = State::Closed(close_tag);
+        } else if let Some(tag) = self.permits.pop_front() {
+            state = State::Notified {
+                additional: false,
+                tag,
+            };
+        }
+        let notified = state.is_notified();
+        let closed = matches!(state, State::Closed(_));
+
         // Add the new entry into the list.
         let key = {
             let entry = Entry::Listener {
@@ -530,7 +1030,7 @@ impl<T> ListenerSlab<T> {
         };
 
         // Replace the tail with the new entry.
-        match mem::replace(&mut self.tail, Some(key)) {
+        match self.tail.replace(key) {
             None => self.head = Some(key),
             Some(tail) => {
                 let tail = &self.listeners[tail.get()];
@@ -538,9 +1038,11 @@ impl<T> ListenerSlab<T> {
             }
         }
 
-        // If there are no listeners that have been notified, then the new listener is the next
-        // listener to be notified.
-        if self.start.is_none() {
+        if notified {
+            self.notified += 1;
+        } else if !closed && self.start.is_none() {
+            // If there are no listeners that have been notified, then the new listener is the next
+            // listener to be notified.
             self.start = Some(key);
         }
 
@@ -612,6 +1114,30 @@ impl<T> ListenerSlab<T> {
     pub(crate) fn notify(&mut self, mut notify: impl Notification<Tag = T>) -> usize {
         let mut n = notify.count(Internal::new());
         let is_additional = notify.is_additional(Internal::new());
+
+        if notify.is_coalesced(Internal::new()) && (self.notified > 0 || !self.permits.is_empty()) {
+            // A notification is already pending, so merge this one into it.
+            return 0;
+        }
+
+        if self.start.is_none() {
+            // Nobody is waiting for a notification, so store permits if requested.
+            if self.permit_capacity > 0 {
+                if !is_additional {
+                    n = n.saturating_sub(self.notified);
+                }
+                self.store_permits(n, is_additional, &mut notify);
+            } else if n > 0
+                && self.close_tag.is_none()
+                && self.permits.is_empty()
+                && notify.is_sticky(Internal::new())
+            {
+                self.permits.push_back(notify.next_tag(Internal::new()));
+            }
+
+            return 0;
+        }
+
         if !is_additional {
             // Make sure we're not notifying more than we have.
             if n <= self.notified {
@@ -620,26 +1146,40 @@ impl<T> ListenerSlab<T> {
             n -= self.notified;
         }
 
+        // Notifying every unnotified entry, such as with `usize::MAX`, needs no picking.
+        let unnotified = self.len - self.notified;
+        if n >= unnotified && self.wake_order != WakeOrder::Random {
+            self.notify_all(&mut notify, is_additional);
+            self.store_permits(n - unnotified, is_additional, &mut notify);
+            return unnotified;
+        }
+
         let original_count = n;
         while n > 0 {
             n -= 1;
 
             // Notify the next entry.
             match self.start {
-                None => return original_count - n - 1,
+                None => {
+                    // Keep whatever is left over for future listeners.
+                    self.store_permits(n + 1, is_additional, &mut notify);
+                    return original_count - n - 1;
+                }
 
-                Some(e) => {
-                    // Get the entry and move the pointer forwards.
-                    let entry = &self.listeners[e.get()];
-                    self.start = entry.next().get();
+                Some(start) => {
+                    // Move the entry in front of the first unnotified entry, so that every
+                    // notified entry still comes before it.
+                    let e = self.pick(start);
+                    self.move_to_notified(e);
 
                     // Set the state to `Notified` and notify.
+                    let entry = &self.listeners[e.get()];
                     let tag = notify.next_tag(Internal::new());
                     if let State::Task(task) = entry.state().replace(State::Notified {
                         tag,
                         additional: is_additional,
                     }) {
-                        task.wake();
+                        self.tasks.push(task);
                     }
 
                     // Bump the notified count.
@@ -651,14 +1191,178 @@ impl<T> ListenerSlab<T> {
         original_count - n
     }
 
+    /// Notifies every unnotified entry in wake order, in a single walk over the list.
+    ///
+    /// The entries stay where they are, since they all end up notified.
+    fn notify_all(&mut self, notify: &mut impl Notification<Tag = T>, is_additional: bool) {
+        let first = self.start.take();
+        let lifo = self.wake_order == WakeOrder::Lifo;
+        let mut key = if lifo { self.tail } else { first };
+
+        while let Some(k) = key {
+            let entry = &self.listeners[k.get()];
+            key = if !lifo {
+                entry.next().get()
+            } else if Some(k) == first {
+                None
+            } else {
+                entry.prev().get()
+            };
+
+            // Set the state to `Notified` and notify.
+            let tag = notify.next_tag(Internal::new());
+            if let State::Task(task) = entry.state().replace(State::Notified {
+                tag,
+                additional: is_additional,
+            }) {
+                self.tasks.push(task);
+            }
+        }
+
+        self.notified = self.len;
+    }
+
+    /// Stores up to `n` permits, as far as the permit capacity allows.
+    fn store_permits(
+        &mut self,
+        mut n: usize,
+        is_additional: bool,
+        notify: &mut impl Notification<Tag = T>,
+    ) {
+        if self.close_tag.is_some() {
+            return;
+        }
+
+        // Permits count as notified listeners.
+        if !is_additional {
+            n = n.saturating_sub(self.permits.len());
+        }
+
+        while n > 0 && self.permits.len() < self.permit_capacity {
+            self.permits.push_back(notify.next_tag(Internal::new()));
+            n -= 1;
+        }
+    }
+
+    /// Picks the unnotified entry to notify next, according to the wake order.
+    fn pick(&mut self, start: NonZeroUsize) -> NonZeroUsize {
+        match self.wake_order {
+            WakeOrder::Fifo => start,
+
+            // Every entry after the first unnotified one is unnotified, so this includes the tail.
+            WakeOrder::Lifo => self.tail.unwrap(),
+
+            WakeOrder::Random => {
+                let unnotified = self.len - self.notified;
+                let mut e = start;
+                for _ in 0..self.rng.below(unnotified) {
+                    e = self.listeners[e.get()].next().get().unwrap();
+                }
+                e
+            }
+        }
+    }
+
+    /// Moves an unnotified entry right in front of the first unnotified entry, and advances the
+    /// first unnotified entry past it if it was that entry.
+    fn move_to_notified(&mut self, key: NonZeroUsize) {
+        let start = self.start.expect("no unnotified entries");
+        if start == key {
+            self.start = self.listeners[key.get()].next().get();
+            return;
+        }
+
+        let entry = &self.listeners[key.get()];
+        let prev = entry.prev().get();
+        let next = entry.next().get();
+
+        // Unlink the entry. It comes after `start`, so it has a previous entry.
+        self.listeners[prev.unwrap().get()].next().set(next);
+        match next {
+            None => self.tail = prev,
+            Some(n) => self.listeners[n.get()].prev().set(prev),
+        }
+
+        // Link it in again in front of `start`.
+        let before = self.listeners[start.get()].prev().get();
+        entry.prev().set(before);
+        entry.next().set(Some(start));
+        match before {
+            None => self.head = Some(key),
+            Some(b) => self.listeners[b.get()].next().set(Some(key)),
+        }
+        self.listeners[start.get()].prev().set(Some(key));
+    }
+
+    /// Deliver notifications deferred by `Event::notify_from_isr()`.
+    fn notify_pending(&mut self, pending: &AtomicUsize) {
+        if pending.load(Ordering::Relaxed) > 0 {
+            let n = pending.swap(0, Ordering::SeqCst);
+            self.notify(GenericNotify::new(n, false, NothingProducer::default()));
+        }
+    }
+
+    /// Deliver notifications deferred by `Event::notify_from_isr()` without allocating.
+    ///
+    /// At most `ISR_WAKEUPS` listeners are notified, and only as many as there is room for in the
+    /// task buffer. Permits are never stored, since that may allocate. Whatever is left over goes
+    /// back into `pending`. Returns the number of listeners that were notified.
+    fn notify_pending_bounded(&mut self, pending: &AtomicUsize) -> usize {
+        if pending.load(Ordering::Relaxed) == 0 {
+            return 0;
+        }
+        let n = pending.swap(0, Ordering::SeqCst);
+
+        let room = (self.tasks.capacity() - self.tasks.len()).min(ISR_WAKEUPS);
+        let unnotified = self.len - self.notified;
+        let now = n.min(self.notified + unnotified.min(room));
+        let notified = self.notify(GenericNotify::new(now, false, NothingProducer::default()));
+
+        // Leave the rest for later, if there is anything it could still do.
+        if now < n && (now < self.len || self.permit_capacity > 0) {
+            pending.fetch_max(n, Ordering::SeqCst);
+        }
+
+        notified
+    }
+
+    /// Closes the list, waking every listener that has not been notified yet.
+    #[cold]
+    pub(crate) fn close(&mut self, tag: fn() -> T) {
+        self.close_tag = Some(tag);
+        self.permits.clear();
+
+        // Mark every unnotified entry as closed.
+        while let Some(e) = self.start {
+            let entry = &self.listeners[e.get()];
+            self.start = entry.next().get();
+
+            if let State::Task(task) = entry.state().replace(State::Closed(tag)) {
+                self.tasks.push(task);
+            }
+        }
+    }
+
     /// Register a task to be notified when the event is triggered.
     ///
     /// Returns `true` if the listener was already notified, and `false` otherwise. If the listener
     /// isn't inserted, returns `None`.
+    #[cfg(test)]
     pub(crate) fn register(
+        &mut self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        task: TaskRef<'_>,
+    ) -> RegisterResult<T> {
+        self.register_with(listener, task, false)
+    }
+
+    /// Register a task, moving the listener to the back of the list if it was notified and
+    /// `rearm` is `true`.
+    pub(crate) fn register_with(
         &mut self,
         mut listener: Pin<&mut Option<Listener<T>>>,
         task: TaskRef<'_>,
+        rearm: bool,
     ) -> RegisterResult<T> {
         let key = match *listener {
             Some(Listener::HasNode(key)) => key,
@@ -672,7 +1376,11 @@ impl<T> ListenerSlab<T> {
             State::Notified { tag, .. } => {
                 // The listener was already notified, so we don't need to do anything.
                 self.remove(key, false);
-                *listener = None;
+                *listener = if rearm {
+                    Some(Listener::HasNode(self.insert(State::Created)))
+                } else {
+                    None
+                };
                 RegisterResult::Notified(tag)
             }
 
@@ -687,6 +1395,12 @@ impl<T> ListenerSlab<T> {
                 RegisterResult::Registered
             }
 
+            State::Closed(close_tag) => {
+                // The event was closed, there is nothing to wait for.
+                entry.state().set(State::Closed(close_tag));
+                RegisterResult::Closed(close_tag)
+            }
+
             _ => {
                 // Register the task.
                 entry.state().set(State::Task(task.into_task()));
@@ -694,6 +1408,53 @@ impl<T> ListenerSlab<T> {
             }
         }
     }
+
+    /// Call `f` with the tag of the listener's notification, if it was notified.
+    pub(crate) fn peek<R>(&self, key: NonZeroUsize, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let entry = &self.listeners[key.get()];
+
+        let state = entry.state().replace(State::NotifiedTaken);
+        let result = match &state {
+            State::Notified { tag, .. } => Some(f(tag)),
+            _ => None,
+        };
+        entry.state().set(state);
+
+        result
+    }
+
+    /// Check whether the listener was notified, without registering a task.
+    pub(crate) fn check(
+        &mut self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+    ) -> RegisterResult<T> {
+        let key = match *listener {
+            Some(Listener::HasNode(key)) => key,
+            _ => return RegisterResult::NeverInserted,
+        };
+
+        let entry = &self.listeners[key.get()];
+
+        match entry.state().replace(State::NotifiedTaken) {
+            State::Notified { tag, .. } => {
+                // The listener was notified, remove it.
+                self.remove(key, false);
+                *listener = None;
+                RegisterResult::Notified(tag)
+            }
+
+            State::Closed(close_tag) => {
+                entry.state().set(State::Closed(close_tag));
+                RegisterResult::Closed(close_tag)
+            }
+
+            state => {
+                // Leave the task as it is.
+                entry.state().set(state);
+                RegisterResult::Registered
+            }
+        }
+    }
 }
 
 pub(crate) enum Listener<T> {
@@ -730,6 +1491,10 @@ impl<T> PartialEq for Listener<T> {
 }
 
 /// A simple mutex type that optimistically assumes that the lock is uncontended.
+///
+/// With the `critical-section` feature, the lock is only ever held inside a critical section.
+/// Since nobody else can hold it while we are in one, failing to lock it means that it is being
+/// locked reentrantly, so there is no point in spinning.
 pub(crate) struct Mutex<T> {
     /// The inner value.
     value: UnsafeCell<T>,
@@ -758,7 +1523,20 @@ impl<T> Mutex<T> {
     }
 
     /// Lock the mutex.
+    #[cfg(not(feature = "critical-section"))]
     pub(crate) fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.try_lock_once().or_else(|| self.try_lock_slow())
+    }
+
+    /// Lock the mutex.
+    #[cfg(feature = "critical-section")]
+    pub(crate) fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.try_lock_once()
+    }
+
+    /// Lock the mutex without spinning.
+    #[cfg(not(feature = "critical-section"))]
+    pub(crate) fn try_lock_once(&self) -> Option<MutexGuard<'_, T>> {
         // Try to lock the mutex.
         if self
             .locked
@@ -768,13 +1546,40 @@ impl<T> Mutex<T> {
             // We have successfully locked the mutex.
             Some(MutexGuard {
                 mutex: self,
-                guard: self.value.get(),
+                guard: self.value.get_mut(),
             })
         } else {
-            self.try_lock_slow()
+            None
         }
     }
 
+    /// Lock the mutex without spinning.
+    #[cfg(feature = "critical-section")]
+    pub(crate) fn try_lock_once(&self) -> Option<MutexGuard<'_, T>> {
+        // SAFETY: The critical section is released in the same thread, either below or when the
+        // guard is dropped. Guards are dropped in the reverse order of locking.
+        let restore = unsafe { critical_section::acquire() };
+
+        // Try to lock the mutex.
+        if self
+            .locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            // We have successfully locked the mutex.
+            Some(MutexGuard {
+                mutex: self,
+                guard: self.value.get_mut(),
+                restore,
+            })
+        } else {
+            // SAFETY: This pairs with the `acquire` above.
+            unsafe { critical_section::release(restore) };
+            None
+        }
+    }
+
+    #[cfg(not(feature = "critical-section"))]
     #[cold]
     fn try_lock_slow(&self) -> Option<MutexGuard<'_, T>> {
         // Assume that the contention is short-term.
@@ -790,7 +1595,7 @@ impl<T> Mutex<T> {
                 // We have successfully locked the mutex.
                 return Some(MutexGuard {
                     mutex: self,
-                    guard: self.value.get(),
+                    guard: self.value.get_mut(),
                 });
             }
 
@@ -805,12 +1610,24 @@ impl<T> Mutex<T> {
 
 pub(crate) struct MutexGuard<'a, T> {
     mutex: &'a Mutex<T>,
-    guard: ConstPtr<T>,
+    guard: MutPtr<T>,
+
+    /// The state to restore when the critical section is released.
+    ///
+    /// The guard holds a raw pointer, so it can't be sent to another thread to be released there.
+    #[cfg(feature = "critical-section")]
+    restore: critical_section::RestoreState,
 }
 
 impl<'a, T> Drop for MutexGuard<'a, T> {
     fn drop(&mut self) {
         self.mutex.locked.store(false, Ordering::Release);
+
+        // SAFETY: This pairs with the `acquire` in `try_lock_once`.
+        #[cfg(feature = "critical-section")]
+        unsafe {
+            critical_section::release(self.restore)
+        };
     }
 }
 
@@ -824,7 +1641,7 @@ impl<'a, T> ops::Deref for MutexGuard<'a, T> {
 
 impl<'a, T> ops::DerefMut for MutexGuard<'a, T> {
     fn deref_mut(&mut self) -> &mut T {
-        unsafe { self.guard.deref_mut() }
+        unsafe { self.guard.deref() }
     }
 }
 
@@ -856,6 +1673,39 @@ mod tests {
         assert_eq!(*guard, 2);
     }
 
+    #[test]
+    fn listener_slab_shrink_to_fit() {
+        let mut listeners = ListenerSlab::<()>::with_config(&EventBuilder::new().capacity(8));
+        assert!(listeners.listeners.capacity() >= 9);
+
+        // Insert a few listeners and remove some of them, including the last one.
+        let key1 = listeners.insert(State::Created);
+        let key2 = listeners.insert(State::Created);
+        let key3 = listeners.insert(State::Created);
+        let key4 = listeners.insert(State::Created);
+        listeners.remove(key2, false);
+        listeners.remove(key4, false);
+
+        // The trailing empty slot is dropped, and the hole is reused first.
+        listeners.shrink_to_fit();
+        assert_eq!(listeners.listeners.len(), 4);
+        assert_eq!(listeners.first_empty, key2);
+        assert_eq!(listeners.listeners[2], Entry::Empty(key4));
+
+        assert_eq!(listeners.insert(State::Created), key2);
+        assert_eq!(listeners.insert(State::Created), key4);
+        assert_eq!(listeners.len, 4);
+
+        // An empty list goes back to just the sentinel.
+        for key in [key1, key2, key3, key4] {
+            listeners.remove(key, false);
+        }
+        listeners.shrink_to_fit();
+        assert_eq!(listeners.listeners.len(), 1);
+        assert_eq!(listeners.first_empty, NonZeroUsize::new(1).unwrap());
+        assert_eq!(listeners.insert(State::Created), key1);
+    }
+
     #[test]
     fn smoke_listener_slab() {
         let mut listeners = ListenerSlab::<()>::new();
@@ -1111,6 +1961,9 @@ mod tests {
             }
         );
 
+        // The task is woken up once the list is unlocked.
+        assert!(!woken.load(Ordering::SeqCst));
+        listeners.wake_tasks();
         assert!(woken.load(Ordering::SeqCst));
         assert_eq!(
             listeners.register(
@@ -1286,6 +2139,7 @@ mod tests {
 
         // Notify the second listener.
         listeners.notify(GenericNotify::new(1, false, || ()));
+        listeners.wake_tasks();
         assert!(woken.load(Ordering::SeqCst));
 
         assert_eq!(listeners.len, 2);
@@ -1360,6 +2214,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn backup_queue_overflow() {
+        let config = EventBuilder::new().backup_queue(1, QueueOverflow::Allocate);
+        let inner = crate::Inner::<()>::with_config(&config);
+        let (mut listener1, mut listener2) = (None, None);
+        inner.insert(Pin::new(&mut listener1));
+
+        {
+            let _lock = inner.list.inner.try_lock().unwrap();
+
+            // The second operation spills over.
+            inner.notify(GenericNotify::new(1, false, || ()));
+            inner.insert(Pin::new(&mut listener2));
+            assert!(inner.list.queue.is_full());
+            assert_eq!(inner.list.queued(), 2);
+        }
+
+        // Both are applied, in order, once the list is unlocked.
+        drop(inner.try_lock());
+        assert_eq!(inner.list.queued(), 0);
+        assert!(inner
+            .remove(Pin::new(&mut listener1), false)
+            .unwrap()
+            .is_notified());
+        assert!(!inner
+            .remove(Pin::new(&mut listener2), false)
+            .unwrap()
+            .is_notified());
+
+        // Giving up when the queue is full only applies to fallible operations.
+        let config = EventBuilder::new().backup_queue(1, QueueOverflow::Fail);
+        let inner = crate::Inner::<()>::with_config(&config);
+        let mut listener = None;
+
+        {
+            let _lock = inner.list.inner.try_lock().unwrap();
+            inner.notify(GenericNotify::new(1, false, || ()));
+            assert_eq!(inner.try_insert(Pin::new(&mut listener)), Err(AllocError));
+            assert!(listener.is_none());
+        }
+
+        inner.try_insert(Pin::new(&mut listener)).unwrap();
+        assert!(inner
+            .remove(Pin::new(&mut listener), false)
+            .unwrap()
+            .is_notified());
+    }
+
     #[test]
     fn uncontended_inner() {
         let inner = crate::Inner::new();
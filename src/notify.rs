@@ -1,8 +1,10 @@
 //! The `Notification` trait for specifying notification.
 
 use crate::sync::atomic::{self, Ordering};
-#[cfg(feature = "std")]
 use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+use core::num;
 
 pub(crate) use __private::Internal;
 
@@ -25,6 +27,18 @@ pub trait NotificationPrivate {
     /// Get the number of listeners to wake.
     fn count(&self, internal: Internal) -> usize;
 
+    /// Whether or not a permit should be stored if there are no listeners to notify.
+    fn is_sticky(&self, internal: Internal) -> bool;
+
+    /// Whether or not the notification should be dropped while a listener is already notified.
+    fn is_coalesced(&self, internal: Internal) -> bool;
+
+    /// Called right before the listeners are notified.
+    fn before_notify(&mut self, internal: Internal);
+
+    /// Called right after the listeners have been notified, with the number of notified listeners.
+    fn after_notify(&mut self, count: usize, internal: Internal);
+
     /// Get a tag to be associated with a notification.
     ///
     /// This method is expected to be called `count()` times.
@@ -86,6 +100,18 @@ impl NotificationPrivate for Notify {
         self.0
     }
 
+    fn is_sticky(&self, _: Internal) -> bool {
+        false
+    }
+
+    fn is_coalesced(&self, _: Internal) -> bool {
+        false
+    }
+
+    fn before_notify(&mut self, _: Internal) {}
+
+    fn after_notify(&mut self, _: usize, _: Internal) {}
+
     fn next_tag(&mut self, _: Internal) -> Self::Tag {}
 }
 
@@ -119,6 +145,22 @@ where
         self.0.count(i)
     }
 
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.0.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.0.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.0.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.0.after_notify(count, i);
+    }
+
     fn next_tag(&mut self, i: Internal) -> Self::Tag {
         self.0.next_tag(i)
     }
@@ -154,6 +196,256 @@ where
         self.0.count(i)
     }
 
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.0.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.0.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.0.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.0.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        self.0.next_tag(i)
+    }
+}
+
+/// Emit a fence with the given ordering for this notification.
+#[derive(Debug, Clone)]
+#[doc(hidden)]
+pub struct FenceWith<N: ?Sized> {
+    ordering: Ordering,
+    inner: N,
+}
+
+impl<N> FenceWith<N> {
+    /// Create a new `FenceWith` with the given ordering and notification.
+    fn new(ordering: Ordering, inner: N) -> Self {
+        Self { ordering, inner }
+    }
+}
+
+impl<N> NotificationPrivate for FenceWith<N>
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, _: Internal) {
+        match self.ordering {
+            Ordering::Relaxed => {}
+            Ordering::SeqCst => full_fence(),
+            ordering => atomic::fence(ordering),
+        }
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.inner.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.inner.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        self.inner.next_tag(i)
+    }
+}
+
+/// Run callbacks before and after the listeners are notified.
+#[doc(hidden)]
+pub struct Inspect<N: ?Sized, B, A> {
+    before: Option<B>,
+    after: Option<A>,
+    inner: N,
+}
+
+impl<N: fmt::Debug, B, A> fmt::Debug for Inspect<N, B, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N, B, A> Inspect<N, B, A> {
+    /// Create a new `Inspect` with the given callbacks and notification.
+    fn new(before: B, after: A, inner: N) -> Self {
+        Self {
+            before: Some(before),
+            after: Some(after),
+            inner,
+        }
+    }
+}
+
+impl<N, B, A> NotificationPrivate for Inspect<N, B, A>
+where
+    N: Notification + ?Sized,
+    B: FnOnce(),
+    A: FnOnce(usize),
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.inner.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.inner.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+        if let Some(before) = self.before.take() {
+            before();
+        }
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        if let Some(after) = self.after.take() {
+            after(count);
+        }
+        self.inner.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        self.inner.next_tag(i)
+    }
+}
+
+/// Store a permit if there are no listeners to notify.
+#[derive(Debug, Clone)]
+#[doc(hidden)]
+pub struct Sticky<N: ?Sized>(N);
+
+impl<N> Sticky<N> {
+    /// Create a new `Sticky` with the given notification.
+    fn new(inner: N) -> Self {
+        Self(inner)
+    }
+}
+
+impl<N> NotificationPrivate for Sticky<N>
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.0.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.0.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.0.count(i)
+    }
+
+    fn is_sticky(&self, _: Internal) -> bool {
+        true
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.0.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.0.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.0.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        self.0.next_tag(i)
+    }
+}
+
+/// Drop the notification while a listener is already notified.
+#[derive(Debug, Clone)]
+#[doc(hidden)]
+pub struct Coalesced<N: ?Sized>(N);
+
+impl<N> Coalesced<N> {
+    /// Create a new `Coalesced` with the given notification.
+    fn new(inner: N) -> Self {
+        Self(inner)
+    }
+}
+
+impl<N> NotificationPrivate for Coalesced<N>
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.0.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.0.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.0.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.0.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, _: Internal) -> bool {
+        true
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.0.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.0.after_notify(count, i);
+    }
+
     fn next_tag(&mut self, i: Internal) -> Self::Tag {
         self.0.next_tag(i)
     }
@@ -199,6 +491,22 @@ where
         self.inner.count(i)
     }
 
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.inner.after_notify(count, i);
+    }
+
     fn next_tag(&mut self, _: Internal) -> Self::Tag {
         self.tag.clone()
     }
@@ -231,18 +539,176 @@ impl<N: fmt::Debug, F> fmt::Debug for TagWith<N, F> {
 }
 
 #[cfg(feature = "std")]
-impl<N, F> TagWith<N, F> {
-    /// Create a new `TagFn` with the given tag function and notification.
-    fn new(tag: F, inner: N) -> Self {
-        Self { tag, inner }
+impl<N, F> TagWith<N, F> {
+    /// Create a new `TagFn` with the given tag function and notification.
+    fn new(tag: F, inner: N) -> Self {
+        Self { tag, inner }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F, T> NotificationPrivate for TagWith<N, F>
+where
+    N: Notification + ?Sized,
+    F: FnMut() -> T,
+{
+    type Tag = T;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.inner.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.inner.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.inner.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, _: Internal) -> Self::Tag {
+        (self.tag)()
+    }
+}
+
+/// Use a function of the listener's position to generate a tag to notify listeners.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub struct TagWithIndex<N: ?Sized, F> {
+    tag: F,
+    index: usize,
+    inner: N,
+}
+
+#[cfg(feature = "std")]
+impl<N: fmt::Debug, F> fmt::Debug for TagWithIndex<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Ellipses;
+
+        impl fmt::Debug for Ellipses {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("..")
+            }
+        }
+
+        f.debug_struct("TagWithIndex")
+            .field("tag", &Ellipses)
+            .field("index", &self.index)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F> TagWithIndex<N, F> {
+    /// Create a new `TagWithIndex` with the given tag function and notification.
+    fn new(tag: F, inner: N) -> Self {
+        Self {
+            tag,
+            index: 0,
+            inner,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F, T> NotificationPrivate for TagWithIndex<N, F>
+where
+    N: Notification + ?Sized,
+    F: FnMut(usize) -> T,
+{
+    type Tag = T;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.inner.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.inner.count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.inner.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, _: Internal) -> Self::Tag {
+        let index = self.index;
+        self.index += 1;
+        (self.tag)(index)
+    }
+}
+
+/// Use a function to convert the tags of a notification.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub struct MapTag<N: ?Sized, F> {
+    map: F,
+    inner: N,
+}
+
+#[cfg(feature = "std")]
+impl<N: fmt::Debug, F> fmt::Debug for MapTag<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Ellipses;
+
+        impl fmt::Debug for Ellipses {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("..")
+            }
+        }
+
+        f.debug_struct("MapTag")
+            .field("map", &Ellipses)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F> MapTag<N, F> {
+    /// Create a new `MapTag` with the given conversion function and notification.
+    fn new(map: F, inner: N) -> Self {
+        Self { map, inner }
     }
 }
 
 #[cfg(feature = "std")]
-impl<N, F, T> NotificationPrivate for TagWith<N, F>
+impl<N, F, T> NotificationPrivate for MapTag<N, F>
 where
-    N: Notification + ?Sized,
-    F: FnMut() -> T,
+    N: Notification,
+    F: FnMut(N::Tag) -> T,
 {
     type Tag = T;
 
@@ -258,8 +724,24 @@ where
         self.inner.count(i)
     }
 
-    fn next_tag(&mut self, _: Internal) -> Self::Tag {
-        (self.tag)()
+    fn is_sticky(&self, i: Internal) -> bool {
+        self.inner.is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        self.inner.is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        self.inner.before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        self.inner.after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        (self.map)(self.inner.next_tag(i))
     }
 }
 
@@ -272,6 +754,12 @@ pub(crate) struct GenericNotify<F> {
     /// Whether this notification is additional.
     additional: bool,
 
+    /// Whether this notification is sticky.
+    sticky: bool,
+
+    /// Whether this notification is coalesced.
+    coalesced: bool,
+
     /// Generate tags.
     tags: F,
 }
@@ -281,9 +769,24 @@ impl<T, F: TagProducer<Tag = T>> GenericNotify<F> {
         Self {
             count,
             additional,
+            sticky: false,
+            coalesced: false,
             tags,
         }
     }
+
+    /// Set whether this notification is sticky.
+    pub(crate) fn set_sticky(mut self, sticky: bool) -> Self {
+        self.sticky = sticky;
+        self
+    }
+
+    /// Set whether this notification is coalesced.
+    #[allow(dead_code)] // only the no_std backup queue needs this
+    pub(crate) fn set_coalesced(mut self, coalesced: bool) -> Self {
+        self.coalesced = coalesced;
+        self
+    }
 }
 
 impl<T, F: TagProducer<Tag = T>> NotificationPrivate for GenericNotify<F> {
@@ -301,6 +804,18 @@ impl<T, F: TagProducer<Tag = T>> NotificationPrivate for GenericNotify<F> {
         self.count
     }
 
+    fn is_sticky(&self, _: Internal) -> bool {
+        self.sticky
+    }
+
+    fn is_coalesced(&self, _: Internal) -> bool {
+        self.coalesced
+    }
+
+    fn before_notify(&mut self, _: Internal) {}
+
+    fn after_notify(&mut self, _: usize, _: Internal) {}
+
     fn next_tag(&mut self, _: Internal) -> Self::Tag {
         self.tags.next_tag()
     }
@@ -322,6 +837,34 @@ impl<T, F: FnMut() -> T> TagProducer for F {
     }
 }
 
+/// A producer for notifications that can only carry zero-sized tags, such as `()`.
+pub(crate) struct NothingProducer<T>(PhantomData<T>);
+
+impl<T> Default for NothingProducer<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> fmt::Debug for NothingProducer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NothingProducer").finish()
+    }
+}
+
+impl<T> TagProducer for NothingProducer<T> {
+    type Tag = T;
+
+    fn next_tag(&mut self) -> Self::Tag {
+        // This has to be a zero-sized type with no drop handler.
+        assert_eq!(mem::size_of::<Self::Tag>(), 0);
+        assert!(!mem::needs_drop::<Self::Tag>());
+
+        // SAFETY: As this is a ZST without a drop handler, zero is valid.
+        unsafe { mem::zeroed() }
+    }
+}
+
 /// A value that can be converted into a [`Notification`].
 ///
 /// This trait adds onto the [`Notification`] trait by providing combinators that can be applied to all
@@ -353,7 +896,8 @@ impl<T, F: FnMut() -> T> TagProducer for F {
 /// ```
 ///
 /// This trait is implemented for all types that implement [`Notification`], as well as for non-floating-point
-/// numeric literals (`usize`, `i32`, etc).
+/// numeric literals (`usize`, `i32`, etc) and unsigned non-zero integers (`NonZeroUsize`, etc).
+/// A `bool` notifies one listener if it is `true`, and none otherwise.
 ///
 /// This function can be thought of as being analogous to [`std::iter::IntoIterator`], but for [`Notification`].
 pub trait IntoNotification: __private::Sealed {
@@ -468,6 +1012,149 @@ pub trait IntoNotification: __private::Sealed {
         Relaxed::new(self.into_notification())
     }
 
+    /// Emit a fence with the given ordering for this notification, instead of a `SeqCst` fence.
+    ///
+    /// This sits between the default `SeqCst` fence and [`relaxed()`], which emits no fence at
+    /// all. For instance, a `Release` fence is enough when the listeners only need to see the
+    /// writes made before the notification, and they don't check anything that is written after
+    /// they start listening. `Ordering::Relaxed` behaves like [`relaxed()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// event.notify(1.fence_with(Ordering::Release));
+    /// listener.wait();
+    /// ```
+    ///
+    /// [`relaxed()`]: IntoNotification::relaxed
+    fn fence_with(self, ordering: Ordering) -> FenceWith<Self::Notify>
+    where
+        Self: Sized,
+    {
+        FenceWith::new(ordering, self.into_notification())
+    }
+
+    /// Run callbacks right before and right after the listeners are notified.
+    ///
+    /// `before` is called once the fence has been emitted, and `after` is called with the number
+    /// of listeners that were notified, which is the number that the notifying method returns.
+    /// This makes it possible to add metrics or logging to a single notification site. Every
+    /// event type that accepts notifications runs the callbacks.
+    ///
+    /// Since `after` sees the returned count, it sees `0` for a notification that is not
+    /// delivered right away: one held back by [`Event::pause()`], or one that finds the event
+    /// busy when the `std` feature is disabled. Such notifications are delivered later without
+    /// running the callbacks again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    /// use std::cell::Cell;
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// let woken = Cell::new(0);
+    /// event.notify(5.inspect(|| (), |count| woken.set(count)));
+    /// assert_eq!(woken.get(), 2);
+    /// ```
+    ///
+    /// [`Event::pause()`]: crate::Event::pause
+    fn inspect<B, A>(self, before: B, after: A) -> Inspect<Self::Notify, B, A>
+    where
+        Self: Sized,
+        B: FnOnce(),
+        A: FnOnce(usize),
+    {
+        Inspect::new(before, after, self.into_notification())
+    }
+
+    /// Store a permit for the next listener if there is nobody to notify.
+    ///
+    /// Usually, notifications are lost if there are no listeners waiting for them. A sticky
+    /// notification instead stores a single permit in the [`Event`] when there are no
+    /// unnotified listeners. The next listener created by [`Event::listen()`] takes the permit
+    /// and is notified immediately.
+    ///
+    /// At most one permit is stored, no matter how many sticky notifications are sent or what
+    /// their count is. For tagged events, the permit holds the tag of the first such
+    /// notification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // Nobody is listening, so a permit is stored.
+    /// event.notify(1.sticky());
+    /// event.notify(1.sticky());
+    ///
+    /// // The first listener takes the permit.
+    /// let listener1 = event.listen();
+    /// assert!(event.is_notified());
+    /// listener1.wait();
+    ///
+    /// // There was only one permit.
+    /// let listener2 = event.listen();
+    /// assert!(!event.is_notified());
+    /// ```
+    ///
+    /// [`Event`]: crate::Event
+    /// [`Event::listen()`]: crate::Event::listen
+    fn sticky(self) -> Sticky<Self::Notify>
+    where
+        Self: Sized,
+    {
+        Sticky::new(self.into_notification())
+    }
+
+    /// Drop this notification if a listener is already notified.
+    ///
+    /// A notified listener that hasn't observed its notification yet is on its way to handle
+    /// whatever the notification was about. When producers signal far more often than consumers
+    /// get around to polling, further notifications, and [`additional`] ones in particular, would
+    /// only wake up more consumers that find nothing left to do. A coalesced notification is
+    /// merged into the pending one instead, so it only wakes up listeners if none are notified.
+    ///
+    /// Permits stored for future listeners count as notified listeners.
+    ///
+    /// [`additional`]: IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify(1.additional().coalesced()), 1);
+    ///
+    /// // `listener1` hasn't observed its notification yet, so this is merged into it.
+    /// assert_eq!(event.notify(1.additional().coalesced()), 0);
+    ///
+    /// listener1.wait();
+    /// assert_eq!(event.notify(1.additional().coalesced()), 1);
+    /// ```
+    fn coalesced(self) -> Coalesced<Self::Notify>
+    where
+        Self: Sized,
+    {
+        Coalesced::new(self.into_notification())
+    }
+
     /// Use a tag with this notification.
     ///
     /// In many cases, it is desired to send additional information to the listener of the [`Event`]. For instance,
@@ -539,6 +1226,185 @@ pub trait IntoNotification: __private::Sealed {
     {
         TagWith::new(tag, self.into_notification())
     }
+
+    /// Use a function of the listener's position to generate a tag with this notification.
+    ///
+    /// This works like [`IntoNotification::tag_with()`], but the function receives the position
+    /// of the listener within this notification: `0` for the first listener that is notified, `1`
+    /// for the second, and so on. This makes it easy to give the first listener something
+    /// different from the rest, such as handing it a resource and telling the others to retry.
+    ///
+    /// Tagging functions cannot be implemented efficiently for `no_std`, so this is only available
+    /// when the `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Listener, Event};
+    ///
+    /// let event = Event::<bool>::with_tag();
+    ///
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// // Only the first listener gets the resource.
+    /// event.notify(2.tag_with_index(|index| index == 0));
+    ///
+    /// assert_eq!(listener1.wait(), true);
+    /// assert_eq!(listener2.wait(), false);
+    /// ```
+    #[cfg(feature = "std")]
+    fn tag_with_index<T, F>(self, tag: F) -> TagWithIndex<Self::Notify, F>
+    where
+        Self: Sized + IntoNotification<Tag = ()>,
+        F: FnMut(usize) -> T,
+    {
+        TagWithIndex::new(tag, self.into_notification())
+    }
+
+    /// Use a function to convert the tags of this notification.
+    ///
+    /// Every tag produced by the notification is passed through `map` before it is handed to a
+    /// listener. This makes it possible to notify an [`Event`] with tags of a different type than
+    /// the one at hand, converting them only for the listeners that are actually notified.
+    ///
+    /// Tagging functions cannot be implemented efficiently for `no_std`, so this is only available
+    /// when the `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Listener, Event};
+    ///
+    /// let event = Event::<String>::with_tag();
+    ///
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// // Notify with a number, converted to a string for every listener.
+    /// event.notify(2.tag(7).map_tag(|n: i32| n.to_string()));
+    ///
+    /// assert_eq!(listener1.wait(), "7");
+    /// assert_eq!(listener2.wait(), "7");
+    /// ```
+    ///
+    /// [`Event`]: crate::Event
+    #[cfg(feature = "std")]
+    fn map_tag<T, F>(self, map: F) -> MapTag<Self::Notify, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Tag) -> T,
+    {
+        MapTag::new(map, self.into_notification())
+    }
+}
+
+/// A type-erased notification.
+///
+/// Notifications are usually built from combinators, and their concrete types are long and
+/// unnameable. Boxing them as trait objects makes it possible to pick a notification strategy at
+/// runtime, or to pass notifications through APIs that can't be generic, at the cost of an
+/// allocation and dynamic dispatch. Mutable references to notifications, and
+/// `Box<dyn Notification<Tag = T> + Send>`, can be used as notifications as well.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{DynNotification, Event, IntoNotification};
+///
+/// fn strategy(all: bool) -> DynNotification<'static> {
+///     if all {
+///         Box::new(usize::MAX.into_notification())
+///     } else {
+///         Box::new(1.additional().relaxed())
+///     }
+/// }
+///
+/// let event = Event::new();
+/// let listener1 = event.listen();
+/// let listener2 = event.listen();
+///
+/// assert_eq!(event.notify(strategy(false)), 1);
+/// assert_eq!(event.notify(strategy(true)), 1);
+/// ```
+pub type DynNotification<'a, T = ()> = alloc::boxed::Box<dyn Notification<Tag = T> + 'a>;
+
+impl<N> NotificationPrivate for alloc::boxed::Box<N>
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        (**self).is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        (**self).fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        (**self).count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        (**self).is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        (**self).is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        (**self).before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        (**self).after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        (**self).next_tag(i)
+    }
+}
+
+impl<N> NotificationPrivate for &mut N
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        (**self).is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        (**self).fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        (**self).count(i)
+    }
+
+    fn is_sticky(&self, i: Internal) -> bool {
+        (**self).is_sticky(i)
+    }
+
+    fn is_coalesced(&self, i: Internal) -> bool {
+        (**self).is_coalesced(i)
+    }
+
+    fn before_notify(&mut self, i: Internal) {
+        (**self).before_notify(i);
+    }
+
+    fn after_notify(&mut self, count: usize, i: Internal) {
+        (**self).after_notify(count, i);
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        (**self).next_tag(i)
+    }
 }
 
 impl<N: Notification> IntoNotification for N {
@@ -572,6 +1438,39 @@ macro_rules! impl_for_numeric_types {
 
 impl_for_numeric_types! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
 
+macro_rules! impl_for_non_zero_types {
+    ($($ty:ty)*) => {$(
+        impl IntoNotification for $ty {
+            type Tag = ();
+            type Notify = Notify;
+
+            #[inline]
+            fn into_notification(self) -> Self::Notify {
+                self.get().into_notification()
+            }
+        }
+
+        impl __private::Sealed for $ty {}
+    )*};
+}
+
+impl_for_non_zero_types! {
+    num::NonZeroUsize num::NonZeroU8 num::NonZeroU16 num::NonZeroU32 num::NonZeroU64
+    num::NonZeroU128
+}
+
+impl IntoNotification for bool {
+    type Tag = ();
+    type Notify = Notify;
+
+    #[inline]
+    fn into_notification(self) -> Self::Notify {
+        Notify::new(self as usize)
+    }
+}
+
+impl __private::Sealed for bool {}
+
 /// Equivalent to `atomic::fence(Ordering::SeqCst)`, but in some cases faster.
 #[inline]
 pub(super) fn full_fence() {
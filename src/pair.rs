@@ -0,0 +1,261 @@
+//! Split capability handles for an [`Event`].
+
+use crate::{CloseableListener, Event, EventListener, IntoNotification};
+
+use core::fmt;
+
+/// A handle that can only notify an [`Event`].
+///
+/// This is created by [`Event::pair()`], together with a [`Waiter`] for the same event.
+pub struct Notifier<T = ()> {
+    event: Event<T>,
+}
+
+impl<T> fmt::Debug for Notifier<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Notifier").field(&self.event).finish()
+    }
+}
+
+impl<T> Clone for Notifier<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+        }
+    }
+}
+
+impl<T> Notifier<T> {
+    /// Notifies a number of active listeners.
+    ///
+    /// See [`Event::notify()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen();
+    /// assert_eq!(notifier.notify(1), 1);
+    /// ```
+    #[inline]
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        self.event.notify(notify)
+    }
+
+    /// Notifies a number of active listeners and returns how many of them actually received the
+    /// notification.
+    ///
+    /// See [`Event::notify_count()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    /// assert_eq!(notifier.notify_count(1), 0);
+    /// ```
+    #[inline]
+    pub fn notify_count(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        self.event.notify_count(notify)
+    }
+
+    /// Closes the event.
+    ///
+    /// See [`Event::close()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// assert!(notifier.close());
+    /// assert!(waiter.is_closed());
+    /// ```
+    #[inline]
+    pub fn close(&self) -> bool
+    where
+        T: Default,
+    {
+        self.event.close()
+    }
+
+    /// Closes the event, completing plain listeners with tags made by `tag`.
+    ///
+    /// See [`Event::close_with()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::<u32>::with_tag().pair();
+    ///
+    /// assert!(notifier.close_with(|| 7));
+    /// assert!(waiter.is_closed());
+    /// ```
+    #[inline]
+    pub fn close_with(&self, tag: fn() -> T) -> bool {
+        self.event.close_with(tag)
+    }
+
+    /// Returns the number of listeners currently registered to the event.
+    ///
+    /// See [`Event::listener_count()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen();
+    /// assert_eq!(notifier.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.event.listener_count()
+    }
+}
+
+/// A handle that can only listen to an [`Event`].
+///
+/// This is created by [`Event::pair()`], together with a [`Notifier`] for the same event.
+pub struct Waiter<T = ()> {
+    event: Event<T>,
+}
+
+impl<T> fmt::Debug for Waiter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Waiter").field(&self.event).finish()
+    }
+}
+
+impl<T> Clone for Waiter<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+        }
+    }
+}
+
+impl<T> Waiter<T> {
+    /// Returns a guard listening for a notification.
+    ///
+    /// See [`Event::listen()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen();
+    /// notifier.notify(1);
+    /// listener.await;
+    /// # });
+    /// ```
+    #[inline]
+    pub fn listen(&self) -> EventListener<T> {
+        self.event.listen()
+    }
+
+    /// Returns a guard listening for a notification or for the event to be closed.
+    ///
+    /// See [`Event::listen_closeable()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Closed, Event};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen_closeable();
+    /// notifier.close();
+    /// assert_eq!(listener.await, Err(Closed));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn listen_closeable(&self) -> CloseableListener<T> {
+        self.event.listen_closeable()
+    }
+
+    /// Tell whether any listeners are currently notified.
+    ///
+    /// See [`Event::is_notified()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen();
+    /// notifier.notify(1);
+    /// assert!(waiter.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.event.is_notified()
+    }
+
+    /// Tells whether the event has been closed.
+    ///
+    /// See [`Event::is_closed()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    /// assert!(!waiter.is_closed());
+    /// ```
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.event.is_closed()
+    }
+}
+
+impl<T> Event<T> {
+    /// Splits this event into a [`Notifier`] that can only notify it and a [`Waiter`] that can
+    /// only listen to it.
+    ///
+    /// Both handles are cheap to clone and share the same list of listeners. This makes it
+    /// possible to enforce at the type level who is allowed to notify and who is allowed to wait.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let (notifier, waiter) = Event::new().pair();
+    ///
+    /// let listener = waiter.listen();
+    /// notifier.notify(1);
+    /// listener.await;
+    /// # });
+    /// ```
+    pub fn pair(self) -> (Notifier<T>, Waiter<T>) {
+        (
+            Notifier {
+                event: self.clone(),
+            },
+            Waiter { event: self },
+        )
+    }
+}
@@ -0,0 +1,174 @@
+//! Holding notifications back while an event is paused.
+
+use crate::notify::{GenericNotify, NothingProducer};
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::{Event, Inner};
+
+/// The notifications held back by [`Event::pause()`].
+pub(crate) struct Pause {
+    /// Whether the event is paused.
+    paused: AtomicBool,
+
+    /// The largest held back notification.
+    held: AtomicUsize,
+
+    /// The sum of the held back additional notifications.
+    held_additional: AtomicUsize,
+}
+
+impl Pause {
+    /// Creates the state of an event that is not paused.
+    pub(crate) fn new() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            held: AtomicUsize::new(0),
+            held_additional: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T> Inner<T> {
+    /// Holds a notification back if the event is paused, and returns whether it was.
+    #[inline]
+    pub(crate) fn hold(&self, count: usize, additional: bool) -> bool {
+        if !self.pause.paused.load(Ordering::Acquire) {
+            return false;
+        }
+
+        if additional {
+            let _ = self.pause.held_additional.fetch_update(
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+                |held| Some(held.saturating_add(count)),
+            );
+        } else {
+            self.pause.held.fetch_max(count, Ordering::SeqCst);
+        }
+
+        // The event may have been resumed before we were done, in which case `resume()` may have
+        // missed the notification.
+        if !self.pause.paused.load(Ordering::SeqCst) {
+            self.release();
+        }
+
+        true
+    }
+
+    /// Delivers the held back notifications and returns the number of notified listeners.
+    ///
+    /// Only untagged events can be paused, so the tags can be produced out of thin air.
+    fn release(&self) -> usize {
+        let mut count = 0;
+
+        for &(held, additional) in &[
+            (&self.pause.held, false),
+            (&self.pause.held_additional, true),
+        ] {
+            let n = held.swap(0, Ordering::SeqCst);
+            if n == 0 {
+                continue;
+            }
+
+            self.advance_epoch();
+            count += self.notify(GenericNotify::new(
+                n,
+                additional,
+                NothingProducer::default(),
+            ));
+            self.notify_parent();
+            #[cfg(feature = "std")]
+            self.forwards.notify(n, additional);
+        }
+
+        count
+    }
+}
+
+impl Event {
+    /// Holds notifications back until [`Event::resume()`] is called.
+    ///
+    /// While the event is paused, notifications sent with [`Event::notify()`],
+    /// [`Event::notify_count()`], `Event::notify_last()` and `Event::notify_if()`, or any of the
+    /// shorthands built on them, are counted instead of delivered. This supports updating state in
+    /// several steps, without waking up listeners that would observe it half-way.
+    ///
+    /// Held back notifications are merged: [`Event::resume()`] delivers one notification for the
+    /// largest count that was held back, and one [`additional`] notification for the sum of the
+    /// held back additional ones. These are delivered like [`Event::notify()`] would, so the order
+    /// and metadata filters of `notify_last()` and `notify_if()` are not kept. Other notification
+    /// methods are not affected by pausing.
+    ///
+    /// [`additional`]: crate::IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// event.pause();
+    /// assert_eq!(event.notify(1), 0);
+    /// assert!(!event.is_notified());
+    ///
+    /// assert_eq!(event.resume(), 1);
+    /// listener.wait();
+    /// ```
+    pub fn pause(&self) {
+        let inner = unsafe { &*self.inner() };
+        inner.pause.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Delivers the notifications held back since [`Event::pause()`] was called.
+    ///
+    /// Returns the number of listeners that were notified. Does nothing if the event is not
+    /// paused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// event.pause();
+    /// event.notify(1);
+    /// event.notify(1.additional());
+    /// event.notify(1.additional());
+    ///
+    /// // One listener for the regular notification, two for the additional ones.
+    /// assert_eq!(event.resume(), 3);
+    /// ```
+    pub fn resume(&self) -> usize {
+        match self.try_inner() {
+            Some(inner) if inner.pause.paused.swap(false, Ordering::SeqCst) => inner.release(),
+            _ => 0,
+        }
+    }
+
+    /// Returns `true` if the event is paused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert!(!event.is_paused());
+    ///
+    /// event.pause();
+    /// assert!(event.is_paused());
+    ///
+    /// event.resume();
+    /// assert!(!event.is_paused());
+    /// ```
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.try_inner()
+            .map_or(false, |inner| inner.pause.paused.load(Ordering::Acquire))
+    }
+}
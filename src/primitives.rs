@@ -0,0 +1,30 @@
+//! Synchronization primitives built on top of [`Event`].
+//!
+//! These types wrap the check-listen-check loop that most users of this crate end up writing by
+//! hand. They use the same listener list as [`Event`], so they are available on `no_std` as well;
+//! only the blocking methods require the `std` feature. [`watch`] needs a lock around the stored
+//! value, so it is only available with the `std` feature.
+//!
+//! [`Event`]: crate::Event
+
+mod atomic_waiter;
+mod condvar;
+mod event_flag;
+mod event_group;
+mod mutex;
+mod once_event;
+mod rwlock;
+mod semaphore;
+
+pub mod oneshot;
+#[cfg(feature = "std")]
+pub mod watch;
+
+pub use atomic_waiter::AtomicWaiter;
+pub use condvar::Condvar;
+pub use event_flag::EventFlag;
+pub use event_group::EventGroup;
+pub use mutex::{Mutex, MutexGuard};
+pub use once_event::OnceEvent;
+pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use semaphore::{Semaphore, SemaphorePermit};
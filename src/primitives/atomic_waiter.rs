@@ -0,0 +1,318 @@
+//! An atomic integer that can be waited on.
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::{listener, Event};
+
+use core::fmt;
+
+/// An atomic integer that tasks and threads can wait on until it takes a value they want.
+///
+/// Most users of [`Event`] pair it with an atomic value, and write the same loop over and over:
+/// check the value, start listening, check again, and wait. An `AtomicWaiter` does this once and
+/// for all. Its `*_and_notify` methods change the value and wake every waiter, which then checks
+/// whether the new value is what it was waiting for, much like a futex.
+///
+/// All operations use [`Ordering::SeqCst`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use event_listener::primitives::AtomicWaiter;
+///
+/// let pending = Arc::new(AtomicWaiter::new(3));
+///
+/// for _ in 0..3 {
+///     let pending = pending.clone();
+///     thread::spawn(move || {
+///         pending.fetch_sub_and_notify(1);
+///     });
+/// }
+///
+/// // Wait for the workers to finish.
+/// assert_eq!(pending.wait_while_blocking(|n| n > 0), 0);
+/// ```
+pub struct AtomicWaiter {
+    /// The value.
+    value: AtomicUsize,
+
+    /// Tasks waiting for the value to change.
+    event: Event,
+}
+
+impl fmt::Debug for AtomicWaiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicWaiter")
+            .field("value", &self.load())
+            .finish()
+    }
+}
+
+impl Default for AtomicWaiter {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl AtomicWaiter {
+    /// Creates a new `AtomicWaiter` holding `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(5);
+    /// assert_eq!(waiter.load(), 5);
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new(value: usize) -> Self {
+        Self {
+            value: AtomicUsize::new(value),
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new `AtomicWaiter` holding `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(5);
+    /// assert_eq!(waiter.load(), 5);
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new(value: usize) -> Self {
+        Self {
+            value: AtomicUsize::new(value),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the current value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(1);
+    /// assert_eq!(waiter.load(), 1);
+    /// ```
+    #[inline]
+    pub fn load(&self) -> usize {
+        self.value.load(Ordering::SeqCst)
+    }
+
+    /// Stores a value and wakes all waiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(0);
+    /// waiter.store_and_notify(7);
+    /// assert_eq!(waiter.load(), 7);
+    /// ```
+    pub fn store_and_notify(&self, value: usize) {
+        self.value.store(value, Ordering::SeqCst);
+        self.notify();
+    }
+
+    /// Stores a value, wakes all waiters, and returns the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(1);
+    /// assert_eq!(waiter.swap_and_notify(2), 1);
+    /// ```
+    pub fn swap_and_notify(&self, value: usize) -> usize {
+        let old = self.value.swap(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Adds to the value, wrapping around on overflow, wakes all waiters, and returns the
+    /// previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(1);
+    /// assert_eq!(waiter.fetch_add_and_notify(2), 1);
+    /// assert_eq!(waiter.load(), 3);
+    /// ```
+    pub fn fetch_add_and_notify(&self, value: usize) -> usize {
+        let old = self.value.fetch_add(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Subtracts from the value, wrapping around on overflow, wakes all waiters, and returns the
+    /// previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(3);
+    /// assert_eq!(waiter.fetch_sub_and_notify(2), 3);
+    /// assert_eq!(waiter.load(), 1);
+    /// ```
+    pub fn fetch_sub_and_notify(&self, value: usize) -> usize {
+        let old = self.value.fetch_sub(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Applies a bitwise "and" to the value, wakes all waiters, and returns the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(0b110);
+    /// assert_eq!(waiter.fetch_and_and_notify(0b011), 0b110);
+    /// assert_eq!(waiter.load(), 0b010);
+    /// ```
+    pub fn fetch_and_and_notify(&self, value: usize) -> usize {
+        let old = self.value.fetch_and(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Applies a bitwise "or" to the value, wakes all waiters, and returns the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(0b100);
+    /// assert_eq!(waiter.fetch_or_and_notify(0b001), 0b100);
+    /// assert_eq!(waiter.load(), 0b101);
+    /// ```
+    pub fn fetch_or_and_notify(&self, value: usize) -> usize {
+        let old = self.value.fetch_or(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Applies a bitwise "xor" to the value, wakes all waiters, and returns the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let waiter = AtomicWaiter::new(0b110);
+    /// assert_eq!(waiter.fetch_xor_and_notify(0b011), 0b110);
+    /// assert_eq!(waiter.load(), 0b101);
+    /// ```
+    pub fn fetch_xor_and_notify(&self, value: usize) -> usize {
+        let old = self.value.fetch_xor(value, Ordering::SeqCst);
+        self.notify();
+        old
+    }
+
+    /// Waits asynchronously while `pred` returns `true` for the value.
+    ///
+    /// Returns the first value for which `pred` returned `false`. The predicate is called again
+    /// whenever the value is changed with one of the `*_and_notify` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::AtomicWaiter;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let waiter = AtomicWaiter::new(0);
+    /// waiter.store_and_notify(3);
+    ///
+    /// assert_eq!(waiter.wait_while(|v| v == 0).await, 3);
+    /// # });
+    /// ```
+    pub async fn wait_while(&self, mut pred: impl FnMut(usize) -> bool) -> usize {
+        loop {
+            let value = self.load();
+            if !pred(value) {
+                return value;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the value again after creating the listener.
+            let value = self.load();
+            if !pred(value) {
+                return value;
+            }
+
+            // Wait until the value changes.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread while `pred` returns `true` for the value.
+    ///
+    /// This is the blocking version of [`AtomicWaiter::wait_while()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::primitives::AtomicWaiter;
+    ///
+    /// let state = Arc::new(AtomicWaiter::new(0));
+    ///
+    /// thread::spawn({
+    ///     let state = state.clone();
+    ///     move || state.store_and_notify(2)
+    /// });
+    ///
+    /// assert_eq!(state.wait_while_blocking(|v| v < 2), 2);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_while_blocking(&self, mut pred: impl FnMut(usize) -> bool) -> usize {
+        use crate::Listener;
+
+        loop {
+            let value = self.load();
+            if !pred(value) {
+                return value;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the value again after creating the listener.
+            let value = self.load();
+            if !pred(value) {
+                return value;
+            }
+
+            // Wait until the value changes.
+            listener.wait();
+        }
+    }
+
+    /// Wakes all waiters so that they check the value again.
+    fn notify(&self) {
+        self.event.notify(usize::MAX);
+    }
+}
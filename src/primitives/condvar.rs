@@ -0,0 +1,284 @@
+//! A condition variable that works with any mutex.
+
+use crate::{listener, Event, IntoNotification};
+
+use core::fmt;
+use core::future::Future;
+
+/// A condition variable for use with any mutex, synchronous or asynchronous.
+///
+/// This works like [`std::sync::Condvar`], but isn't tied to a particular mutex. Its wait
+/// methods take the guard of the caller's mutex, and a closure that locks the mutex again. The
+/// listener is registered before the guard is dropped, so a notification sent by whoever locks
+/// the mutex next can't be missed.
+///
+/// Like with any condition variable, a waiter may be woken up while the condition it waits for
+/// doesn't hold, so it should be checked again in a loop, or with [`Condvar::wait_while()`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use std::thread;
+/// use event_listener::primitives::Condvar;
+///
+/// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+///
+/// thread::spawn({
+///     let pair = pair.clone();
+///     move || {
+///         let (lock, cvar) = &*pair;
+///         *lock.lock().unwrap() = true;
+///         cvar.notify_one();
+///     }
+/// });
+///
+/// let (lock, cvar) = &*pair;
+/// let started = lock.lock().unwrap();
+/// let started = cvar.wait_while_blocking(started, || lock.lock().unwrap(), |started| !**started);
+/// assert!(*started);
+/// ```
+pub struct Condvar {
+    /// Tasks waiting for a notification.
+    event: Event,
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Condvar").finish_non_exhaustive()
+    }
+}
+
+impl Default for Condvar {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            event: Event::new(),
+        }
+    }
+
+    /// Wakes up one waiter.
+    ///
+    /// Every call wakes up another waiter, if there is one. Returns the number of waiters that
+    /// were woken up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    ///
+    /// // Nobody is waiting.
+    /// assert_eq!(cvar.notify_one(), 0);
+    /// ```
+    #[inline]
+    pub fn notify_one(&self) -> usize {
+        self.event.notify(1.additional())
+    }
+
+    /// Wakes up all waiters.
+    ///
+    /// Returns the number of waiters that were woken up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    ///
+    /// // Nobody is waiting.
+    /// assert_eq!(cvar.notify_all(), 0);
+    /// ```
+    #[inline]
+    pub fn notify_all(&self) -> usize {
+        self.event.notify(usize::MAX)
+    }
+
+    /// Releases the guard, waits asynchronously for a notification, and locks the mutex again.
+    ///
+    /// `lock` is called to lock the mutex again once a notification is received. The returned
+    /// guard comes from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use event_listener::primitives::Condvar;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let lock = Mutex::new(0);
+    /// let cvar = Condvar::new();
+    ///
+    /// let guard = lock.lock().unwrap();
+    /// let wait = cvar.wait(guard, || async { lock.lock().unwrap() });
+    /// futures_lite::pin!(wait);
+    ///
+    /// // The guard is released while waiting.
+    /// assert!(futures_lite::future::poll_once(&mut wait).await.is_none());
+    /// *lock.lock().unwrap() = 1;
+    /// cvar.notify_one();
+    ///
+    /// assert_eq!(*wait.await, 1);
+    /// # });
+    /// ```
+    pub async fn wait<G, F, Fut>(&self, guard: G, lock: F) -> G
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = G>,
+    {
+        // Start listening before the guard is released, so no notification is missed.
+        listener!(self.event => listener);
+        drop(guard);
+
+        listener.await;
+        lock().await
+    }
+
+    /// Releases the guard, blocks until a notification, and locks the mutex again.
+    ///
+    /// This is the blocking version of [`Condvar::wait()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread;
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let pair = Arc::new((Mutex::new(0), Condvar::new()));
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let mut guard = lock.lock().unwrap();
+    ///
+    /// thread::spawn({
+    ///     let pair = pair.clone();
+    ///     move || {
+    ///         let (lock, cvar) = &*pair;
+    ///         *lock.lock().unwrap() = 1;
+    ///         cvar.notify_all();
+    ///     }
+    /// });
+    ///
+    /// while *guard == 0 {
+    ///     guard = cvar.wait_blocking(guard, || lock.lock().unwrap());
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking<G>(&self, guard: G, lock: impl FnOnce() -> G) -> G {
+        use crate::Listener;
+
+        // Start listening before the guard is released, so no notification is missed.
+        listener!(self.event => listener);
+        drop(guard);
+
+        listener.wait();
+        lock()
+    }
+
+    /// Waits asynchronously while `condition` returns `true`.
+    ///
+    /// `condition` is called with the guard locked, first right away and then every time the
+    /// waiter is woken up. In between, the guard is released, and `lock` is called to lock the
+    /// mutex again. Returns the guard for which `condition` returned `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use event_listener::primitives::Condvar;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let lock = Mutex::new(true);
+    /// let cvar = Condvar::new();
+    ///
+    /// let guard = lock.lock().unwrap();
+    /// let guard = cvar
+    ///     .wait_while(guard, || async { lock.lock().unwrap() }, |ready| !**ready)
+    ///     .await;
+    /// assert!(*guard);
+    /// # });
+    /// ```
+    pub async fn wait_while<G, F, Fut>(
+        &self,
+        mut guard: G,
+        mut lock: F,
+        mut condition: impl FnMut(&mut G) -> bool,
+    ) -> G
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = G>,
+    {
+        while condition(&mut guard) {
+            guard = self.wait(guard, &mut lock).await;
+        }
+
+        guard
+    }
+
+    /// Blocks while `condition` returns `true`.
+    ///
+    /// This is the blocking version of [`Condvar::wait_while()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use event_listener::primitives::Condvar;
+    ///
+    /// let lock = Mutex::new(3);
+    /// let cvar = Condvar::new();
+    ///
+    /// let guard = lock.lock().unwrap();
+    /// let guard = cvar.wait_while_blocking(guard, || lock.lock().unwrap(), |n| **n == 0);
+    /// assert_eq!(*guard, 3);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_while_blocking<G>(
+        &self,
+        mut guard: G,
+        mut lock: impl FnMut() -> G,
+        mut condition: impl FnMut(&mut G) -> bool,
+    ) -> G {
+        while condition(&mut guard) {
+            guard = self.wait_blocking(guard, &mut lock);
+        }
+
+        guard
+    }
+}
@@ -0,0 +1,263 @@
+//! A manual-reset event flag.
+
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::{listener, Event};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use {crate::Listener, std::time::Duration, std::time::Instant};
+
+use core::fmt;
+
+/// A flag that can be raised and lowered, waking waiters when it is raised.
+///
+/// This has the semantics of a manual-reset event on Windows. [`EventFlag::set()`] raises the
+/// flag and wakes all current waiters; while the flag is raised, waits complete immediately.
+/// [`EventFlag::reset()`] lowers the flag again.
+///
+/// Unlike a bare [`Event`], a `set()` call is never lost when nobody is waiting yet. Waiters that
+/// were registered when the flag was raised are woken even if the flag is lowered again before
+/// they get to run.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::primitives::EventFlag;
+///
+/// let flag = EventFlag::new();
+/// assert!(!flag.is_set());
+///
+/// flag.set();
+///
+/// // This returns immediately since the flag is raised.
+/// flag.wait_blocking();
+///
+/// flag.reset();
+/// assert!(!flag.is_set());
+/// ```
+pub struct EventFlag {
+    /// Whether the flag is raised.
+    set: AtomicBool,
+
+    /// Tasks waiting for the flag to be raised.
+    event: Event,
+}
+
+impl fmt::Debug for EventFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventFlag")
+            .field("set", &self.is_set())
+            .finish()
+    }
+}
+
+impl Default for EventFlag {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventFlag {
+    /// Creates a new, lowered `EventFlag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            set: AtomicBool::new(false),
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new, lowered `EventFlag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            set: AtomicBool::new(false),
+            event: Event::new(),
+        }
+    }
+
+    /// Raises the flag and wakes all current waiters.
+    ///
+    /// Returns `true` if the flag was previously lowered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// assert!(flag.set());
+    /// assert!(!flag.set());
+    /// ```
+    pub fn set(&self) -> bool {
+        if self.set.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        self.event.notify(usize::MAX);
+        true
+    }
+
+    /// Lowers the flag.
+    ///
+    /// Returns `true` if the flag was previously raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// flag.set();
+    ///
+    /// assert!(flag.reset());
+    /// assert!(!flag.reset());
+    /// ```
+    #[inline]
+    pub fn reset(&self) -> bool {
+        self.set.swap(false, Ordering::SeqCst)
+    }
+
+    /// Tells whether the flag is raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// assert!(!flag.is_set());
+    ///
+    /// flag.set();
+    /// assert!(flag.is_set());
+    /// ```
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.set.load(Ordering::SeqCst)
+    }
+
+    /// Waits asynchronously until the flag is raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let flag = EventFlag::new();
+    /// flag.set();
+    /// flag.wait().await;
+    /// # });
+    /// ```
+    pub async fn wait(&self) {
+        if self.is_set() {
+            return;
+        }
+
+        // Set up an event listener.
+        listener!(self.event => listener);
+
+        // Check the flag again after creating the listener.
+        if self.is_set() {
+            return;
+        }
+
+        // The only notifications come from `set()`, so we are done once we receive one.
+        listener.await;
+    }
+
+    /// Blocks the current thread until the flag is raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// flag.set();
+    /// flag.wait_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking(&self) {
+        self.wait_internal(None);
+    }
+
+    /// Blocks the current thread until the flag is raised or the timeout is reached.
+    ///
+    /// Returns `true` if the flag was raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// assert!(!flag.wait_timeout(Duration::from_millis(10)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        self.wait_internal(Instant::now().checked_add(timeout))
+    }
+
+    /// Blocks the current thread until the flag is raised or the deadline is reached.
+    ///
+    /// Returns `true` if the flag was raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::primitives::EventFlag;
+    ///
+    /// let flag = EventFlag::new();
+    /// flag.set();
+    /// assert!(flag.wait_deadline(Instant::now() + Duration::from_secs(1)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_deadline(&self, deadline: Instant) -> bool {
+        self.wait_internal(Some(deadline))
+    }
+
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_internal(&self, deadline: Option<Instant>) -> bool {
+        if self.is_set() {
+            return true;
+        }
+
+        // Set up an event listener.
+        listener!(self.event => listener);
+
+        // Check the flag again after creating the listener.
+        if self.is_set() {
+            return true;
+        }
+
+        // The only notifications come from `set()`, so we are done once we receive one.
+        match deadline {
+            None => {
+                listener.wait();
+                true
+            }
+            Some(deadline) => listener.try_wait_deadline(deadline).is_ok() || self.is_set(),
+        }
+    }
+}
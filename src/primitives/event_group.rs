@@ -0,0 +1,305 @@
+//! A group of event bits.
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::{listener, Event};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::Listener;
+
+use core::fmt;
+
+/// A set of event bits that tasks can wait on.
+///
+/// Notifiers raise bits with [`EventGroup::set()`], and waiters wait until either any or all of
+/// a given mask of bits are raised. Waiters can optionally clear the bits they waited for once
+/// their condition is met, which makes it possible to consume a bit in a single step.
+///
+/// This mirrors event groups found in embedded real-time operating systems.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::primitives::EventGroup;
+///
+/// const RX_READY: usize = 1 << 0;
+/// const TX_READY: usize = 1 << 1;
+///
+/// let group = EventGroup::new();
+/// group.set(RX_READY);
+///
+/// // RX_READY is raised, so this returns immediately and clears it.
+/// let bits = group.wait_any_blocking(RX_READY | TX_READY, true);
+/// assert_eq!(bits, RX_READY);
+/// assert_eq!(group.get(), 0);
+/// ```
+pub struct EventGroup {
+    /// The current bits.
+    bits: AtomicUsize,
+
+    /// Tasks waiting for bits to be raised.
+    event: Event,
+}
+
+impl fmt::Debug for EventGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventGroup")
+            .field("bits", &format_args!("{:#b}", self.get()))
+            .finish()
+    }
+}
+
+impl Default for EventGroup {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventGroup {
+    /// Creates a new `EventGroup` with all bits lowered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// assert_eq!(group.get(), 0);
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            bits: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new `EventGroup` with all bits lowered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// assert_eq!(group.get(), 0);
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bits: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the current bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b101);
+    /// assert_eq!(group.get(), 0b101);
+    /// ```
+    #[inline]
+    pub fn get(&self) -> usize {
+        self.bits.load(Ordering::SeqCst)
+    }
+
+    /// Raises the given bits and wakes the waiters.
+    ///
+    /// Returns the bits as they were before this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// assert_eq!(group.set(0b01), 0);
+    /// assert_eq!(group.set(0b10), 0b01);
+    /// ```
+    pub fn set(&self, bits: usize) -> usize {
+        let old = self.bits.fetch_or(bits, Ordering::SeqCst);
+
+        // Only wake waiters if something changed.
+        if old | bits != old {
+            self.event.notify(usize::MAX);
+        }
+
+        old
+    }
+
+    /// Lowers the given bits.
+    ///
+    /// Returns the bits as they were before this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b11);
+    /// assert_eq!(group.clear(0b01), 0b11);
+    /// assert_eq!(group.get(), 0b10);
+    /// ```
+    #[inline]
+    pub fn clear(&self, bits: usize) -> usize {
+        self.bits.fetch_and(!bits, Ordering::SeqCst)
+    }
+
+    /// Waits asynchronously until any of the bits in `mask` are raised.
+    ///
+    /// Returns the bits as they were when the condition was met. If `clear` is `true`, the bits
+    /// in `mask` are lowered in the same atomic step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b10);
+    /// assert_eq!(group.wait_any(0b11, false).await, 0b10);
+    /// # });
+    /// ```
+    pub async fn wait_any(&self, mask: usize, clear: bool) -> usize {
+        self.wait_for(mask, false, clear).await
+    }
+
+    /// Waits asynchronously until all of the bits in `mask` are raised.
+    ///
+    /// Returns the bits as they were when the condition was met. If `clear` is `true`, the bits
+    /// in `mask` are lowered in the same atomic step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b11);
+    /// assert_eq!(group.wait_all(0b11, true).await, 0b11);
+    /// assert_eq!(group.get(), 0);
+    /// # });
+    /// ```
+    pub async fn wait_all(&self, mask: usize, clear: bool) -> usize {
+        self.wait_for(mask, true, clear).await
+    }
+
+    /// Blocks the current thread until any of the bits in `mask` are raised.
+    ///
+    /// Returns the bits as they were when the condition was met. If `clear` is `true`, the bits
+    /// in `mask` are lowered in the same atomic step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b100);
+    /// assert_eq!(group.wait_any_blocking(0b110, false), 0b100);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_any_blocking(&self, mask: usize, clear: bool) -> usize {
+        self.wait_for_blocking(mask, false, clear)
+    }
+
+    /// Blocks the current thread until all of the bits in `mask` are raised.
+    ///
+    /// Returns the bits as they were when the condition was met. If `clear` is `true`, the bits
+    /// in `mask` are lowered in the same atomic step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::EventGroup;
+    ///
+    /// let group = EventGroup::new();
+    /// group.set(0b110);
+    /// assert_eq!(group.wait_all_blocking(0b110, true), 0b110);
+    /// assert_eq!(group.get(), 0);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_all_blocking(&self, mask: usize, clear: bool) -> usize {
+        self.wait_for_blocking(mask, true, clear)
+    }
+
+    /// Check the condition, clearing the bits if it is met and `clear` is set.
+    fn try_match(&self, mask: usize, all: bool, clear: bool) -> Option<usize> {
+        let mut bits = self.bits.load(Ordering::SeqCst);
+
+        loop {
+            let matched = if all {
+                bits & mask == mask
+            } else {
+                bits & mask != 0
+            };
+
+            if !matched {
+                return None;
+            }
+
+            if !clear {
+                return Some(bits);
+            }
+
+            match self.bits.compare_exchange_weak(
+                bits,
+                bits & !mask,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Some(bits),
+                Err(b) => bits = b,
+            }
+        }
+    }
+
+    async fn wait_for(&self, mask: usize, all: bool, clear: bool) -> usize {
+        loop {
+            if let Some(bits) = self.try_match(mask, all, clear) {
+                return bits;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the bits again after creating the listener.
+            if let Some(bits) = self.try_match(mask, all, clear) {
+                return bits;
+            }
+
+            // Wait for some bits to be raised.
+            listener.await;
+        }
+    }
+
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_for_blocking(&self, mask: usize, all: bool, clear: bool) -> usize {
+        loop {
+            if let Some(bits) = self.try_match(mask, all, clear) {
+                return bits;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the bits again after creating the listener.
+            if let Some(bits) = self.try_match(mask, all, clear) {
+                return bits;
+            }
+
+            // Wait for some bits to be raised.
+            listener.wait();
+        }
+    }
+}
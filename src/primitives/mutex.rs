@@ -0,0 +1,348 @@
+//! A mutex with async and blocking locking.
+
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::{listener, Event};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use {crate::Listener, std::time::Duration, std::time::Instant};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// A mutual exclusion lock that can be acquired asynchronously or by blocking.
+///
+/// Unlike [`std::sync::Mutex`], this mutex is never poisoned: a panic while the lock is held
+/// simply releases it. Unlocking wakes up one waiter, which then competes for the lock with
+/// anyone else trying to take it, so the lock isn't handed over fairly.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use event_listener::primitives::Mutex;
+///
+/// let counter = Arc::new(Mutex::new(0));
+///
+/// let handles: Vec<_> = (0..10)
+///     .map(|_| {
+///         let counter = counter.clone();
+///         thread::spawn(move || *counter.lock_blocking() += 1)
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// assert_eq!(*counter.lock_blocking(), 10);
+/// ```
+pub struct Mutex<T: ?Sized> {
+    /// Whether the lock is held.
+    locked: AtomicBool,
+
+    /// Tasks waiting for the lock to be released.
+    event: Event,
+
+    /// The protected value.
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send + ?Sized> Send for Mutex<T> {}
+unsafe impl<T: Send + ?Sized> Sync for Mutex<T> {}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("Mutex");
+        match self.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for Mutex<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Mutex<T> {
+    /// Creates a new, unlocked mutex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            event: Event::new(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Creates a new, unlocked mutex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            event: Event::new(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the mutex and returns the protected value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(10);
+    /// assert_eq!(mutex.into_inner(), 10);
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Attempts to acquire the lock without waiting.
+    ///
+    /// Returns `None` if the lock is held.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(1);
+    ///
+    /// let guard = mutex.try_lock().unwrap();
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// drop(guard);
+    /// assert!(mutex.try_lock().is_some());
+    /// ```
+    #[inline]
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        if self
+            .locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            Some(MutexGuard { mutex: self })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires the lock asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let mutex = Mutex::new(1);
+    /// *mutex.lock().await += 1;
+    /// assert_eq!(*mutex.lock().await, 2);
+    /// # });
+    /// ```
+    pub async fn lock(&self) -> MutexGuard<'_, T> {
+        loop {
+            // Attempt to grab the lock.
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+
+            // Wait until the lock is released.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until the lock is acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(1);
+    /// *mutex.lock_blocking() += 1;
+    /// assert_eq!(*mutex.lock_blocking(), 2);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn lock_blocking(&self) -> MutexGuard<'_, T> {
+        self.lock_internal(None).unwrap()
+    }
+
+    /// Blocks the current thread until the lock is acquired or the timeout is reached.
+    ///
+    /// Returns `None` if the timeout was reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(1);
+    ///
+    /// let guard = mutex.lock_blocking();
+    /// assert!(mutex.lock_timeout(Duration::from_millis(10)).is_none());
+    ///
+    /// drop(guard);
+    /// assert!(mutex.lock_timeout(Duration::from_millis(10)).is_some());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn lock_timeout(&self, timeout: Duration) -> Option<MutexGuard<'_, T>> {
+        self.lock_internal(Instant::now().checked_add(timeout))
+    }
+
+    /// Blocks the current thread until the lock is acquired or the deadline is reached.
+    ///
+    /// Returns `None` if the deadline was reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mutex = Mutex::new(1);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert!(mutex.lock_deadline(deadline).is_some());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn lock_deadline(&self, deadline: Instant) -> Option<MutexGuard<'_, T>> {
+        self.lock_internal(Some(deadline))
+    }
+
+    /// Returns a mutable reference to the protected value.
+    ///
+    /// No locking is needed, since the mutex is borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Mutex;
+    ///
+    /// let mut mutex = Mutex::new(1);
+    /// *mutex.get_mut() = 2;
+    /// assert_eq!(*mutex.try_lock().unwrap(), 2);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn lock_internal(&self, deadline: Option<Instant>) -> Option<MutexGuard<'_, T>> {
+        loop {
+            // Attempt to grab the lock.
+            if let Some(guard) = self.try_lock() {
+                return Some(guard);
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_lock() {
+                return Some(guard);
+            }
+
+            // Wait until the lock is released.
+            match deadline {
+                None => listener.wait(),
+                Some(deadline) => {
+                    if listener.try_wait_deadline(deadline).is_err() {
+                        return self.try_lock();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A guard that releases the lock of a [`Mutex`] when dropped.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+}
+
+unsafe impl<T: Sync + ?Sized> Sync for MutexGuard<'_, T> {}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: The lock is held.
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The lock is held.
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.locked.store(false, Ordering::Release);
+
+        // Wake up a waiter, unless one is already on its way.
+        self.mutex.event.notify(1);
+    }
+}
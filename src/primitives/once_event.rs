@@ -0,0 +1,242 @@
+//! An event that can only be notified once.
+
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::{listener, Event};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use {crate::Listener, std::time::Duration, std::time::Instant};
+
+use core::fmt;
+
+/// An event that is notified exactly once.
+///
+/// Once [`OnceEvent::notify()`] has been called, every current waiter is woken up and every
+/// future wait completes immediately. This is useful for shutdown flags or for signalling that
+/// initialization has finished.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::primitives::OnceEvent;
+///
+/// let ready = OnceEvent::new();
+/// assert!(!ready.is_notified());
+///
+/// assert!(ready.notify());
+/// assert!(!ready.notify());
+///
+/// // This returns immediately.
+/// ready.wait_blocking();
+/// ```
+pub struct OnceEvent {
+    /// Whether the event has been notified.
+    notified: AtomicBool,
+
+    /// Tasks waiting for the notification.
+    event: Event,
+}
+
+impl fmt::Debug for OnceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceEvent")
+            .field("notified", &self.is_notified())
+            .finish()
+    }
+}
+
+impl Default for OnceEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnceEvent {
+    /// Creates a new, unnotified `OnceEvent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            notified: AtomicBool::new(false),
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new, unnotified `OnceEvent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            notified: AtomicBool::new(false),
+            event: Event::new(),
+        }
+    }
+
+    /// Notifies the event, waking up all current waiters.
+    ///
+    /// Returns `true` if this call notified the event and `false` if it was already notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// assert!(event.notify());
+    /// assert!(!event.notify());
+    /// ```
+    pub fn notify(&self) -> bool {
+        if self.notified.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        self.event.notify(usize::MAX);
+        true
+    }
+
+    /// Tells whether the event has been notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// assert!(!event.is_notified());
+    ///
+    /// event.notify();
+    /// assert!(event.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.notified.load(Ordering::SeqCst)
+    }
+
+    /// Waits asynchronously until the event is notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = OnceEvent::new();
+    /// event.notify();
+    /// event.wait().await;
+    /// # });
+    /// ```
+    pub async fn wait(&self) {
+        loop {
+            if self.is_notified() {
+                return;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the flag again after creating the listener.
+            if self.is_notified() {
+                return;
+            }
+
+            // Wait for the notification.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until the event is notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// event.notify();
+    /// event.wait_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking(&self) {
+        self.wait_internal(None);
+    }
+
+    /// Blocks the current thread until the event is notified or the timeout is reached.
+    ///
+    /// Returns `true` if the event was notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// assert!(!event.wait_timeout(Duration::from_millis(10)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        self.wait_internal(Instant::now().checked_add(timeout))
+    }
+
+    /// Blocks the current thread until the event is notified or the deadline is reached.
+    ///
+    /// Returns `true` if the event was notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::primitives::OnceEvent;
+    ///
+    /// let event = OnceEvent::new();
+    /// event.notify();
+    /// assert!(event.wait_deadline(Instant::now() + Duration::from_secs(1)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_deadline(&self, deadline: Instant) -> bool {
+        self.wait_internal(Some(deadline))
+    }
+
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_internal(&self, deadline: Option<Instant>) -> bool {
+        loop {
+            if self.is_notified() {
+                return true;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the flag again after creating the listener.
+            if self.is_notified() {
+                return true;
+            }
+
+            // Wait for the notification.
+            match deadline {
+                None => listener.wait(),
+                Some(deadline) => {
+                    if listener.try_wait_deadline(deadline).is_err() {
+                        return self.is_notified();
+                    }
+                }
+            }
+        }
+    }
+}
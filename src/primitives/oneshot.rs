@@ -0,0 +1,318 @@
+//! A channel for sending a single value.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::primitives::oneshot;
+//! # futures_lite::future::block_on(async {
+//!
+//! let (tx, rx) = oneshot::channel();
+//! tx.send(42).unwrap();
+//! assert_eq!(rx.await, Ok(42));
+//! # });
+//! ```
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Arc;
+use crate::{Event, EventListener};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::{listener, Listener};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A value has been written into the slot.
+const SENT: usize = 1 << 0;
+
+/// The value has been taken out of the slot by the receiver.
+const TAKEN: usize = 1 << 1;
+
+/// The sender has been dropped.
+const SENDER_CLOSED: usize = 1 << 2;
+
+/// The receiver has been dropped.
+const RECEIVER_CLOSED: usize = 1 << 3;
+
+/// Creates a new oneshot channel.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::primitives::oneshot;
+///
+/// let (tx, mut rx) = oneshot::channel();
+/// assert_eq!(rx.try_recv(), Err(oneshot::TryRecvError::Empty));
+///
+/// tx.send("hello").unwrap();
+/// assert_eq!(rx.try_recv(), Ok("hello"));
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: AtomicUsize::new(0),
+        value: UnsafeCell::new(None),
+        event: Event::new(),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver {
+            shared,
+            listener: None,
+        },
+    )
+}
+
+/// State shared between the sender and the receiver.
+struct Shared<T> {
+    /// Bit flags describing the channel state.
+    state: AtomicUsize,
+
+    /// The sent value.
+    ///
+    /// Only the sender may write this before `SENT` is set, and only the receiver may read it
+    /// after `SENT` is set.
+    value: UnsafeCell<Option<T>>,
+
+    /// The receiver waiting for a value.
+    event: Event,
+}
+
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+/// The sending half of a oneshot channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a value to the receiver.
+    ///
+    /// If the receiver has already been dropped, the value is handed back in `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// drop(rx);
+    /// assert_eq!(tx.send(1), Err(1));
+    /// ```
+    pub fn send(self, value: T) -> Result<(), T> {
+        // SAFETY: The receiver never reads the slot before `SENT` is set.
+        unsafe {
+            *self.shared.value.get() = Some(value);
+        }
+
+        let state = self.shared.state.fetch_or(SENT, Ordering::AcqRel);
+        if state & RECEIVER_CLOSED != 0 {
+            // The receiver will never read the slot, so take the value back.
+            // SAFETY: The receiver is gone, so we have exclusive access to the slot.
+            let value = unsafe { (*self.shared.value.get()).take() };
+            return Err(value.unwrap());
+        }
+
+        self.shared.event.notify(1);
+        Ok(())
+    }
+
+    /// Tells whether the receiver has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel::<i32>();
+    /// assert!(!tx.is_closed());
+    ///
+    /// drop(rx);
+    /// assert!(tx.is_closed());
+    /// ```
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.shared.state.load(Ordering::Acquire) & RECEIVER_CLOSED != 0
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.state.fetch_or(SENDER_CLOSED, Ordering::AcqRel);
+        self.shared.event.notify(1);
+    }
+}
+
+/// The receiving half of a oneshot channel.
+///
+/// This is a future that resolves to the sent value, or to [`RecvError`] if the sender was
+/// dropped without sending anything.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// Listens for the sender, if we are waiting.
+    listener: Option<EventListener>,
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive the value without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::oneshot;
+    ///
+    /// let (tx, mut rx) = oneshot::channel::<i32>();
+    /// assert_eq!(rx.try_recv(), Err(oneshot::TryRecvError::Empty));
+    ///
+    /// drop(tx);
+    /// assert_eq!(rx.try_recv(), Err(oneshot::TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let state = self.shared.state.load(Ordering::Acquire);
+
+        if state & (SENT | TAKEN) == SENT {
+            self.shared.state.fetch_or(TAKEN, Ordering::Relaxed);
+
+            // SAFETY: `SENT` is set, so the sender no longer touches the slot.
+            let value = unsafe { (*self.shared.value.get()).take() };
+            return Ok(value.unwrap());
+        }
+
+        if state & (SENDER_CLOSED | TAKEN) != 0 {
+            Err(TryRecvError::Closed)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Blocks the current thread until the value is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use event_listener::primitives::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// thread::spawn(move || tx.send(7).unwrap());
+    /// assert_eq!(rx.recv_blocking(), Ok(7));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn recv_blocking(mut self) -> Result<T, RecvError> {
+        let shared = self.shared.clone();
+
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Closed) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            // Set up an event listener.
+            listener!(shared.event => listener);
+
+            // Check again now that we're listening.
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Closed) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            // Wait for the sender.
+            listener.wait();
+        }
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Poll::Ready(Ok(value)),
+                Err(TryRecvError::Closed) => return Poll::Ready(Err(RecvError)),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            match self.listener.as_mut() {
+                None => {
+                    // Start listening and then check again.
+                    self.listener = Some(self.shared.event.listen());
+                }
+
+                Some(listener) => {
+                    // Wait for the sender.
+                    match Pin::new(listener).poll(cx) {
+                        Poll::Ready(()) => self.listener = None,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared
+            .state
+            .fetch_or(RECEIVER_CLOSED, Ordering::AcqRel);
+    }
+}
+
+/// An error returned when the sender was dropped without sending a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("receiving from a closed oneshot channel")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecvError {}
+
+/// An error returned from [`Receiver::try_recv()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TryRecvError {
+    /// No value has been sent yet.
+    Empty,
+
+    /// The sender was dropped without sending a value, or the value was already received.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("receiving from an empty oneshot channel"),
+            Self::Closed => f.write_str("receiving from a closed oneshot channel"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryRecvError {}
@@ -0,0 +1,501 @@
+//! A reader-writer lock with async and blocking locking.
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::DualEvent;
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+
+/// Set in the state while a writer holds the lock.
+const WRITER: usize = 1;
+
+/// The amount added to the state for each reader holding the lock.
+const ONE_READER: usize = 2;
+
+/// A reader-writer lock that can be acquired asynchronously or by blocking.
+///
+/// Any number of readers may hold the lock at once, or one writer. The lock prefers writers: as
+/// soon as a writer is waiting, new readers wait too, so that a steady stream of readers can't
+/// starve it. Readers and writers wait on the two classes of a [`DualEvent`], so that releasing
+/// the lock wakes up the next writer, or every reader if no writer is waiting.
+///
+/// Like [`Mutex`], this lock is never poisoned.
+///
+/// [`DualEvent`]: crate::DualEvent
+/// [`Mutex`]: crate::primitives::Mutex
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use event_listener::primitives::RwLock;
+///
+/// let lock = Arc::new(RwLock::new(0));
+///
+/// let handles: Vec<_> = (0..10)
+///     .map(|i| {
+///         let lock = lock.clone();
+///         thread::spawn(move || {
+///             if i % 2 == 0 {
+///                 *lock.write_blocking() += 1;
+///             } else {
+///                 assert!(*lock.read_blocking() <= 5);
+///             }
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// assert_eq!(*lock.read_blocking(), 5);
+/// ```
+pub struct RwLock<T: ?Sized> {
+    /// The number of readers holding the lock, times [`ONE_READER`], plus [`WRITER`] if a writer
+    /// holds it.
+    state: AtomicUsize,
+
+    /// The number of writers waiting for the lock.
+    writers_waiting: AtomicUsize,
+
+    /// Readers waiting as class A, and writers waiting as class B.
+    event: DualEvent,
+
+    /// The protected value.
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send + ?Sized> Send for RwLock<T> {}
+unsafe impl<T: Send + Sync + ?Sized> Sync for RwLock<T> {}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("RwLock");
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+impl<T: Default> Default for RwLock<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new, unlocked reader-writer lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            writers_waiting: AtomicUsize::new(0),
+            event: DualEvent::new(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Creates a new, unlocked reader-writer lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            writers_waiting: AtomicUsize::new(0),
+            event: DualEvent::new(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the lock and returns the protected value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(10);
+    /// assert_eq!(lock.into_inner(), 10);
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    /// Attempts to acquire a read lock without waiting.
+    ///
+    /// Returns `None` if a writer holds the lock, or if one is waiting for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let reader1 = lock.try_read().unwrap();
+    /// let reader2 = lock.try_read().unwrap();
+    /// assert!(lock.try_write().is_none());
+    /// ```
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::SeqCst);
+
+        loop {
+            // Give way to writers.
+            if state & WRITER != 0 || self.writers_waiting.load(Ordering::SeqCst) > 0 {
+                return None;
+            }
+
+            if state > usize::MAX - ONE_READER {
+                panic!("too many readers");
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state + ONE_READER,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Some(RwLockReadGuard { lock: self }),
+                Err(s) => state = s,
+            }
+        }
+    }
+
+    /// Acquires a read lock asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let lock = RwLock::new(1);
+    /// let reader1 = lock.read().await;
+    /// let reader2 = lock.read().await;
+    /// assert_eq!(*reader1 + *reader2, 2);
+    /// # });
+    /// ```
+    pub async fn read(&self) -> RwLockReadGuard<'_, T> {
+        loop {
+            // Attempt to grab the lock.
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+
+            // Set up an event listener.
+            let listener = self.event.listen_a();
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+
+            // Wait until the lock is released.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until a read lock is acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    /// assert_eq!(*lock.read_blocking(), 1);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn read_blocking(&self) -> RwLockReadGuard<'_, T> {
+        use crate::Listener;
+
+        loop {
+            // Attempt to grab the lock.
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+
+            // Set up an event listener.
+            let listener = self.event.listen_a();
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+
+            // Wait until the lock is released.
+            listener.wait();
+        }
+    }
+
+    /// Attempts to acquire a write lock without waiting.
+    ///
+    /// Returns `None` if the lock is held.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let writer = lock.try_write().unwrap();
+    /// assert!(lock.try_read().is_none());
+    ///
+    /// drop(writer);
+    /// assert!(lock.try_read().is_some());
+    /// ```
+    #[inline]
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        if self
+            .state
+            .compare_exchange(0, WRITER, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            Some(RwLockWriteGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires a write lock asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let lock = RwLock::new(1);
+    /// *lock.write().await += 1;
+    /// assert_eq!(*lock.read().await, 2);
+    /// # });
+    /// ```
+    pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
+        // Attempt to grab the lock.
+        if let Some(guard) = self.try_write() {
+            return guard;
+        }
+
+        // Hold new readers back until we get the lock.
+        let intent = WriteIntent::new(self);
+
+        loop {
+            // Set up an event listener.
+            let listener = self.event.listen_b();
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_write() {
+                intent.acquired();
+                return guard;
+            }
+
+            // Wait until the lock is released.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until a write lock is acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    /// *lock.write_blocking() += 1;
+    /// assert_eq!(*lock.read_blocking(), 2);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn write_blocking(&self) -> RwLockWriteGuard<'_, T> {
+        use crate::Listener;
+
+        // Attempt to grab the lock.
+        if let Some(guard) = self.try_write() {
+            return guard;
+        }
+
+        // Hold new readers back until we get the lock.
+        let intent = WriteIntent::new(self);
+
+        loop {
+            // Set up an event listener.
+            let listener = self.event.listen_b();
+
+            // Try again now that we're listening.
+            if let Some(guard) = self.try_write() {
+                intent.acquired();
+                return guard;
+            }
+
+            // Wait until the lock is released.
+            listener.wait();
+        }
+    }
+
+    /// Returns a mutable reference to the protected value.
+    ///
+    /// No locking is needed, since the lock is borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::RwLock;
+    ///
+    /// let mut lock = RwLock::new(1);
+    /// *lock.get_mut() = 2;
+    /// assert_eq!(*lock.try_read().unwrap(), 2);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+/// A writer waiting for the lock, which keeps new readers from taking it.
+struct WriteIntent<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<'a, T: ?Sized> WriteIntent<'a, T> {
+    fn new(lock: &'a RwLock<T>) -> Self {
+        lock.writers_waiting.fetch_add(1, Ordering::SeqCst);
+        Self { lock }
+    }
+
+    /// Stops waiting after the lock was acquired.
+    ///
+    /// Readers are left waiting, since the writer holds the lock now.
+    fn acquired(self) {
+        self.lock.writers_waiting.fetch_sub(1, Ordering::SeqCst);
+        mem::forget(self);
+    }
+}
+
+impl<T: ?Sized> Drop for WriteIntent<'_, T> {
+    fn drop(&mut self) {
+        // The writer gave up. If it was the last one, let the readers in.
+        if self.lock.writers_waiting.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.lock.event.notify_a(usize::MAX);
+        }
+    }
+}
+
+/// A guard that releases a read lock of a [`RwLock`] when dropped.
+pub struct RwLockReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+unsafe impl<T: Sync + ?Sized> Sync for RwLockReadGuard<'_, T> {}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for RwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: A read lock is held.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // If this was the last reader, wake up a writer.
+        if self.lock.state.fetch_sub(ONE_READER, Ordering::SeqCst) == ONE_READER {
+            self.lock.event.notify_b(1);
+        }
+    }
+}
+
+/// A guard that releases the write lock of a [`RwLock`] when dropped.
+pub struct RwLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+unsafe impl<T: Sync + ?Sized> Sync for RwLockWriteGuard<'_, T> {}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for RwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: The write lock is held.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The write lock is held.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.state.fetch_and(!WRITER, Ordering::SeqCst);
+
+        // Wake up the next writer, or all readers if no writer is waiting.
+        self.lock.event.notify_b_or_a(1, usize::MAX);
+    }
+}
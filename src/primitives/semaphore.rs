@@ -0,0 +1,296 @@
+//! A counting semaphore.
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::{listener, Event, IntoNotification};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::Listener;
+
+use core::fmt;
+
+/// A counter for limiting the number of concurrent operations.
+///
+/// The semaphore holds a number of permits. [`Semaphore::acquire()`] waits until a permit is
+/// available and takes it, and the returned [`SemaphorePermit`] gives it back when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::primitives::Semaphore;
+///
+/// let s = Semaphore::new(2);
+///
+/// let p1 = s.try_acquire().unwrap();
+/// let p2 = s.try_acquire().unwrap();
+/// assert!(s.try_acquire().is_none());
+///
+/// drop(p1);
+/// assert!(s.try_acquire().is_some());
+/// # drop(p2);
+/// ```
+pub struct Semaphore {
+    /// The number of available permits.
+    count: AtomicUsize,
+
+    /// Tasks waiting for a permit.
+    event: Event,
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.count.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl Semaphore {
+    /// The largest number of permits a semaphore can hold.
+    ///
+    /// This leaves room for the permits that are handed out, so that returning them can't
+    /// overflow the counter, even if [`Semaphore::add_permits()`] filled it up in the meantime.
+    pub const MAX_PERMITS: usize = usize::MAX >> 3;
+
+    /// Creates a new semaphore with `n` permits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than [`Semaphore::MAX_PERMITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(5);
+    /// ```
+    #[cfg(not(loom))]
+    #[inline]
+    pub const fn new(n: usize) -> Self {
+        assert!(
+            n <= Self::MAX_PERMITS,
+            "a semaphore may not have more than MAX_PERMITS permits"
+        );
+
+        Self {
+            count: AtomicUsize::new(n),
+            event: Event::new(),
+        }
+    }
+
+    /// Creates a new semaphore with `n` permits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than [`Semaphore::MAX_PERMITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(5);
+    /// ```
+    #[cfg(loom)]
+    #[inline]
+    pub fn new(n: usize) -> Self {
+        assert!(
+            n <= Self::MAX_PERMITS,
+            "a semaphore may not have more than MAX_PERMITS permits"
+        );
+
+        Self {
+            count: AtomicUsize::new(n),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(2);
+    /// let _p = s.try_acquire();
+    /// assert_eq!(s.available_permits(), 1);
+    /// ```
+    #[inline]
+    pub fn available_permits(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Attempts to get a permit without waiting.
+    ///
+    /// Returns `None` if no permits are available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(1);
+    ///
+    /// let permit = s.try_acquire().unwrap();
+    /// assert!(s.try_acquire().is_none());
+    ///
+    /// drop(permit);
+    /// assert!(s.try_acquire().is_some());
+    /// ```
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let mut count = self.count.load(Ordering::Acquire);
+
+        loop {
+            if count == 0 {
+                return None;
+            }
+
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Some(SemaphorePermit { semaphore: self }),
+                Err(c) => count = c,
+            }
+        }
+    }
+
+    /// Waits for a permit asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let s = Semaphore::new(1);
+    /// let permit = s.acquire().await;
+    /// assert_eq!(s.available_permits(), 0);
+    /// # });
+    /// ```
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        loop {
+            // Attempt to grab a permit.
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Try again now that we're listening.
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            // Wait until a permit is released.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until a permit is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(1);
+    /// let permit = s.acquire_blocking();
+    /// assert_eq!(s.available_permits(), 0);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn acquire_blocking(&self) -> SemaphorePermit<'_> {
+        loop {
+            // Attempt to grab a permit.
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Try again now that we're listening.
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            // Wait until a permit is released.
+            listener.wait();
+        }
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up to `n` waiters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the semaphore would hold more than [`Semaphore::MAX_PERMITS`] permits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(0);
+    /// s.add_permits(2);
+    /// assert_eq!(s.available_permits(), 2);
+    /// ```
+    pub fn add_permits(&self, n: usize) {
+        let added = self
+            .count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count
+                    .checked_add(n)
+                    .filter(|&count| count <= Self::MAX_PERMITS)
+            });
+        if added.is_err() {
+            panic!("a semaphore may not have more than MAX_PERMITS permits");
+        }
+
+        // Each permit gets its own waiter, even if earlier waiters haven't woken up yet.
+        self.event.notify(n.additional());
+    }
+}
+
+/// A permit acquired from a [`Semaphore`].
+///
+/// The permit is returned to the semaphore when this is dropped.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit").finish_non_exhaustive()
+    }
+}
+
+impl SemaphorePermit<'_> {
+    /// Drops the permit without returning it to the semaphore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::Semaphore;
+    ///
+    /// let s = Semaphore::new(1);
+    /// s.try_acquire().unwrap().forget();
+    /// assert_eq!(s.available_permits(), 0);
+    /// ```
+    #[inline]
+    pub fn forget(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // The permit was taken from the counter, so `MAX_PERMITS` leaves room to put it back.
+        self.semaphore.count.fetch_add(1, Ordering::AcqRel);
+        self.semaphore.event.notify(1.additional());
+    }
+}
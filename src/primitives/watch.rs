@@ -0,0 +1,274 @@
+//! A value that tasks can watch for changes.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::primitives::watch;
+//!
+//! let config = watch::Value::new("initial");
+//! let seen = config.version();
+//!
+//! config.set("updated");
+//!
+//! // The value changed since we last looked, so this returns immediately.
+//! let seen = config.changed_blocking(seen);
+//! assert_eq!(*config.borrow(), "updated");
+//! assert_eq!(seen, config.version());
+//! ```
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::{listener, Event};
+
+#[cfg(not(target_family = "wasm"))]
+use crate::Listener;
+
+use core::fmt;
+use core::ops::Deref;
+use std::sync::{RwLock, RwLockReadGuard};
+
+/// A value with a version counter that is bumped every time the value is replaced.
+///
+/// Readers can [`borrow()`] the current value, and wait for it to be replaced with
+/// [`changed()`]. Waiting is relative to a version number obtained from [`version()`] or from a
+/// previous wait, so replacements that happen between two waits are never missed.
+///
+/// [`borrow()`]: Value::borrow
+/// [`changed()`]: Value::changed
+/// [`version()`]: Value::version
+pub struct Value<T> {
+    /// The current value.
+    value: RwLock<T>,
+
+    /// The number of times the value has been replaced.
+    version: AtomicUsize,
+
+    /// Tasks waiting for the value to change.
+    event: Event,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Value")
+            .field("value", &*self.borrow())
+            .field("version", &self.version())
+            .finish()
+    }
+}
+
+impl<T: Default> Default for Value<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Value<T> {
+    /// Creates a new watched value at version zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(1);
+    /// assert_eq!(value.version(), 0);
+    /// ```
+    pub fn new(value: T) -> Self {
+        Self {
+            value: RwLock::new(value),
+            version: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the current version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(1);
+    /// value.set(2);
+    /// assert_eq!(value.version(), 1);
+    /// ```
+    #[inline]
+    pub fn version(&self) -> usize {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Borrows the current value.
+    ///
+    /// Replacing the value blocks while the returned guard is alive, so don't hold on to it for
+    /// long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(String::from("hello"));
+    /// assert_eq!(value.borrow().len(), 5);
+    /// ```
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref(self.value.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Replaces the value, wakes all waiters and returns the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(1);
+    /// assert_eq!(value.replace(2), 1);
+    /// assert_eq!(*value.borrow(), 2);
+    /// ```
+    pub fn replace(&self, value: T) -> T {
+        let mut old = Some(value);
+        self.modify(|v| old = Some(core::mem::replace(v, old.take().unwrap())));
+        old.unwrap()
+    }
+
+    /// Sets the value and wakes all waiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(1);
+    /// value.set(2);
+    /// assert_eq!(*value.borrow(), 2);
+    /// ```
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.replace(value);
+    }
+
+    /// Modifies the value in place and wakes all waiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = watch::Value::new(vec![1]);
+    /// value.modify(|v| v.push(2));
+    /// assert_eq!(*value.borrow(), [1, 2]);
+    /// ```
+    pub fn modify(&self, f: impl FnOnce(&mut T)) {
+        {
+            let mut value = self.value.write().unwrap_or_else(|e| e.into_inner());
+            f(&mut value);
+
+            // Bump the version while still holding the lock, so that readers never see a new
+            // version with an old value.
+            self.version.fetch_add(1, Ordering::SeqCst);
+        }
+
+        self.event.notify(usize::MAX);
+    }
+
+    /// Waits asynchronously until the version differs from `since`.
+    ///
+    /// Returns the new version, which can be passed to the next call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::primitives::watch;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let value = watch::Value::new(1);
+    /// let seen = value.version();
+    /// value.set(2);
+    ///
+    /// let seen = value.changed(seen).await;
+    /// assert_eq!(seen, value.version());
+    /// # });
+    /// ```
+    pub async fn changed(&self, since: usize) -> usize {
+        loop {
+            let version = self.version();
+            if version != since {
+                return version;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the version again after creating the listener.
+            let version = self.version();
+            if version != since {
+                return version;
+            }
+
+            // Wait for the value to change.
+            listener.await;
+        }
+    }
+
+    /// Blocks the current thread until the version differs from `since`.
+    ///
+    /// Returns the new version, which can be passed to the next call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::primitives::watch;
+    ///
+    /// let value = Arc::new(watch::Value::new(1));
+    /// let seen = value.version();
+    ///
+    /// thread::spawn({
+    ///     let value = value.clone();
+    ///     move || value.set(2)
+    /// });
+    ///
+    /// value.changed_blocking(seen);
+    /// assert_eq!(*value.borrow(), 2);
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn changed_blocking(&self, since: usize) -> usize {
+        loop {
+            let version = self.version();
+            if version != since {
+                return version;
+            }
+
+            // Set up an event listener.
+            listener!(self.event => listener);
+
+            // Check the version again after creating the listener.
+            let version = self.version();
+            if version != since {
+                return version;
+            }
+
+            // Wait for the value to change.
+            listener.wait();
+        }
+    }
+}
+
+/// A borrowed reference to the value inside of a [`Value`].
+pub struct Ref<'a, T>(RwLockReadGuard<'a, T>);
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
@@ -0,0 +1,200 @@
+//! Waiting on whichever of several listeners is notified first.
+
+use crate::{Event, EventListener, Listener};
+
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Waits for whichever of two listeners is notified first.
+///
+/// The returned future resolves to the tag of the first listener that is notified. The other
+/// listener is dropped at that point. If it was notified as well, dropping it passes its
+/// notification on to the next listener of its event, so no notification is lost.
+///
+/// The listeners may belong to different events.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{race, Event, IntoNotification};
+/// # futures_lite::future::block_on(async {
+///
+/// let event1 = Event::with_tag();
+/// let event2 = Event::with_tag();
+///
+/// let listener = race(event1.listen(), event2.listen());
+/// event2.notify(1.tag(2));
+///
+/// assert_eq!(listener.await, 2);
+/// # });
+/// ```
+///
+/// A notification of the listener that lost the race is passed on:
+///
+/// ```
+/// use event_listener::{race, Event};
+/// # futures_lite::future::block_on(async {
+///
+/// let event = Event::new();
+/// let other = Event::new();
+///
+/// let listener = race(event.listen(), other.listen());
+/// let next = other.listen();
+///
+/// event.notify(1);
+/// other.notify(1);
+/// listener.await;
+///
+/// // `other` notified the raced listener, which passed the notification on.
+/// next.await;
+/// # });
+/// ```
+pub fn race<T, A, B>(a: A, b: B) -> Race<T, A, B>
+where
+    A: Listener<T>,
+    B: Listener<T>,
+{
+    Race {
+        a: Some(a),
+        b: Some(b),
+        _marker: PhantomData,
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A future that waits for whichever of two listeners is notified first.
+    ///
+    /// This is created by [`race()`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Race<T, A, B> {
+        #[pin]
+        a: Option<A>,
+        #[pin]
+        b: Option<B>,
+        _marker: PhantomData<fn() -> T>,
+    }
+}
+
+impl<T, A: fmt::Debug, B: fmt::Debug> fmt::Debug for Race<T, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Race")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+impl<T, A, B> Future for Race<T, A, B>
+where
+    A: Listener<T>,
+    B: Listener<T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut this = self.project();
+
+        let a = this
+            .a
+            .as_mut()
+            .as_pin_mut()
+            .expect("`Race` polled after completion");
+        if let Poll::Ready(tag) = a.poll(cx) {
+            // Drop both listeners, so that `b` passes on a notification it may have received.
+            this.a.set(None);
+            this.b.set(None);
+            return Poll::Ready(tag);
+        }
+
+        let b = this
+            .b
+            .as_mut()
+            .as_pin_mut()
+            .expect("`Race` polled after completion");
+        if let Poll::Ready(tag) = b.poll(cx) {
+            this.a.set(None);
+            this.b.set(None);
+            return Poll::Ready(tag);
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Waits for whichever of several events is notified first.
+///
+/// A listener is registered on every event before this function returns, so a notification sent
+/// to any of them after the call is never missed, even if the returned future has not been
+/// polled yet. The future resolves to the index of the event that was notified first, together
+/// with the tag of the notification.
+///
+/// Once the future resolves, the listeners on the other events are dropped. A listener that was
+/// notified as well passes its notification on to the next listener of its event.
+///
+/// If `events` is empty, the future never resolves.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{select_events, Event};
+/// # futures_lite::future::block_on(async {
+///
+/// let events = [Event::new(), Event::new(), Event::new()];
+///
+/// let select = select_events(&[&events[0], &events[1], &events[2]]);
+/// events[1].notify(1);
+///
+/// assert_eq!(select.await, (1, ()));
+/// # });
+/// ```
+pub fn select_events<T>(events: &[&Event<T>]) -> SelectEvents<T> {
+    SelectEvents {
+        listeners: events.iter().map(|event| event.listen()).collect(),
+        done: false,
+    }
+}
+
+/// A future that waits for whichever of several events is notified first.
+///
+/// This is created by [`select_events()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectEvents<T = ()> {
+    /// One listener per event, in the order the events were given.
+    listeners: Vec<EventListener<T>>,
+
+    /// Whether the future has completed.
+    done: bool,
+}
+
+impl<T> fmt::Debug for SelectEvents<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectEvents")
+            .field("listeners", &self.listeners)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<T> Future for SelectEvents<T> {
+    type Output = (usize, T);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<(usize, T)> {
+        assert!(!self.done, "`SelectEvents` polled after completion");
+
+        for (index, listener) in self.listeners.iter_mut().enumerate() {
+            if let Poll::Ready(tag) = Pin::new(listener).poll(cx) {
+                // Drop the other listeners, so that they pass on notifications they received.
+                self.listeners.clear();
+                self.done = true;
+                return Poll::Ready((index, tag));
+            }
+        }
+
+        Poll::Pending
+    }
+}
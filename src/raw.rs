@@ -0,0 +1,314 @@
+//! An [`Event`] whose state is stored inline, for embedding in other primitives.
+//!
+//! [`Event`]: crate::Event
+
+use crate::notify::{Internal, NotificationPrivate};
+use crate::sync::atomic::Ordering;
+use crate::{EventListenerRef, Inner, InnerListener, IntoNotification};
+
+use core::fmt;
+
+/// An event whose listener list is stored inline, instead of behind an [`Arc`].
+///
+/// [`Event`] allocates its state lazily and shares it with its [`EventListener`]s through an
+/// [`Arc`]. That is convenient, but a primitive that embeds exactly one event per object, like
+/// an async lock, pays for the allocation and for a pointer indirection on every operation.
+/// [`RawEvent`] stores the state directly in its owner instead. In exchange, listeners borrow the
+/// event: they are [`EventListenerRef`]s and have to be pinned.
+///
+/// Listeners are created with [`RawEvent::listen()`], or with the unsafe
+/// [`RawEvent::listen_unchecked()`] when the borrow can't be expressed, such as in a future that
+/// is stored inside the object owning the event.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::RawEvent;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Flag {
+///     set: AtomicBool,
+///     event: RawEvent,
+/// }
+///
+/// impl Flag {
+///     async fn wait(&self) {
+///         while !self.set.load(Ordering::Acquire) {
+///             let listener = self.event.listen();
+///             futures_lite::pin!(listener);
+///             listener.as_mut().listen();
+///
+///             if self.set.load(Ordering::Acquire) {
+///                 break;
+///             }
+///             listener.await;
+///         }
+///     }
+///
+///     fn set(&self) {
+///         self.set.store(true, Ordering::Release);
+///         self.event.notify(usize::MAX);
+///     }
+/// }
+///
+/// let flag = Flag { set: AtomicBool::new(false), event: RawEvent::new() };
+/// flag.set();
+/// flag.wait().await;
+/// # });
+/// ```
+///
+/// [`Arc`]: alloc::sync::Arc
+/// [`Event`]: crate::Event
+/// [`EventListener`]: crate::EventListener
+pub struct RawEvent<T = ()> {
+    /// The state of the event.
+    inner: Inner<T>,
+}
+
+unsafe impl<T: Send> Send for RawEvent<T> {}
+unsafe impl<T: Send> Sync for RawEvent<T> {}
+
+impl<T> core::panic::UnwindSafe for RawEvent<T> {}
+impl<T> core::panic::RefUnwindSafe for RawEvent<T> {}
+
+impl<T> fmt::Debug for RawEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawEvent")
+            .field("listeners", &self.listener_count())
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for RawEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RawEvent {
+    /// Creates a new [`RawEvent`].
+    ///
+    /// Unlike [`Event::new()`], this initializes the listener list right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    ///
+    /// let event = RawEvent::new();
+    /// ```
+    ///
+    /// [`Event::new()`]: crate::Event::new
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            inner: Inner::new(),
+        }
+    }
+}
+
+impl<T> RawEvent<T> {
+    /// Creates a new [`RawEvent`] with a tag type.
+    ///
+    /// Tagging cannot be implemented efficiently on `no_std`, so this is only available when the
+    /// `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    ///
+    /// let event = RawEvent::<usize>::with_tag();
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn with_tag() -> Self {
+        Self {
+            inner: Inner::new(),
+        }
+    }
+
+    /// Returns a listener borrowing this event.
+    ///
+    /// The listener starts listening once [`EventListenerRef::listen()`] is called, or when it is
+    /// first polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    /// use futures_lite::pin;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = RawEvent::new();
+    /// let listener = event.listen();
+    /// pin!(listener);
+    /// listener.as_mut().listen();
+    ///
+    /// event.notify(1);
+    /// listener.await;
+    /// # });
+    /// ```
+    #[inline]
+    pub fn listen(&self) -> EventListenerRef<'_, T> {
+        // SAFETY: The listener borrows the event, so it can't outlive it.
+        unsafe { self.listen_unchecked() }
+    }
+
+    /// Returns a listener for this event with an unbounded lifetime.
+    ///
+    /// This works like [`RawEvent::listen()`], but the listener does not borrow the event. This
+    /// makes it possible to store the listener next to the event, such as in a future returned by
+    /// a method of the object owning the event.
+    ///
+    /// # Safety
+    ///
+    /// The listener must be dropped before the event is moved or dropped. Pinning the owner of
+    /// the event, or keeping it behind an `Arc`, is a common way to uphold this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{EventListenerRef, RawEvent};
+    /// use futures_lite::pin;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = RawEvent::new();
+    ///
+    /// // SAFETY: The listener is dropped before the event.
+    /// let listener: EventListenerRef<'static> = unsafe { event.listen_unchecked() };
+    /// pin!(listener);
+    /// listener.as_mut().listen();
+    ///
+    /// event.notify(1);
+    /// listener.await;
+    /// # });
+    /// ```
+    #[inline]
+    pub unsafe fn listen_unchecked<'b>(&self) -> EventListenerRef<'b, T> {
+        EventListenerRef {
+            listener: InnerListener {
+                event: &*(&self.inner as *const Inner<T>),
+                listener: None,
+            },
+        }
+    }
+
+    /// Notifies a number of active listeners.
+    ///
+    /// This works exactly like [`Event::notify()`], and returns the number of listeners that
+    /// were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, RawEvent};
+    ///
+    /// let event = RawEvent::new();
+    /// assert_eq!(event.notify(1), 0);
+    /// assert_eq!(event.notify(1.additional()), 0);
+    /// ```
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    #[inline]
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(Internal::new());
+
+        self.inner
+            .deliver(notify, |inner, notify| inner.notify(notify))
+    }
+
+    /// Closes the event.
+    ///
+    /// This works exactly like [`Event::close()`], and returns `false` if the event was already
+    /// closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    ///
+    /// let event = RawEvent::new();
+    /// assert!(event.close());
+    /// assert!(!event.close());
+    /// assert!(event.is_closed());
+    /// ```
+    ///
+    /// [`Event::close()`]: crate::Event::close
+    pub fn close(&self) -> bool
+    where
+        T: Default,
+    {
+        self.close_with(T::default)
+    }
+
+    /// Closes the event, completing plain listeners with tags made by `tag`.
+    ///
+    /// This works exactly like [`Event::close_with()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    ///
+    /// let event = RawEvent::<u32>::with_tag();
+    /// assert!(event.close_with(|| 7));
+    /// assert!(event.is_closed());
+    /// ```
+    ///
+    /// [`Event::close_with()`]: crate::Event::close_with
+    pub fn close_with(&self, tag: fn() -> T) -> bool {
+        if self.inner.closed.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        self.inner.close(tag);
+        true
+    }
+
+    /// Tells whether the event has been closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    ///
+    /// let event = RawEvent::new();
+    /// assert!(!event.is_closed());
+    /// ```
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of listeners currently registered to this [`RawEvent`].
+    ///
+    /// Like [`Event::listener_count()`], this should only ever be used as a hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::RawEvent;
+    /// use futures_lite::pin;
+    ///
+    /// let event = RawEvent::new();
+    /// let listener = event.listen();
+    /// pin!(listener);
+    /// assert_eq!(event.listener_count(), 0);
+    ///
+    /// listener.as_mut().listen();
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    ///
+    /// [`Event::listener_count()`]: crate::Event::listener_count
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.inner.listeners.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,230 @@
+//! Waiting for the list of listeners to change.
+
+use crate::sync::atomic::Ordering;
+use crate::{listener, Event, Inner, IntoNotification};
+
+impl<T> Inner<T> {
+    /// Returns `true` if a listener holds a notification that it hasn't consumed yet.
+    fn has_notified(&self) -> bool {
+        self.notified.load(Ordering::Acquire) != 0 && self.listeners.load(Ordering::Acquire) != 0
+    }
+
+    /// Wakes up everyone waiting for the list to change, so that they can check it again.
+    ///
+    /// This is called whenever the list is unlocked.
+    #[inline]
+    pub(crate) fn notify_changed(&self) {
+        if let Some(changed) = self.changed.try_inner() {
+            if changed.listeners.load(Ordering::Acquire) > 0 {
+                self.changed.notify(usize::MAX);
+            }
+        }
+    }
+}
+
+impl<T> Event<T> {
+    /// Notifies a number of active listeners and waits until they have consumed the notification.
+    ///
+    /// This works like [`Event::notify()`], but the returned future only resolves once no
+    /// listener of this event holds a notification anymore, which includes listeners notified
+    /// before this call. A notification is consumed when its listener completes, or when the
+    /// listener is dropped without passing the notification on.
+    ///
+    /// This makes it possible to notify every waiter and only then tear down the state they were
+    /// waiting on, knowing that none of them is still about to look at it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Arc::new(Event::new());
+    /// let listener = event.listen();
+    ///
+    /// let waiter = thread::spawn(move || listener.wait());
+    ///
+    /// // Returns once the waiter has woken up.
+    /// assert_eq!(event.notify_sync(1).await, 1);
+    /// # waiter.join().unwrap();
+    /// # });
+    /// ```
+    pub async fn notify_sync(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let count = self.notify_count(notify);
+        let inner = unsafe { &*self.inner() };
+
+        loop {
+            if !inner.has_notified() {
+                return count;
+            }
+
+            // Set up an event listener.
+            listener!(inner.changed => listener);
+
+            // Check again now that we're listening.
+            if !inner.has_notified() {
+                return count;
+            }
+
+            // Wait until the list changes.
+            listener.await;
+        }
+    }
+
+    /// Notifies a number of active listeners and blocks until they have consumed the
+    /// notification.
+    ///
+    /// This is the blocking version of [`Event::notify_sync()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let event = Arc::new(Event::new());
+    ///
+    /// let waiters: Vec<_> = (0..3)
+    ///     .map(|_| {
+    ///         let listener = event.listen();
+    ///         thread::spawn(move || listener.wait())
+    ///     })
+    ///     .collect();
+    ///
+    /// // Returns once every waiter has woken up.
+    /// assert_eq!(event.notify_sync_blocking(usize::MAX), 3);
+    /// assert_eq!(event.total_listeners(), 0);
+    /// # for waiter in waiters { waiter.join().unwrap(); }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn notify_sync_blocking(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        use crate::Listener;
+
+        let count = self.notify_count(notify);
+        let inner = unsafe { &*self.inner() };
+
+        loop {
+            if !inner.has_notified() {
+                return count;
+            }
+
+            // Set up an event listener.
+            listener!(inner.changed => listener);
+
+            // Check again now that we're listening.
+            if !inner.has_notified() {
+                return count;
+            }
+
+            // Wait until the list changes.
+            listener.wait();
+        }
+    }
+
+    /// Waits until at least `n` listeners are registered.
+    ///
+    /// This lets the notifying side make sure that waiters are in place before it fires a
+    /// notification, as benchmarks, barriers and tests often need to. Every listener counts,
+    /// whether or not it has been notified, as long as it hasn't completed or been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Arc::new(Event::new());
+    ///
+    /// let waiter = thread::spawn({
+    ///     let event = event.clone();
+    ///     move || event.listen().wait()
+    /// });
+    ///
+    /// // Make sure the notification doesn't fire before the waiter is listening.
+    /// event.wait_for_listeners(1).await;
+    /// event.notify(1);
+    /// # waiter.join().unwrap();
+    /// # });
+    /// ```
+    pub async fn wait_for_listeners(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        let inner = unsafe { &*self.inner() };
+
+        loop {
+            if inner.listeners.load(Ordering::Acquire) >= n {
+                return;
+            }
+
+            // Set up an event listener.
+            listener!(inner.changed => listener);
+
+            // Check again now that we're listening.
+            if inner.listeners.load(Ordering::Acquire) >= n {
+                return;
+            }
+
+            // Wait until the list changes.
+            listener.await;
+        }
+    }
+
+    /// Blocks until at least `n` listeners are registered.
+    ///
+    /// This is the blocking version of [`Event::wait_for_listeners()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let event = Arc::new(Event::new());
+    ///
+    /// let waiters: Vec<_> = (0..3)
+    ///     .map(|_| {
+    ///         let event = event.clone();
+    ///         thread::spawn(move || event.listen().wait())
+    ///     })
+    ///     .collect();
+    ///
+    /// event.wait_for_listeners_blocking(3);
+    /// assert_eq!(event.notify(usize::MAX), 3);
+    /// # for waiter in waiters { waiter.join().unwrap(); }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_for_listeners_blocking(&self, n: usize) {
+        use crate::Listener;
+
+        if n == 0 {
+            return;
+        }
+
+        let inner = unsafe { &*self.inner() };
+
+        loop {
+            if inner.listeners.load(Ordering::Acquire) >= n {
+                return;
+            }
+
+            // Set up an event listener.
+            listener!(inner.changed => listener);
+
+            // Check again now that we're listening.
+            if inner.listeners.load(Ordering::Acquire) >= n {
+                return;
+            }
+
+            // Wait until the list changes.
+            listener.wait();
+        }
+    }
+}
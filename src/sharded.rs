@@ -0,0 +1,497 @@
+//! An event whose listeners are spread across several lists.
+
+use crate::notify::{GenericNotify, Internal, Notification, NotificationPrivate};
+use crate::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use crate::sync::Arc;
+#[cfg(not(loom))]
+use crate::sync::WithMut;
+use crate::{Inner, InnerListener, IntoNotification, State};
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::pin::Pin;
+use core::ptr;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// An event whose listeners are spread across several internal lists.
+///
+/// Every [`Event`] keeps its listeners in a single list behind a single lock, so many threads
+/// calling [`Event::notify()`] at once end up waiting for each other. A `ShardedEvent` splits its
+/// listeners into a fixed number of shards, each with its own list and lock. Listeners are handed
+/// to the shards in turn as they are registered, and each notification starts with a different
+/// shard, so concurrent notifications mostly take different locks.
+///
+/// Notifications behave as they do on an [`Event`], counted across all shards: `notify(n)` makes
+/// sure that at least `n` listeners are notified in total, and an [`additional`] notification
+/// notifies `n` more. A notified listener that is dropped passes its notification on to a
+/// listener in any shard. What is lost is the order: listeners in different shards aren't
+/// notified in the order they started listening.
+///
+/// [`Event`]: crate::Event
+/// [`Event::notify()`]: crate::Event::notify
+/// [`additional`]: IntoNotification::additional
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::ShardedEvent;
+///
+/// let event = ShardedEvent::new(4);
+///
+/// let listeners: Vec<_> = (0..8).map(|_| event.listen()).collect();
+///
+/// // Listeners from any shard count towards the notification.
+/// assert_eq!(event.notify(3), 3);
+/// assert_eq!(event.notify(3), 0);
+/// ```
+pub struct ShardedEvent<T = ()> {
+    /// The number of shards.
+    shards: usize,
+
+    /// A pointer to the shards, or null if they haven't been allocated yet.
+    inner: AtomicPtr<Shards<T>>,
+}
+
+unsafe impl<T: Send> Send for ShardedEvent<T> {}
+unsafe impl<T: Send> Sync for ShardedEvent<T> {}
+
+impl<T> core::panic::UnwindSafe for ShardedEvent<T> {}
+impl<T> core::panic::RefUnwindSafe for ShardedEvent<T> {}
+
+/// The shards of a [`ShardedEvent`].
+struct Shards<T> {
+    /// The listener lists.
+    lists: Box<[Inner<T>]>,
+
+    /// The number of listeners registered so far, which picks the shard of the next one.
+    next_listen: AtomicUsize,
+
+    /// The number of notifications sent so far, which picks the first shard of the next one.
+    next_notify: AtomicUsize,
+}
+
+impl<T> Shards<T> {
+    fn new(shards: usize) -> Self {
+        Self {
+            lists: (0..shards).map(|_| Inner::new()).collect::<Vec<_>>().into(),
+            next_listen: AtomicUsize::new(0),
+            next_notify: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the total number of listeners.
+    fn listeners(&self) -> usize {
+        self.lists
+            .iter()
+            .map(|list| list.listeners.load(Ordering::Acquire))
+            .sum()
+    }
+
+    /// Returns the total number of notified listeners that haven't received the notification.
+    fn notified(&self) -> usize {
+        self.lists
+            .iter()
+            .map(|list| {
+                let listeners = list.listeners.load(Ordering::Acquire);
+
+                // `usize::MAX` means that every listener is notified.
+                match list.notified.load(Ordering::Acquire) {
+                    usize::MAX => listeners,
+                    notified => notified.min(listeners),
+                }
+            })
+            .sum()
+    }
+
+    /// Notifies listeners across the shards.
+    fn notify(&self, mut notify: impl Notification<Tag = T>) -> usize {
+        let count = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if notify.is_coalesced(Internal::new()) && self.notified() > 0 {
+            // A notification is already pending, so merge this one into it.
+            return 0;
+        }
+
+        // Listeners that are already notified count towards a notification that isn't additional.
+        let mut remaining = if is_additional {
+            count
+        } else {
+            count.saturating_sub(self.notified())
+        };
+
+        let start = self.next_notify.fetch_add(1, Ordering::Relaxed);
+        let mut notified = 0;
+
+        for i in 0..self.lists.len() {
+            if remaining == 0 {
+                break;
+            }
+
+            // Don't bother locking a shard with nobody in it.
+            let list = &self.lists[start.wrapping_add(i) % self.lists.len()];
+            if list.listeners.load(Ordering::Acquire) == 0 {
+                continue;
+            }
+
+            let n = list.notify(GenericNotify::new(remaining, true, || {
+                notify.next_tag(Internal::new())
+            }));
+            remaining -= n.min(remaining);
+            notified += n;
+        }
+
+        if notified == 0 && remaining > 0 && notify.is_sticky(Internal::new()) {
+            // Leave the permit for the shard that the next listener will be registered with.
+            let next = self.next_listen.load(Ordering::Relaxed) % self.lists.len();
+            self.lists[next].notify(
+                GenericNotify::new(1, true, || notify.next_tag(Internal::new())).set_sticky(true),
+            );
+        }
+
+        notified
+    }
+}
+
+/// A reference to one of the shards of a [`ShardedEvent`].
+pub(crate) struct ShardRef<T> {
+    shards: Arc<Shards<T>>,
+    index: usize,
+}
+
+impl<T> Deref for ShardRef<T> {
+    type Target = Inner<T>;
+
+    #[inline]
+    fn deref(&self) -> &Inner<T> {
+        &self.shards.lists[self.index]
+    }
+}
+
+impl<T> Borrow<Inner<T>> for ShardRef<T> {
+    #[inline]
+    fn borrow(&self) -> &Inner<T> {
+        self
+    }
+}
+
+impl<T> fmt::Debug for ShardedEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedEvent")
+            .field("shards", &self.shards)
+            .field("listeners", &self.total_listeners())
+            .finish()
+    }
+}
+
+impl ShardedEvent {
+    /// Creates a new [`ShardedEvent`] with the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::new(8);
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new(shards: usize) -> Self {
+        assert!(shards > 0, "a `ShardedEvent` needs at least one shard");
+
+        Self {
+            shards,
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`ShardedEvent`] with the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::new(8);
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new(shards: usize) -> Self {
+        assert!(shards > 0, "a `ShardedEvent` needs at least one shard");
+
+        Self {
+            shards,
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+impl<T> ShardedEvent<T> {
+    /// Creates a new [`ShardedEvent`] with a tag type and the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::<usize>::with_tag(8);
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn with_tag(shards: usize) -> Self {
+        assert!(shards > 0, "a `ShardedEvent` needs at least one shard");
+
+        Self {
+            shards,
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates a new [`ShardedEvent`] with a tag type and the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::<usize>::with_tag(8);
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn with_tag(shards: usize) -> Self {
+        assert!(shards > 0, "a `ShardedEvent` needs at least one shard");
+
+        Self {
+            shards,
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Returns the number of shards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::new(8);
+    /// assert_eq!(event.shards(), 8);
+    /// ```
+    #[inline]
+    pub fn shards(&self) -> usize {
+        self.shards
+    }
+
+    /// Returns a guard listening for a notification.
+    ///
+    /// The listener is registered with the next shard in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::new(2);
+    /// let listener = event.listen();
+    /// ```
+    pub fn listen(&self) -> ShardedListener<T> {
+        let shards = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+        let index = shards.next_listen.fetch_add(1, Ordering::Relaxed) % self.shards;
+
+        let mut listener = Box::pin(InnerListener {
+            event: ShardRef {
+                shards: Arc::clone(&shards),
+                index,
+            },
+            listener: None,
+        });
+        listener.as_mut().listen();
+
+        ShardedListener { listener }
+    }
+
+    /// Notifies a number of listeners across all shards.
+    ///
+    /// This works like [`Event::notify()`], with listeners in every shard counting towards the
+    /// notification. Returns the number of listeners that were notified.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, ShardedEvent};
+    ///
+    /// let event = ShardedEvent::new(2);
+    /// let listeners: Vec<_> = (0..4).map(|_| event.listen()).collect();
+    ///
+    /// assert_eq!(event.notify(2), 2);
+    ///
+    /// // Two listeners are notified already, so two more are notified.
+    /// assert_eq!(event.notify(2.additional()), 2);
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let mut notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(Internal::new());
+
+        notify.before_notify(Internal::new());
+        let count = match self.try_inner() {
+            Some(shards) => shards.notify(&mut notify),
+            None if notify.is_sticky(Internal::new()) => {
+                unsafe { &*self.inner() }.notify(&mut notify)
+            }
+            None => 0,
+        };
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Returns the total number of listeners across all shards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ShardedEvent;
+    ///
+    /// let event = ShardedEvent::new(2);
+    /// assert_eq!(event.total_listeners(), 0);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// assert_eq!(event.total_listeners(), 2);
+    /// ```
+    #[inline]
+    pub fn total_listeners(&self) -> usize {
+        self.try_inner().map_or(0, Shards::listeners)
+    }
+
+    /// Returns a reference to the shards, if they have been allocated.
+    #[inline]
+    fn try_inner(&self) -> Option<&Shards<T>> {
+        let inner = self.inner.load(Ordering::Acquire);
+        unsafe { inner.as_ref() }
+    }
+
+    /// Returns a raw, initialized pointer to the shards, allocating them on first use.
+    ///
+    /// This returns a raw pointer instead of reference because `from_raw` requires raw/mut
+    /// provenance.
+    fn inner(&self) -> *const Shards<T> {
+        let mut inner = self.inner.load(Ordering::Acquire);
+
+        // If this is the first use, initialize the state.
+        if inner.is_null() {
+            let new = Arc::into_raw(Arc::new(Shards::<T>::new(self.shards))) as *mut Shards<T>;
+
+            // Replace the null pointer with the new state pointer.
+            inner = self
+                .inner
+                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
+                .unwrap_or_else(|x| x);
+
+            if inner.is_null() {
+                inner = new;
+            } else {
+                // A concurrent operation has initialized the state, deallocate ours.
+                unsafe {
+                    drop(Arc::from_raw(new));
+                }
+            }
+        }
+
+        inner
+    }
+}
+
+impl<T> Drop for ShardedEvent<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.with_mut(|&mut inner| {
+            // If the state pointer has been initialized, drop it.
+            if !inner.is_null() {
+                unsafe {
+                    drop(Arc::from_raw(inner));
+                }
+            }
+        })
+    }
+}
+
+/// A guard waiting for a notification from a [`ShardedEvent`].
+///
+/// This is created by [`ShardedEvent::listen()`]. See the [`Listener`] trait for the
+/// functionality exposed by this type. A listener that is dropped after being notified passes
+/// the notification on to a listener in any shard.
+///
+/// [`Listener`]: crate::Listener
+pub struct ShardedListener<T = ()> {
+    listener: Pin<Box<InnerListener<T, ShardRef<T>>>>,
+}
+
+unsafe impl<T: Send> Send for ShardedListener<T> {}
+unsafe impl<T: Send> Sync for ShardedListener<T> {}
+
+impl<T> core::panic::UnwindSafe for ShardedListener<T> {}
+impl<T> core::panic::RefUnwindSafe for ShardedListener<T> {}
+impl<T> Unpin for ShardedListener<T> {}
+
+impl<T> fmt::Debug for ShardedListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedListener")
+            .field("shard", &self.listener.event.index)
+            .field("state", &format_args!("{}", self.listener.state_name()))
+            .finish()
+    }
+}
+
+impl<T> ShardedListener<T> {
+    #[inline]
+    pub(crate) fn listener(&self) -> &InnerListener<T, ShardRef<T>> {
+        &self.listener
+    }
+
+    #[inline]
+    pub(crate) fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, ShardRef<T>>> {
+        self.listener.as_mut()
+    }
+}
+
+impl<T> Drop for ShardedListener<T> {
+    fn drop(&mut self) {
+        // Take the listener out of its shard here, so that a notification it never received can
+        // be passed on to any shard instead of only its own.
+        let this = self.listener.as_mut().project();
+        if let Some(State::Notified { additional, tag }) =
+            (**this.event).remove(this.listener, false)
+        {
+            let mut tag = Some(tag);
+            this.event
+                .shards
+                .notify(GenericNotify::new(1, additional, || {
+                    tag.take().expect("tag already taken")
+                }));
+        }
+    }
+}
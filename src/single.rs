@@ -0,0 +1,555 @@
+//! A variant of [`Event`] for at most one listener at a time.
+//!
+//! [`Event`]: crate::Event
+
+use crate::notify::{Internal, NotificationPrivate};
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::{CapacityError, IntoNotification, Task, TaskRef};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::{Parker, Unparker};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{Duration, Instant};
+
+/// A listener exists.
+const LISTENING: usize = 1 << 0;
+
+/// The listener was notified, or a sticky notification is waiting for the next listener.
+const NOTIFIED: usize = 1 << 1;
+
+/// The listener is accessing the task slot.
+const REGISTERING: usize = 1 << 2;
+
+/// A notifier is taking the task out of the slot to wake it up.
+const WAKING: usize = 1 << 3;
+
+/// A synchronization primitive for notifying a single async task or thread.
+///
+/// This works like [`Event`], but only one listener may exist at a time, so the listener's task
+/// is kept in a single slot instead of a list. There is no lock and no allocation for the
+/// listener, which makes this a good fit for oneshot-style handoffs, where exactly one party
+/// waits for another. While a listener exists, [`SingleEvent::listen()`] returns an error.
+///
+/// The constructor is `const`, so a [`SingleEvent`] can be stored in a `static`. Listeners borrow
+/// the event instead of sharing ownership of it. Notifications don't carry tags.
+///
+/// [`Event`]: crate::Event
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::SingleEvent;
+/// # futures_lite::future::block_on(async {
+///
+/// static EVENT: SingleEvent = SingleEvent::new();
+///
+/// let listener = EVENT.listen().unwrap();
+/// EVENT.notify(1);
+/// listener.await;
+/// # });
+/// ```
+pub struct SingleEvent {
+    /// The state bits.
+    state: AtomicUsize,
+
+    /// The task of the listener, only accessed by whoever set `REGISTERING` or `WAKING`.
+    task: UnsafeCell<Option<Task>>,
+}
+
+unsafe impl Send for SingleEvent {}
+unsafe impl Sync for SingleEvent {}
+
+impl core::panic::UnwindSafe for SingleEvent {}
+impl core::panic::RefUnwindSafe for SingleEvent {}
+
+impl fmt::Debug for SingleEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleEvent")
+            .field("listening", &(self.listener_count() > 0))
+            .field("notified", &self.is_notified())
+            .finish()
+    }
+}
+
+impl Default for SingleEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SingleEvent {
+    /// Creates a new [`SingleEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// static EVENT: SingleEvent = SingleEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            task: UnsafeCell::new(None),
+        }
+    }
+
+    /// Creates a new [`SingleEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// static EVENT: SingleEvent = SingleEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            task: UnsafeCell::new(None),
+        }
+    }
+
+    /// Returns a guard listening for a notification, or an error if a listener already exists.
+    ///
+    /// If a [`sticky`] notification found no listener, the new listener is notified right away.
+    ///
+    /// [`sticky`]: IntoNotification::sticky
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{CapacityError, SingleEvent};
+    ///
+    /// let event = SingleEvent::new();
+    ///
+    /// let listener = event.listen().unwrap();
+    /// assert_eq!(event.listen().unwrap_err(), CapacityError);
+    ///
+    /// // Dropping the listener makes room for another one.
+    /// drop(listener);
+    /// assert!(event.listen().is_ok());
+    /// ```
+    pub fn listen(&self) -> Result<SingleListener<'_>, CapacityError> {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            if state & LISTENING != 0 {
+                return Err(CapacityError);
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state | LISTENING,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    return Ok(SingleListener {
+                        event: self,
+                        listening: true,
+                    })
+                }
+                Err(actual) => state = actual,
+            }
+        }
+    }
+
+    /// Notifies the listener, if there is one.
+    ///
+    /// This accepts the same kinds of notifications as [`Event::notify()`]. A notification with
+    /// a count of zero does nothing. Otherwise, the listener is notified unless it already is,
+    /// and `1` is returned if it was. If there is no listener, a [`sticky`] notification is kept
+    /// for the next one, and `0` is returned.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    /// [`sticky`]: IntoNotification::sticky
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// assert_eq!(event.notify(1), 1);
+    /// assert_eq!(event.notify(1), 0);
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification<Tag = ()>) -> usize {
+        let mut notify = notify.into_notification();
+        notify.fence(Internal::new());
+
+        notify.before_notify(Internal::new());
+        let count = self.notify_listener(&notify);
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Notifies the listener, and returns `1` if it was notified.
+    fn notify_listener(&self, notify: &impl NotificationPrivate<Tag = ()>) -> usize {
+        if notify.count(Internal::new()) == 0 {
+            return 0;
+        }
+        let sticky = notify.is_sticky(Internal::new());
+
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            let new = if state & NOTIFIED != 0 {
+                // The listener or the permit is notified already.
+                return 0;
+            } else if state & LISTENING != 0 {
+                state | NOTIFIED | WAKING
+            } else if sticky {
+                // Leave a permit for the next listener.
+                state | NOTIFIED
+            } else {
+                return 0;
+            };
+
+            match self
+                .state
+                .compare_exchange_weak(state, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break,
+                Err(actual) => state = actual,
+            }
+        }
+
+        if state & LISTENING == 0 {
+            return 0;
+        }
+
+        // If the listener is registering a task, it wakes itself up once it is done.
+        if state & REGISTERING == 0 {
+            // SAFETY: We set `WAKING`, so nobody else accesses the slot.
+            let task = unsafe { (*self.task.get()).take() };
+            self.state.fetch_and(!WAKING, Ordering::Release);
+
+            if let Some(task) = task {
+                task.wake();
+            }
+        }
+
+        1
+    }
+
+    /// Tell whether the listener is notified, or a sticky notification is waiting for the next
+    /// listener.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    /// assert!(!event.is_notified());
+    ///
+    /// event.notify(1);
+    /// assert!(event.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.state.load(Ordering::Acquire) & NOTIFIED != 0
+    }
+
+    /// Returns the number of listeners, which is either zero or one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.state.load(Ordering::Acquire) & LISTENING
+    }
+
+    /// Puts `task` into the slot, and returns `true` if the listener is notified.
+    ///
+    /// Only the listener may call this.
+    fn register(&self, task: TaskRef<'_>) -> bool {
+        // Take the slot, unless a notifier is taking the old task out of it.
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            if state & NOTIFIED != 0 {
+                return true;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state | REGISTERING,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(actual) => state = actual,
+            }
+        }
+
+        // SAFETY: We set `REGISTERING`, so nobody else accesses the slot.
+        let slot = unsafe { &mut *self.task.get() };
+        match slot {
+            Some(old) if old.as_task_ref().will_wake(task) => {}
+            _ => *slot = Some(task.into_task()),
+        }
+
+        // Give the slot back. A notifier that came in meanwhile left it to us to wake up.
+        let state = self
+            .state
+            .fetch_and(!(REGISTERING | WAKING), Ordering::AcqRel);
+        state & NOTIFIED != 0
+    }
+
+    /// Removes the listener and returns `true` if it was notified.
+    ///
+    /// Only the listener may call this.
+    fn release(&self) -> bool {
+        // Take the slot, waiting for a notifier that is taking the task out of it.
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            if state & WAKING != 0 {
+                crate::sync::spin_loop();
+                state = self.state.load(Ordering::Acquire);
+                continue;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state | REGISTERING,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(actual) => state = actual,
+            }
+        }
+
+        // SAFETY: We set `REGISTERING`, so nobody else accesses the slot.
+        drop(unsafe { (*self.task.get()).take() });
+
+        // Without a listener, notifiers leave the state alone, except for sticky notifications,
+        // which only come once `LISTENING` is cleared.
+        self.state.swap(0, Ordering::AcqRel) & NOTIFIED != 0
+    }
+}
+
+/// A guard waiting for a notification from a [`SingleEvent`].
+///
+/// This is created by [`SingleEvent::listen()`]. Dropping it makes room for another listener and
+/// discards its notification, if any.
+pub struct SingleListener<'a> {
+    /// The event this listener belongs to.
+    event: &'a SingleEvent,
+
+    /// Whether this listener still holds its place, or has received its notification.
+    listening: bool,
+}
+
+impl fmt::Debug for SingleListener<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleListener")
+            .field("listening", &self.listening)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SingleListener<'_> {
+    /// Drops this listener and discards its notification (if any).
+    ///
+    /// Returns `true` if a notification was discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    ///
+    /// let listener = event.listen().unwrap();
+    /// event.notify(1);
+    /// assert!(listener.discard());
+    ///
+    /// let listener = event.listen().unwrap();
+    /// assert!(!listener.discard());
+    /// ```
+    pub fn discard(mut self) -> bool {
+        self.release()
+    }
+
+    /// Returns `true` if this listener listens to the given [`SingleEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// assert!(listener.listens_to(&event));
+    /// assert!(!listener.listens_to(&SingleEvent::new()));
+    /// ```
+    #[inline]
+    pub fn listens_to(&self, event: &SingleEvent) -> bool {
+        core::ptr::eq(self.event, event)
+    }
+
+    /// Blocks until a notification is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// event.notify(1);
+    /// listener.wait();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(mut self) {
+        self.wait_internal(None);
+    }
+
+    /// Blocks until a notification is received or a timeout is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// assert!(!listener.wait_timeout(Duration::from_millis(10)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(self, timeout: Duration) -> bool {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_deadline(deadline),
+            None => {
+                self.wait();
+                true
+            }
+        }
+    }
+
+    /// Blocks until a notification is received or a deadline is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::SingleEvent;
+    ///
+    /// let event = SingleEvent::new();
+    /// let listener = event.listen().unwrap();
+    ///
+    /// event.notify(1);
+    /// assert!(listener.wait_deadline(Instant::now() + Duration::from_millis(10)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_deadline(mut self, deadline: Instant) -> bool {
+        self.wait_internal(Some(deadline))
+    }
+
+    /// Parks the thread until a notification is received or the deadline is reached.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_internal(&mut self, deadline: Option<Instant>) -> bool {
+        crate::sync::thread_local! {
+            /// Cached thread-local parker/unparker pair.
+            static PARKER: (Parker, Unparker) = crate::pair();
+        }
+
+        // Try to borrow the thread-local parker/unparker pair.
+        PARKER
+            .try_with(|(parker, unparker)| self.wait_with_parker(deadline, parker, unparker))
+            .unwrap_or_else(|_| {
+                // If the pair isn't accessible, we may be being called in a destructor.
+                let (parker, unparker) = crate::pair();
+                self.wait_with_parker(deadline, &parker, &unparker)
+            })
+    }
+
+    /// Parks the thread with the given parker until a notification is received or the deadline
+    /// is reached.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_with_parker(
+        &mut self,
+        deadline: Option<Instant>,
+        parker: &Parker,
+        unparker: &Unparker,
+    ) -> bool {
+        while !self.event.register(TaskRef::Unparker(unparker)) {
+            match deadline {
+                None => parker.park(),
+
+                #[cfg(loom)]
+                Some(_deadline) => {
+                    panic!("parking does not support timeouts under loom");
+                }
+
+                #[cfg(not(loom))]
+                Some(deadline) => {
+                    if Instant::now() >= deadline {
+                        return self.release();
+                    }
+                    parker.park_deadline(deadline);
+                }
+            }
+        }
+
+        self.release()
+    }
+
+    /// Gives up our place and returns `true` if we were notified.
+    fn release(&mut self) -> bool {
+        self.listening && {
+            self.listening = false;
+            self.event.release()
+        }
+    }
+}
+
+impl Future for SingleListener<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        assert!(self.listening, "SingleListener was polled after completion");
+
+        if self.event.register(TaskRef::Waker(cx.waker())) {
+            self.release();
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for SingleListener<'_> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
@@ -0,0 +1,165 @@
+//! Point-in-time views of an event's listener list, for diagnostics.
+
+use crate::sync::cell::Cell;
+use crate::{Event, State, Task};
+
+use alloc::vec::Vec;
+
+/// A point-in-time view of the listeners of an [`Event`].
+///
+/// This is created by [`Event::snapshot()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The state of every listener in the list, from the oldest to the newest.
+    pub(crate) listeners: Vec<ListenerSnapshot>,
+
+    /// The number of notified listeners.
+    pub(crate) notified: usize,
+
+    /// The number of operations waiting in the backup queue.
+    pub(crate) queued: usize,
+}
+
+impl Snapshot {
+    /// Returns the state of every listener in the list, from the oldest to the newest.
+    ///
+    /// Listeners are notified in this order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerSnapshot};
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// event.notify(1);
+    ///
+    /// assert_eq!(
+    ///     event.snapshot().listeners(),
+    ///     &[
+    ///         ListenerSnapshot::Notified { additional: false },
+    ///         ListenerSnapshot::Created,
+    ///     ]
+    /// );
+    /// ```
+    #[inline]
+    pub fn listeners(&self) -> &[ListenerSnapshot] {
+        &self.listeners
+    }
+
+    /// Returns the number of notified listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// event.notify(1);
+    ///
+    /// assert_eq!(event.snapshot().notified(), 1);
+    /// ```
+    #[inline]
+    pub fn notified(&self) -> usize {
+        self.notified
+    }
+
+    /// Returns the number of operations waiting in the backup queue.
+    ///
+    /// Without the `std` feature, listeners that cannot take the lock on the list queue their
+    /// insertions, removals and notifications instead. A queue that keeps growing points to a
+    /// lock that is held for too long. With the `std` feature, this is always zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// assert_eq!(event.snapshot().queued(), 0);
+    /// ```
+    #[inline]
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+}
+
+/// The state of a single listener in a [`Snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ListenerSnapshot {
+    /// The listener is in the list, but nothing has waited on it yet.
+    Created,
+
+    /// A task polled the listener and is waiting to be woken through its waker.
+    Task,
+
+    /// A thread is blocked waiting on the listener.
+    Thread,
+
+    /// The listener has been notified, but has not observed the notification yet.
+    Notified {
+        /// Whether this was an additional notification.
+        additional: bool,
+    },
+
+    /// The event was closed before the listener was notified.
+    Closed,
+}
+
+impl ListenerSnapshot {
+    /// Reads the state of a listener out of its cell.
+    ///
+    /// The list must be locked.
+    pub(crate) fn read<T>(state: &Cell<State<T>>) -> Self {
+        let taken = state.replace(State::NotifiedTaken);
+
+        let snapshot = match &taken {
+            State::Created => Self::Created,
+            State::Task(Task::Waker(_)) => Self::Task,
+            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+            State::Task(Task::Unparker(_)) => Self::Thread,
+            State::Notified { additional, .. } => Self::Notified {
+                additional: *additional,
+            },
+            State::NotifiedTaken => Self::Notified { additional: false },
+            State::Closed(_) => Self::Closed,
+        };
+
+        state.set(taken);
+        snapshot
+    }
+}
+
+impl<T> Event<T> {
+    /// Takes a snapshot of the listener list.
+    ///
+    /// The snapshot records the state of every listener, which makes it useful in diagnostics
+    /// dumps when looking for tasks or threads that wait for a notification that never comes.
+    /// Taking a snapshot locks the list, so it should not be done on hot paths.
+    ///
+    /// This method is available with the `snapshot` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerSnapshot};
+    ///
+    /// let event = Event::new();
+    /// assert!(event.snapshot().listeners().is_empty());
+    ///
+    /// let listener = event.listen();
+    /// assert_eq!(event.snapshot().listeners(), &[ListenerSnapshot::Created]);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        match self.try_inner() {
+            Some(inner) => inner.snapshot(),
+            None => Snapshot::default(),
+        }
+    }
+}
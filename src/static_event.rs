@@ -0,0 +1,676 @@
+//! A variant of [`Event`] that stores its state inline and never allocates.
+//!
+//! [`Event`]: crate::Event
+
+use crate::notify::{GenericNotify, Internal, NotificationPrivate};
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::IntoNotification;
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::ops;
+use core::pin::Pin;
+use core::ptr::NonNull;
+use core::task::{Context, Poll, Waker};
+
+/// A synchronization primitive for notifying async tasks that can live in a `static`.
+///
+/// [`Event::new()`] is `const` as well, but the shared state of an [`Event`] is allocated on the
+/// heap the first time it is used. A [`StaticEvent`] stores that state inline instead, and its
+/// listeners are linked into an intrusive list, so neither the event nor its listeners ever touch
+/// the heap. There is no limit on the number of listeners.
+///
+/// In exchange, listeners borrow the event and have to be pinned before they can listen, like
+/// [`EventListenerRef`]. If pinning is inconvenient and the number of listeners is known up
+/// front, consider [`FixedEvent`] instead.
+///
+/// The list is protected by a spin lock. There are no blocking methods.
+///
+/// [`Event`]: crate::Event
+/// [`Event::new()`]: crate::Event::new
+/// [`EventListenerRef`]: crate::EventListenerRef
+/// [`FixedEvent`]: crate::FixedEvent
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::StaticEvent;
+/// # futures_lite::future::block_on(async {
+///
+/// static EVENT: StaticEvent = StaticEvent::new();
+///
+/// let listener = EVENT.listen();
+/// futures_lite::pin!(listener);
+/// listener.as_mut().listen();
+///
+/// EVENT.notify(1);
+/// listener.await;
+/// # });
+/// ```
+pub struct StaticEvent<T = ()> {
+    /// Whether the list is locked.
+    locked: AtomicBool,
+
+    /// The list of listeners.
+    list: UnsafeCell<List<T>>,
+}
+
+unsafe impl<T: Send> Send for StaticEvent<T> {}
+unsafe impl<T: Send> Sync for StaticEvent<T> {}
+
+impl<T> core::panic::UnwindSafe for StaticEvent<T> {}
+impl<T> core::panic::RefUnwindSafe for StaticEvent<T> {}
+
+impl<T> fmt::Debug for StaticEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = self.lock();
+        f.debug_struct("StaticEvent")
+            .field("listeners_notified", &list.notified)
+            .field("listeners_total", &list.len)
+            .finish()
+    }
+}
+
+impl Default for StaticEvent {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StaticEvent {
+    /// Creates a new [`StaticEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// static EVENT: StaticEvent = StaticEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self::with_tag()
+    }
+
+    /// Creates a new [`StaticEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// static EVENT: StaticEvent = StaticEvent::new();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self::with_tag()
+    }
+}
+
+impl<T> StaticEvent<T> {
+    /// Creates a new [`StaticEvent`] with a tag type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// static EVENT: StaticEvent<usize> = StaticEvent::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn with_tag() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            list: UnsafeCell::new(List::new()),
+        }
+    }
+
+    /// Creates a new [`StaticEvent`] with a tag type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// static EVENT: StaticEvent<usize> = StaticEvent::with_tag();
+    /// ```
+    #[inline]
+    #[cfg(loom)]
+    pub fn with_tag() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            list: UnsafeCell::new(List::new()),
+        }
+    }
+
+    /// Returns a listener for this event.
+    ///
+    /// The listener starts listening once [`StaticListener::listen()`] is called, or when it is
+    /// first polled, whichever comes first. To avoid missing notifications in the usual
+    /// check-listen-check loop, call `listen()` before the second check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    /// use futures_lite::pin;
+    ///
+    /// static EVENT: StaticEvent = StaticEvent::new();
+    ///
+    /// let listener = EVENT.listen();
+    /// pin!(listener);
+    /// listener.as_mut().listen();
+    ///
+    /// assert_eq!(EVENT.notify(1), 1);
+    /// ```
+    #[inline]
+    pub fn listen(&self) -> StaticListener<'_, T> {
+        StaticListener {
+            event: self,
+            link: UnsafeCell::new(Link {
+                state: State::Created,
+                prev: None,
+                next: None,
+            }),
+            listening: false,
+        }
+    }
+
+    /// Notifies a number of active listeners.
+    ///
+    /// This works like [`Event::notify()`] and accepts the same kinds of notifications.
+    ///
+    /// [`Event::notify()`]: crate::Event::notify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, StaticEvent};
+    /// use futures_lite::pin;
+    ///
+    /// let event = StaticEvent::new();
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// pin!(listener1, listener2);
+    /// listener1.as_mut().listen();
+    /// listener2.as_mut().listen();
+    ///
+    /// assert_eq!(event.notify(1), 1);
+    /// assert_eq!(event.notify(1), 0);
+    /// assert_eq!(event.notify(1.additional()), 1);
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification<Tag = T>) -> usize {
+        let mut notify = notify.into_notification();
+        notify.fence(Internal::new());
+        notify.before_notify(Internal::new());
+
+        let count = self.lock().notify(&mut notify);
+        notify.after_notify(count, Internal::new());
+        count
+    }
+
+    /// Tell whether any listeners are currently notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    /// use futures_lite::pin;
+    ///
+    /// let event = StaticEvent::new();
+    /// let listener = event.listen();
+    /// pin!(listener);
+    /// listener.as_mut().listen();
+    /// assert!(!event.is_notified());
+    ///
+    /// event.notify(1);
+    /// assert!(event.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.lock().notified > 0
+    }
+
+    /// Returns the number of listeners currently registered to this [`StaticEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    /// use futures_lite::pin;
+    ///
+    /// let event = StaticEvent::new();
+    /// let listener = event.listen();
+    /// pin!(listener);
+    /// assert_eq!(event.listener_count(), 0);
+    ///
+    /// listener.as_mut().listen();
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    #[inline]
+    pub fn listener_count(&self) -> usize {
+        self.lock().len
+    }
+
+    /// Locks the list.
+    fn lock(&self) -> ListGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            crate::sync::spin_loop();
+        }
+
+        ListGuard { event: self }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A listener for a [`StaticEvent`].
+    ///
+    /// This is created by [`StaticEvent::listen()`]. It has to be pinned before it can listen.
+    ///
+    /// If a notified listener is dropped without receiving a notification, dropping will notify
+    /// another active listener.
+    #[project(!Unpin)]
+    pub struct StaticListener<'a, T = ()> {
+        // The event this listener belongs to.
+        event: &'a StaticEvent<T>,
+
+        // Our entry in the list. It is only accessed while the list is locked.
+        link: UnsafeCell<Link<T>>,
+
+        // Whether the entry is linked into the list.
+        listening: bool,
+    }
+
+    impl<'a, T> PinnedDrop for StaticListener<'a, T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            if mem::replace(this.listening, false) {
+                let link = link_ptr(this.link);
+                this.event.lock().remove(link, true);
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for StaticListener<'_, T> {}
+unsafe impl<T: Send> Sync for StaticListener<'_, T> {}
+
+impl<T> core::panic::UnwindSafe for StaticListener<'_, T> {}
+impl<T> core::panic::RefUnwindSafe for StaticListener<'_, T> {}
+
+impl<T> fmt::Debug for StaticListener<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticListener")
+            .field("listening", &self.listening)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> StaticListener<'_, T> {
+    /// Starts listening for notifications.
+    ///
+    /// Does nothing if the listener is already listening.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    /// use futures_lite::pin;
+    ///
+    /// let event = StaticEvent::new();
+    /// let listener = event.listen();
+    /// pin!(listener);
+    ///
+    /// listener.as_mut().listen();
+    /// assert!(listener.is_listening());
+    /// ```
+    pub fn listen(self: Pin<&mut Self>) {
+        let this = self.project();
+        if !mem::replace(this.listening, true) {
+            let link = link_ptr(this.link);
+
+            // SAFETY: The listener is pinned, so the link stays in place until it is removed
+            // from the list on drop.
+            unsafe { this.event.lock().insert(link) };
+        }
+    }
+
+    /// Tells whether the listener is currently registered to the event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// let event = StaticEvent::new();
+    /// let listener = event.listen();
+    /// assert!(!listener.is_listening());
+    /// ```
+    #[inline]
+    pub fn is_listening(&self) -> bool {
+        self.listening
+    }
+
+    /// Returns `true` if this listener listens to the given [`StaticEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::StaticEvent;
+    ///
+    /// let event = StaticEvent::new();
+    /// let listener = event.listen();
+    ///
+    /// assert!(listener.listens_to(&event));
+    /// assert!(!listener.listens_to(&StaticEvent::new()));
+    /// ```
+    #[inline]
+    pub fn listens_to(&self, event: &StaticEvent<T>) -> bool {
+        core::ptr::eq(self.event, event)
+    }
+}
+
+impl<T> Future for StaticListener<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.as_mut().listen();
+
+        let this = self.project();
+        let link = link_ptr(this.link);
+        let mut list = this.event.lock();
+
+        // SAFETY: The list is locked.
+        let state = unsafe { &mut (*link.as_ptr()).state };
+        match mem::replace(state, State::Created) {
+            State::Notified { tag, .. } => {
+                // We have been notified, remove the listener.
+                list.remove(link, false);
+                *this.listening = false;
+                Poll::Ready(tag)
+            }
+
+            State::Waiting(waker) if waker.will_wake(cx.waker()) => {
+                *state = State::Waiting(waker);
+                Poll::Pending
+            }
+
+            _ => {
+                *state = State::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Returns a pointer to the entry of a listener.
+fn link_ptr<T>(link: &UnsafeCell<Link<T>>) -> NonNull<Link<T>> {
+    // SAFETY: `UnsafeCell::get` never returns a null pointer.
+    unsafe { NonNull::new_unchecked(link.get()) }
+}
+
+/// A locked [`StaticEvent`].
+struct ListGuard<'a, T> {
+    event: &'a StaticEvent<T>,
+}
+
+impl<T> ops::Deref for ListGuard<'_, T> {
+    type Target = List<T>;
+
+    fn deref(&self) -> &List<T> {
+        // SAFETY: We hold the lock.
+        unsafe { &*self.event.list.get() }
+    }
+}
+
+impl<T> ops::DerefMut for ListGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut List<T> {
+        // SAFETY: We hold the lock.
+        unsafe { &mut *self.event.list.get() }
+    }
+}
+
+impl<T> Drop for ListGuard<'_, T> {
+    fn drop(&mut self) {
+        self.event.locked.store(false, Ordering::Release);
+    }
+}
+
+/// The state of a listener.
+enum State<T> {
+    /// The listener was just created.
+    Created,
+
+    /// The listener has received a notification.
+    Notified {
+        /// Whether or not this is an "additional" notification.
+        additional: bool,
+
+        /// The tag associated with the notification.
+        tag: T,
+    },
+
+    /// A task is waiting for a notification.
+    Waiting(Waker),
+}
+
+impl<T> State<T> {
+    fn is_notified(&self) -> bool {
+        matches!(self, Self::Notified { .. })
+    }
+}
+
+/// An entry in the list, stored inside of a [`StaticListener`].
+struct Link<T> {
+    /// The state of the listener.
+    state: State<T>,
+
+    /// The previous listener in the list.
+    prev: Option<NonNull<Link<T>>>,
+
+    /// The next listener in the list.
+    next: Option<NonNull<Link<T>>>,
+}
+
+/// An intrusive linked list of listeners.
+struct List<T> {
+    /// First entry in the list.
+    head: Option<NonNull<Link<T>>>,
+
+    /// Last entry in the list.
+    tail: Option<NonNull<Link<T>>>,
+
+    /// The first unnotified entry in the list.
+    start: Option<NonNull<Link<T>>>,
+
+    /// The total number of listeners.
+    len: usize,
+
+    /// The number of notified listeners.
+    notified: usize,
+
+    /// A permit stored by a sticky notification, waiting for the next listener.
+    sticky: Option<T>,
+}
+
+impl<T> List<T> {
+    #[cfg(not(loom))]
+    const fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            start: None,
+            len: 0,
+            notified: 0,
+            sticky: None,
+        }
+    }
+
+    #[cfg(loom)]
+    fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            start: None,
+            len: 0,
+            notified: 0,
+            sticky: None,
+        }
+    }
+
+    /// Inserts an entry at the end of the list.
+    ///
+    /// # Safety
+    ///
+    /// The entry must stay in place until it is removed again.
+    unsafe fn insert(&mut self, link: NonNull<Link<T>>) {
+        // If a sticky notification left a permit behind, this listener takes it.
+        let state = match self.sticky.take() {
+            Some(tag) => State::Notified {
+                additional: false,
+                tag,
+            },
+            None => State::Created,
+        };
+        let notified = state.is_notified();
+
+        link.as_ptr().write(Link {
+            state,
+            prev: self.tail,
+            next: None,
+        });
+
+        // Replace the tail with the new entry.
+        match self.tail.replace(link) {
+            None => self.head = Some(link),
+            Some(tail) => (*tail.as_ptr()).next = Some(link),
+        }
+
+        if notified {
+            self.notified += 1;
+        } else if self.start.is_none() {
+            self.start = Some(link);
+        }
+
+        self.len += 1;
+    }
+
+    /// Removes an entry from the list and returns its state.
+    fn remove(&mut self, link: NonNull<Link<T>>, propagate: bool) -> State<T> {
+        // SAFETY: Entries stay in place while they are in the list, and the list is locked.
+        let (state, prev, next) = unsafe {
+            let entry = &mut *link.as_ptr();
+            (
+                mem::replace(&mut entry.state, State::Created),
+                entry.prev.take(),
+                entry.next.take(),
+            )
+        };
+
+        // Unlink from the previous entry.
+        match prev {
+            None => self.head = next,
+            Some(p) => unsafe { (*p.as_ptr()).next = next },
+        }
+
+        // Unlink from the next entry.
+        match next {
+            None => self.tail = prev,
+            Some(n) => unsafe { (*n.as_ptr()).prev = prev },
+        }
+
+        // If this was the first unnotified entry, move the pointer to the next one.
+        if self.start == Some(link) {
+            self.start = next;
+        }
+
+        self.len -= 1;
+
+        match state {
+            State::Notified { additional, tag } if propagate => {
+                self.notified -= 1;
+
+                // Propagate the notification to the next entry.
+                let mut tag = Some(tag);
+                self.notify(GenericNotify::new(1, additional, move || {
+                    tag.take().expect("tag already taken")
+                }));
+
+                State::Created
+            }
+
+            state => {
+                if state.is_notified() {
+                    self.notified -= 1;
+                }
+
+                state
+            }
+        }
+    }
+
+    /// Notifies a number of listeners.
+    fn notify(&mut self, mut notify: impl NotificationPrivate<Tag = T>) -> usize {
+        let mut n = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if self.start.is_none() {
+            // Nobody is waiting for a notification, so store a permit if requested.
+            if n > 0 && self.sticky.is_none() && notify.is_sticky(Internal::new()) {
+                self.sticky = Some(notify.next_tag(Internal::new()));
+            }
+
+            return 0;
+        }
+
+        if !is_additional {
+            if n <= self.notified {
+                return 0;
+            }
+            n -= self.notified;
+        }
+
+        let mut count = 0;
+        while count < n {
+            let link = match self.start {
+                Some(link) => link,
+                None => break,
+            };
+
+            // SAFETY: Entries stay in place while they are in the list, and the list is locked.
+            let entry = unsafe { &mut *link.as_ptr() };
+
+            // Move the pointer forwards.
+            self.start = entry.next;
+
+            // Set the state to `Notified` and wake the task.
+            let tag = notify.next_tag(Internal::new());
+            let old = mem::replace(
+                &mut entry.state,
+                State::Notified {
+                    additional: is_additional,
+                    tag,
+                },
+            );
+            if let State::Waiting(waker) = old {
+                waker.wake();
+            }
+
+            self.notified += 1;
+            count += 1;
+        }
+
+        count
+    }
+}
@@ -1,20 +1,33 @@
 //! libstd-based implementation of `event-listener`.
 //!
-//! This implementation crates an intrusive linked list of listeners.
+//! This implementation crates an intrusive linked list of listeners, protected by a mutex.
 
-use crate::notify::{GenericNotify, Internal, Notification};
-use crate::sync::atomic::Ordering;
+use crate::builder::Rng;
+use crate::notify::{GenericNotify, Internal, NothingProducer, Notification};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{ListenerSnapshot, Snapshot};
+use crate::sync::atomic::{self, AtomicUsize, Ordering};
 use crate::sync::cell::{Cell, UnsafeCell};
-use crate::sync::{Mutex, MutexGuard};
-use crate::{RegisterResult, State, TaskRef};
+use crate::sync::{Mutex, MutexGuard, TryLockError};
+use crate::{
+    AllocError, EventBuilder, RegisterResult, State, Task, TaskRef, TryListenError, WakeOrder,
+};
 
 use core::marker::PhantomPinned;
-use core::mem;
+use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 use core::ptr::NonNull;
 
-pub(super) struct List<T>(Mutex<Inner<T>>);
+use std::collections::VecDeque;
+
+pub(super) struct List<T> {
+    /// The list itself.
+    inner: Mutex<Inner<T>>,
+
+    /// The number of listeners admitted by `Event::try_listen_bounded()`.
+    listen_bound: usize,
+}
 
 struct Inner<T> {
     /// The head of the linked list.
@@ -31,72 +44,159 @@ struct Inner<T> {
 
     /// The number of notified listeners.
     notified: usize,
+
+    /// Permits stored by notifications that found nobody to notify, waiting for new listeners.
+    permits: VecDeque<T>,
+
+    /// The number of permits that are kept, or zero if only sticky notifications keep one.
+    permit_capacity: usize,
+
+    /// Makes the tags of closed listeners, once the event has been closed.
+    close_tag: Option<fn() -> T>,
+
+    /// The order in which entries are notified.
+    wake_order: WakeOrder,
+
+    /// Picks entries for `WakeOrder::Random`.
+    rng: Rng,
+
+    /// Tasks to wake up once the list is unlocked.
+    tasks: Vec<Task>,
 }
 
 impl<T> List<T> {
     /// Create a new, empty event listener list.
-    pub(super) fn new() -> Self {
-        Self(Mutex::new(Inner {
-            head: None,
-            tail: None,
-            next: None,
-            len: 0,
-            notified: 0,
-        }))
+    pub(super) fn new(config: &EventBuilder) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                head: None,
+                tail: None,
+                next: None,
+                len: 0,
+                notified: 0,
+                permits: VecDeque::new(),
+                permit_capacity: config.permit_capacity,
+                close_tag: None,
+                wake_order: config.wake_order,
+                rng: Rng::new(),
+                tasks: Vec::with_capacity(INLINE_WAKEUPS),
+            }),
+            listen_bound: config.listen_bound.unwrap_or(usize::MAX),
+        }
     }
 
-    /// Get the total number of listeners without blocking.
-    pub(crate) fn try_total_listeners(&self) -> Option<usize> {
-        self.0.try_lock().ok().map(|list| list.len)
+    /// Get the number of listeners admitted by `Event::try_listen_bounded()`.
+    #[inline]
+    pub(crate) fn listen_bound(&self) -> usize {
+        self.listen_bound
+    }
+
+    /// Get the total number of listeners and the number of notified listeners without blocking.
+    pub(crate) fn try_counts(&self) -> Option<(usize, usize)> {
+        self.inner
+            .try_lock()
+            .ok()
+            .map(|list| (list.len, list.notified))
+    }
+
+    /// Get the name of a listener's state without blocking.
+    pub(crate) fn try_state_name(&self, listener: Option<&Listener<T>>) -> Option<&'static str> {
+        let listener = match listener {
+            Some(listener) => listener,
+            None => return Some("Idle"),
+        };
+
+        let _list = self.inner.try_lock().ok()?;
+        let link = listener.link.get();
+
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let state = unsafe { &link.deref().state };
+        let taken = state.replace(State::NotifiedTaken);
+        let name = taken.name();
+        state.set(taken);
+        Some(name)
     }
 
     /// Get the total number of listeners with blocking.
     pub(crate) fn total_listeners(&self) -> usize {
-        self.0.lock().unwrap_or_else(|e| e.into_inner()).len
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).len
     }
 }
 
 impl<T> crate::Inner<T> {
-    fn lock(&self) -> ListLock<'_, '_, T> {
+    /// Free the memory held for stored permits.
+    ///
+    /// Listeners hold their own entries, so there is nothing else to free.
+    pub(crate) fn shrink_to_fit(&self) {
+        self.lock().permits.shrink_to_fit();
+    }
+
+    fn lock(&self) -> ListLock<'_, T> {
         ListLock {
             inner: self,
-            lock: self.list.0.lock().unwrap_or_else(|e| e.into_inner()),
+            lock: ManuallyDrop::new(self.list.inner.lock().unwrap_or_else(|e| e.into_inner())),
         }
     }
 
-    /// Add a new listener to the list.
-    pub(crate) fn insert(&self, mut listener: Pin<&mut Option<Listener<T>>>) {
-        let mut inner = self.lock();
+    /// Deliver notifications deferred by `Event::notify_from_isr()`, unless the list is locked.
+    ///
+    /// If it is, the lock holder delivers them once it is done. This never waits for the lock or
+    /// allocates. Listeners are notified a few at a time, so that their tasks can be woken from
+    /// the stack once the list is unlocked.
+    pub(crate) fn notify_pending(&self) {
+        loop {
+            // Don't go through `ListLock`, since unlocking it may allocate.
+            let mut list = match self.list.inner.try_lock() {
+                Ok(list) => list,
+                Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                Err(TryLockError::WouldBlock) => return,
+            };
 
-        listener.as_mut().set(Some(Listener {
-            link: UnsafeCell::new(Link {
-                state: Cell::new(State::Created),
-                prev: Cell::new(inner.tail),
-                next: Cell::new(None),
-            }),
-            _pin: PhantomPinned,
-        }));
-        let listener = listener.as_pin_mut().unwrap();
+            let notified = list.notify_pending_bounded(&self.pending);
+            self.store_counts(&list);
+            let wakeups = Wakeups::take(&mut list.tasks);
+            drop(list);
 
-        {
-            let entry_guard = listener.link.get();
-            // SAFETY: We are locked, so we can access the inner `link`.
-            let entry = unsafe { entry_guard.deref() };
+            wakeups.wake();
 
-            // Replace the tail with the new entry.
-            match mem::replace(&mut inner.tail, Some(entry.into())) {
-                None => inner.head = Some(entry.into()),
-                Some(t) => unsafe { t.as_ref().next.set(Some(entry.into())) },
-            };
-        }
+            // Whatever could not be delivered without allocating is left for the next lock holder.
+            if notified == 0 {
+                return;
+            }
 
-        // If there are no unnotified entries, this is the first one.
-        if inner.next.is_none() {
-            inner.next = inner.tail;
+            // Another interrupt handler may have failed to lock the list after we checked.
+            atomic::fence(Ordering::SeqCst);
+            if self.pending.load(Ordering::Relaxed) == 0 {
+                return;
+            }
         }
+    }
 
-        // Bump the entry count.
-        inner.len += 1;
+    /// Update the atomic counters from a locked list.
+    fn store_counts(&self, list: &Inner<T>) {
+        let notified = if list.notified < list.len {
+            list.notified
+        } else {
+            usize::MAX
+        };
+
+        self.notified.store(notified, Ordering::Release);
+        self.listeners.store(list.len, Ordering::Release);
+    }
+
+    /// Add a new listener to the list.
+    pub(crate) fn insert(&self, listener: Pin<&mut Option<Listener<T>>>) {
+        self.lock().insert(listener, 0, 0);
+    }
+
+    /// Add a new listener with the given metadata and priority to the list.
+    pub(crate) fn insert_with(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        meta: usize,
+        priority: usize,
+    ) {
+        self.lock().insert(listener, meta, priority);
     }
 
     /// Remove a listener from the list.
@@ -114,14 +214,130 @@ impl<T> crate::Inner<T> {
         self.lock().notify(notify)
     }
 
+    /// Notifies a number of entries whose metadata matches the predicate.
+    #[cold]
+    pub(crate) fn notify_if(
+        &self,
+        notify: impl Notification<Tag = T>,
+        pred: impl FnMut(usize) -> bool,
+    ) -> usize {
+        self.lock().notify_if(notify, pred)
+    }
+
+    /// Notifies a number of entries, starting with the most recently inserted one.
+    #[cold]
+    pub(crate) fn notify_last(&self, notify: impl Notification<Tag = T>) -> usize {
+        self.lock().notify_last(notify)
+    }
+
+    /// Performs several notifications in order, taking the lock only once.
+    #[cold]
+    pub(crate) fn notify_batch<N: Notification<Tag = T>>(
+        &self,
+        notifications: impl Iterator<Item = N>,
+    ) -> usize {
+        let mut list = self.lock();
+        notifications.map(|notify| list.notify(notify)).sum()
+    }
+
+    /// Notifies one entry per tag, until either the tags or the unnotified entries run out.
+    #[cold]
+    pub(crate) fn notify_with_tags(&self, tags: impl Iterator<Item = T>) -> usize {
+        self.lock().notify_with_tags(tags)
+    }
+
+    /// Notifies a number of entries and returns the exact number of notified entries.
+    ///
+    /// Notifications are never deferred here, so this is the same as `notify`.
+    #[inline]
+    pub(crate) fn notify_exact(&self, notify: impl Notification<Tag = T>) -> usize {
+        self.notify(notify)
+    }
+
+    /// Add a new listener to the list, reporting allocation failure.
+    ///
+    /// The list is intrusive, so this never allocates.
+    pub(crate) fn try_insert(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), AllocError> {
+        self.insert(listener);
+        Ok(())
+    }
+
+    /// Add a new listener to the list, unless it already has as many as it admits.
+    pub(crate) fn insert_bounded(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), TryListenError> {
+        let mut list = self.lock();
+        if list.len >= self.list.listen_bound {
+            return Err(TryListenError::Full);
+        }
+
+        list.insert(listener, 0, 0);
+        Ok(())
+    }
+
+    /// Add a new listener to the list, unless it already has as many as it admits, reporting
+    /// allocation failure.
+    ///
+    /// The list is intrusive, so this never allocates.
+    pub(crate) fn try_insert_bounded(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+    ) -> Result<(), TryListenError> {
+        self.insert_bounded(listener)
+    }
+
+    /// Closes the list, waking every listener that has not been notified yet.
+    #[cold]
+    pub(crate) fn close(&self, tag: fn() -> T) {
+        self.lock().close(tag);
+    }
+
+    /// Take a snapshot of the listener list.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        let list = self.lock();
+        let mut listeners = Vec::with_capacity(list.len);
+
+        let mut link = list.head;
+        while let Some(l) = link {
+            // SAFETY: We are locked, so every link in the list is valid.
+            let l = unsafe { l.as_ref() };
+            listeners.push(ListenerSnapshot::read(&l.state));
+            link = l.next.get();
+        }
+
+        Snapshot {
+            listeners,
+            notified: list.notified,
+            queued: 0,
+        }
+    }
+
     /// Register a task to be notified when the event is triggered.
     ///
     /// Returns `true` if the listener was already notified, and `false` otherwise. If the listener
     /// isn't inserted, returns `None`.
     pub(crate) fn register(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        task: TaskRef<'_>,
+    ) -> RegisterResult<T> {
+        self.register_with(listener, task, false)
+    }
+
+    /// Register a task to be notified when the event is triggered.
+    ///
+    /// If `rearm` is `true` and the listener was notified, it is moved to the back of the list
+    /// under the same lock instead of being removed, so it keeps receiving notifications.
+    pub(crate) fn register_with(
         &self,
         mut listener: Pin<&mut Option<Listener<T>>>,
         task: TaskRef<'_>,
+        rearm: bool,
     ) -> RegisterResult<T> {
         let mut inner = self.lock();
         let entry_guard = match listener.as_mut().as_pin_mut() {
@@ -135,7 +351,11 @@ impl<T> crate::Inner<T> {
         match entry.state.replace(State::NotifiedTaken) {
             State::Notified { tag, .. } => {
                 // We have been notified, remove the listener.
-                inner.remove(listener, false);
+                let (meta, priority) = (entry.meta, entry.priority);
+                inner.remove(listener.as_mut(), false);
+                if rearm {
+                    inner.insert(listener, meta, priority);
+                }
                 RegisterResult::Notified(tag)
             }
 
@@ -152,6 +372,12 @@ impl<T> crate::Inner<T> {
                 RegisterResult::Registered
             }
 
+            State::Closed(close_tag) => {
+                // The event was closed, there is nothing to wait for.
+                entry.state.set(State::Closed(close_tag));
+                RegisterResult::Closed(close_tag)
+            }
+
             _ => {
                 // We have not been notified, register the task.
                 entry.state.set(State::Task(task.into_task()));
@@ -159,9 +385,150 @@ impl<T> crate::Inner<T> {
             }
         }
     }
+
+    /// Call `f` with the tag of the listener's notification, if it was notified.
+    pub(crate) fn peek<R>(
+        &self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+        f: impl FnOnce(&T) -> R,
+    ) -> Option<R> {
+        let _inner = self.lock();
+        let entry_guard = listener.as_mut().as_pin_mut()?.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+
+        let state = entry.state.replace(State::NotifiedTaken);
+        let result = match &state {
+            State::Notified { tag, .. } => Some(f(tag)),
+            _ => None,
+        };
+        entry.state.set(state);
+
+        result
+    }
+
+    /// Check whether the listener was notified, without registering a task.
+    pub(crate) fn check(&self, mut listener: Pin<&mut Option<Listener<T>>>) -> RegisterResult<T> {
+        let mut inner = self.lock();
+        let entry_guard = match listener.as_mut().as_pin_mut() {
+            Some(listener) => listener.link.get(),
+            None => return RegisterResult::NeverInserted,
+        };
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+
+        match entry.state.replace(State::NotifiedTaken) {
+            State::Notified { tag, .. } => {
+                // We have been notified, remove the listener.
+                inner.remove(listener, false);
+                RegisterResult::Notified(tag)
+            }
+
+            State::Closed(close_tag) => {
+                entry.state.set(State::Closed(close_tag));
+                RegisterResult::Closed(close_tag)
+            }
+
+            state => {
+                // We have not been notified, leave the task as it is.
+                entry.state.set(state);
+                RegisterResult::Registered
+            }
+        }
+    }
 }
 
 impl<T> Inner<T> {
+    /// Add a new listener to the list.
+    fn insert(
+        &mut self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+        meta: usize,
+        priority: usize,
+    ) {
+        // Listeners on a closed list are closed right away. Otherwise, if a notification left a
+        // permit behind, this listener takes it.
+        let state = match self.close_tag {
+            Some(close_tag) => State::Closed(close_tag),
+            None => match self.permits.pop_front() {
+                Some(tag) => State::Notified {
+                    additional: false,
+                    tag,
+                },
+                None => State::Created,
+            },
+        };
+        let notified = state.is_notified();
+        let closed = matches!(state, State::Closed(_));
+
+        listener.as_mut().set(Some(Listener {
+            link: UnsafeCell::new(Link {
+                state: Cell::new(state),
+                prev: Cell::new(self.tail),
+                next: Cell::new(None),
+                meta,
+                priority,
+            }),
+            _pin: PhantomPinned,
+        }));
+        let listener = listener.as_pin_mut().unwrap();
+
+        {
+            let entry_guard = listener.link.get();
+            // SAFETY: We are locked, so we can access the inner `link`.
+            let entry = unsafe { entry_guard.deref() };
+
+            // Unnotified entries are kept sorted by priority, so find the first one with a lower
+            // priority than the new entry.
+            let mut lower = None;
+            if priority > 0 && !notified && !closed {
+                let mut link = self.next;
+                while let Some(l) = link {
+                    let l = unsafe { l.as_ref() };
+                    if l.priority < priority {
+                        lower = Some(l);
+                        break;
+                    }
+                    link = l.next.get();
+                }
+            }
+
+            match lower {
+                // Link the new entry in front of it.
+                Some(lower) => {
+                    let before = lower.prev.get();
+                    entry.prev.set(before);
+                    entry.next.set(Some(lower.into()));
+                    match before {
+                        None => self.head = Some(entry.into()),
+                        Some(b) => unsafe { b.as_ref().next.set(Some(entry.into())) },
+                    }
+                    lower.prev.set(Some(entry.into()));
+
+                    if self.next == Some(lower.into()) {
+                        self.next = Some(entry.into());
+                    }
+                }
+
+                // Replace the tail with the new entry.
+                None => match self.tail.replace(entry.into()) {
+                    None => self.head = Some(entry.into()),
+                    Some(t) => unsafe { t.as_ref().next.set(Some(entry.into())) },
+                },
+            }
+        }
+
+        if notified {
+            self.notified += 1;
+        } else if !closed && self.next.is_none() {
+            // If there are no unnotified entries, this is the first one.
+            self.next = self.tail;
+        }
+
+        // Bump the entry count.
+        self.len += 1;
+    }
+
     fn remove(
         &mut self,
         mut listener: Pin<&mut Option<Listener<T>>>,
@@ -235,6 +602,29 @@ impl<T> Inner<T> {
         let mut n = notify.count(Internal::new());
         let is_additional = notify.is_additional(Internal::new());
 
+        if notify.is_coalesced(Internal::new()) && (self.notified > 0 || !self.permits.is_empty()) {
+            // A notification is already pending, so merge this one into it.
+            return 0;
+        }
+
+        if self.next.is_none() {
+            // Nobody is waiting for a notification, so store permits if requested.
+            if self.permit_capacity > 0 {
+                if !is_additional {
+                    n = n.saturating_sub(self.notified);
+                }
+                self.store_permits(n, is_additional, &mut notify);
+            } else if n > 0
+                && self.close_tag.is_none()
+                && self.permits.is_empty()
+                && notify.is_sticky(Internal::new())
+            {
+                self.permits.push_back(notify.next_tag(Internal::new()));
+            }
+
+            return 0;
+        }
+
         if !is_additional {
             if n < self.notified {
                 return 0;
@@ -242,44 +632,308 @@ impl<T> Inner<T> {
             n -= self.notified;
         }
 
+        // Notifying every unnotified entry, such as with `usize::MAX`, needs no picking.
+        let unnotified = self.len - self.notified;
+        if n >= unnotified && self.wake_order != WakeOrder::Random {
+            self.notify_all(&mut notify, is_additional);
+            self.store_permits(n - unnotified, is_additional, &mut notify);
+            return unnotified;
+        }
+
         let original_count = n;
         while n > 0 {
             n -= 1;
 
             // Notify the next entry.
             match self.next {
-                None => return original_count - n - 1,
-
-                Some(e) => {
-                    // Get the entry and move the pointer forwards.
-                    let entry = unsafe { e.as_ref() };
-                    self.next = entry.next.get();
-
-                    // Set the state to `Notified` and notify.
-                    let tag = notify.next_tag(Internal::new());
-                    if let State::Task(task) = entry.state.replace(State::Notified {
-                        additional: is_additional,
-                        tag,
-                    }) {
-                        task.wake();
-                    }
+                None => {
+                    // Keep whatever is left over for future listeners.
+                    self.store_permits(n + 1, is_additional, &mut notify);
+                    return original_count - n - 1;
+                }
 
-                    // Bump the notified count.
-                    self.notified += 1;
+                Some(next) => {
+                    let e = self.pick(next);
+                    self.notify_entry(e, &mut notify, is_additional);
                 }
             }
         }
 
         original_count - n
     }
+
+    fn notify_if(
+        &mut self,
+        mut notify: impl Notification<Tag = T>,
+        mut pred: impl FnMut(usize) -> bool,
+    ) -> usize {
+        let mut n = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if !is_additional {
+            // Every entry before the first unnotified one is notified, so count the matching ones.
+            let mut link = self.head;
+            while link.is_some() && link != self.next {
+                let entry = unsafe { link.unwrap().as_ref() };
+                if pred(entry.meta) {
+                    n = n.saturating_sub(1);
+                }
+                link = entry.next.get();
+            }
+        }
+
+        let mut count = 0;
+        let mut link = self.next;
+        while n > 0 {
+            let e = match link {
+                Some(e) => e,
+                None => break,
+            };
+            let entry = unsafe { e.as_ref() };
+            link = entry.next.get();
+
+            if !pred(entry.meta) {
+                continue;
+            }
+
+            self.notify_entry(e, &mut notify, is_additional);
+            count += 1;
+            n -= 1;
+        }
+
+        count
+    }
+
+    fn notify_last(&mut self, mut notify: impl Notification<Tag = T>) -> usize {
+        if self.next.is_none() {
+            // Nobody is waiting, so there is no difference to a regular notification.
+            return self.notify(notify);
+        }
+
+        let mut n = notify.count(Internal::new());
+        let is_additional = notify.is_additional(Internal::new());
+
+        if !is_additional {
+            if n < self.notified {
+                return 0;
+            }
+            n -= self.notified;
+        }
+
+        let mut count = 0;
+        while n > 0 && self.next.is_some() {
+            // Every entry after the first unnotified one is unnotified, so this includes the tail.
+            let e = self.tail.unwrap();
+            self.notify_entry(e, &mut notify, is_additional);
+            count += 1;
+            n -= 1;
+        }
+
+        count
+    }
+
+    /// Notifies every unnotified entry in wake order, in a single walk over the list.
+    ///
+    /// The entries stay where they are, since they all end up notified.
+    fn notify_all(&mut self, notify: &mut impl Notification<Tag = T>, is_additional: bool) {
+        let first = self.next.take();
+        let lifo = self.wake_order == WakeOrder::Lifo;
+        let mut link = if lifo { self.tail } else { first };
+
+        while let Some(e) = link {
+            let entry = unsafe { e.as_ref() };
+            link = if !lifo {
+                entry.next.get()
+            } else if Some(e) == first {
+                None
+            } else {
+                entry.prev.get()
+            };
+
+            // Set the state to `Notified` and notify.
+            let tag = notify.next_tag(Internal::new());
+            if let State::Task(task) = entry.state.replace(State::Notified {
+                additional: is_additional,
+                tag,
+            }) {
+                self.tasks.push(task);
+            }
+        }
+
+        self.notified = self.len;
+    }
+
+    /// Stores up to `n` permits, as far as the permit capacity allows.
+    fn store_permits(
+        &mut self,
+        mut n: usize,
+        is_additional: bool,
+        notify: &mut impl Notification<Tag = T>,
+    ) {
+        if self.close_tag.is_some() {
+            return;
+        }
+
+        // Permits count as notified listeners.
+        if !is_additional {
+            n = n.saturating_sub(self.permits.len());
+        }
+
+        while n > 0 && self.permits.len() < self.permit_capacity {
+            self.permits.push_back(notify.next_tag(Internal::new()));
+            n -= 1;
+        }
+    }
+
+    /// Picks the unnotified entry to notify next, according to the wake order.
+    fn pick(&mut self, next: NonNull<Link<T>>) -> NonNull<Link<T>> {
+        match self.wake_order {
+            WakeOrder::Fifo => next,
+
+            // Every entry after the first unnotified one is unnotified, so this includes the tail.
+            WakeOrder::Lifo => self.tail.unwrap(),
+
+            WakeOrder::Random => {
+                let unnotified = self.len - self.notified;
+                let mut e = next;
+                for _ in 0..self.rng.below(unnotified) {
+                    e = unsafe { e.as_ref() }.next.get().unwrap();
+                }
+                e
+            }
+        }
+    }
+
+    /// Notifies an unnotified entry, moving it in front of the first unnotified entry so that
+    /// every notified entry still comes before it.
+    fn notify_entry(
+        &mut self,
+        e: NonNull<Link<T>>,
+        notify: &mut impl Notification<Tag = T>,
+        is_additional: bool,
+    ) {
+        self.move_to_notified(e);
+
+        // Set the state to `Notified` and notify.
+        let entry = unsafe { e.as_ref() };
+        let tag = notify.next_tag(Internal::new());
+        if let State::Task(task) = entry.state.replace(State::Notified {
+            additional: is_additional,
+            tag,
+        }) {
+            self.tasks.push(task);
+        }
+
+        self.notified += 1;
+    }
+
+    /// Moves an unnotified entry right in front of the first unnotified entry, and advances the
+    /// first unnotified entry past it if it was that entry.
+    fn move_to_notified(&mut self, e: NonNull<Link<T>>) {
+        let next = self.next.expect("no unnotified entries");
+        if next == e {
+            self.next = unsafe { e.as_ref() }.next.get();
+            return;
+        }
+
+        let entry = unsafe { e.as_ref() };
+        let prev = entry.prev.get();
+        let following = entry.next.get();
+
+        // Unlink the entry. It comes after `next`, so it has a previous entry.
+        unsafe { prev.unwrap().as_ref() }.next.set(following);
+        match following {
+            None => self.tail = prev,
+            Some(f) => unsafe { f.as_ref() }.prev.set(prev),
+        }
+
+        // Link it in again in front of `next`.
+        let before = unsafe { next.as_ref() }.prev.get();
+        entry.prev.set(before);
+        entry.next.set(Some(next));
+        match before {
+            None => self.head = Some(e),
+            Some(b) => unsafe { b.as_ref() }.next.set(Some(e)),
+        }
+        unsafe { next.as_ref() }.prev.set(Some(e));
+    }
+
+    fn notify_with_tags(&mut self, mut tags: impl Iterator<Item = T>) -> usize {
+        let mut count = 0;
+
+        while let Some(e) = self.next {
+            // Only take a tag once there is an entry to hand it to.
+            let tag = match tags.next() {
+                Some(tag) => tag,
+                None => break,
+            };
+
+            // Get the entry and move the pointer forwards.
+            let entry = unsafe { e.as_ref() };
+            self.next = entry.next.get();
+
+            // Set the state to `Notified` and notify.
+            if let State::Task(task) = entry.state.replace(State::Notified {
+                additional: true,
+                tag,
+            }) {
+                self.tasks.push(task);
+            }
+
+            self.notified += 1;
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Deliver notifications deferred by `Event::notify_from_isr()` without allocating.
+    ///
+    /// At most `INLINE_WAKEUPS` listeners are notified, and only as many as there is room for in
+    /// the task buffer. Permits are never stored, since that may allocate. Whatever is left over
+    /// goes back into `pending`. Returns the number of listeners that were notified.
+    fn notify_pending_bounded(&mut self, pending: &AtomicUsize) -> usize {
+        if pending.load(Ordering::Relaxed) == 0 {
+            return 0;
+        }
+        let n = pending.swap(0, Ordering::SeqCst);
+
+        let room = (self.tasks.capacity() - self.tasks.len()).min(INLINE_WAKEUPS);
+        let unnotified = self.len - self.notified;
+        let now = n.min(self.notified + unnotified.min(room));
+        let notified = self.notify(GenericNotify::new(now, false, NothingProducer::default()));
+
+        // Leave the rest for later, if there is anything it could still do.
+        if now < n && (now < self.len || self.permit_capacity > 0) {
+            pending.fetch_max(n, Ordering::SeqCst);
+        }
+
+        notified
+    }
+
+    #[cold]
+    fn close(&mut self, tag: fn() -> T) {
+        self.close_tag = Some(tag);
+        self.permits.clear();
+
+        // Mark every unnotified entry as closed.
+        while let Some(e) = self.next {
+            let entry = unsafe { e.as_ref() };
+            self.next = entry.next.get();
+
+            if let State::Task(task) = entry.state.replace(State::Closed(tag)) {
+                self.tasks.push(task);
+            }
+        }
+    }
 }
 
-struct ListLock<'a, 'b, T> {
-    lock: MutexGuard<'a, Inner<T>>,
-    inner: &'b crate::Inner<T>,
+struct ListLock<'a, T> {
+    lock: ManuallyDrop<MutexGuard<'a, Inner<T>>>,
+    inner: &'a crate::Inner<T>,
 }
 
-impl<T> Deref for ListLock<'_, '_, T> {
+impl<T> Deref for ListLock<'_, T> {
     type Target = Inner<T>;
 
     fn deref(&self) -> &Self::Target {
@@ -287,24 +941,90 @@ impl<T> Deref for ListLock<'_, '_, T> {
     }
 }
 
-impl<T> DerefMut for ListLock<'_, '_, T> {
+impl<T> DerefMut for ListLock<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.lock
     }
 }
 
-impl<T> Drop for ListLock<'_, '_, T> {
+impl<T> Drop for ListLock<'_, T> {
     fn drop(&mut self) {
-        let list = &mut **self;
+        loop {
+            let inner = self.inner;
+            let pending = &inner.pending;
+            let list = &mut **self;
+
+            // Deliver notifications from interrupt handlers that could not lock the list.
+            if pending.load(Ordering::Relaxed) > 0 {
+                let n = pending.swap(0, Ordering::SeqCst);
+                list.notify(GenericNotify::new(n, false, NothingProducer::default()));
+            }
 
-        // Update the notified count.
-        let notified = if list.notified < list.len {
-            list.notified
+            // Update the notified count.
+            inner.store_counts(list);
+
+            // Wake up tasks only after unlocking, so that they don't run into the lock right away.
+            let tasks = Wakeups::take(&mut list.tasks);
+
+            // SAFETY: The guard is not used again until it is replaced below.
+            unsafe { ManuallyDrop::drop(&mut self.lock) };
+
+            tasks.wake();
+
+            // An interrupt handler may have failed to lock the list after we checked for pending
+            // notifications. If so, lock it again and deliver them.
+            atomic::fence(Ordering::SeqCst);
+            if self.inner.pending.load(Ordering::Relaxed) == 0 {
+                break;
+            }
+
+            match self.inner.list.inner.try_lock() {
+                Ok(lock) => self.lock = ManuallyDrop::new(lock),
+                Err(TryLockError::Poisoned(e)) => self.lock = ManuallyDrop::new(e.into_inner()),
+                Err(TryLockError::WouldBlock) => break,
+            }
+        }
+
+        self.inner.notify_changed();
+    }
+}
+
+/// The number of tasks that are moved out of the list without allocating.
+const INLINE_WAKEUPS: usize = 8;
+
+/// Tasks taken out of the list, to be woken once it is unlocked.
+///
+/// Most unlocks wake a handful of tasks at most. Those are moved to the stack, so that the buffer
+/// in the list keeps its capacity. Only larger batches need to allocate.
+struct Wakeups {
+    inline: [Option<Task>; INLINE_WAKEUPS],
+    spilled: Vec<Task>,
+}
+
+impl Wakeups {
+    const NONE: Option<Task> = None;
+
+    /// Take all tasks out of `tasks`, keeping its capacity.
+    fn take(tasks: &mut Vec<Task>) -> Self {
+        let spilled = if tasks.len() > INLINE_WAKEUPS {
+            tasks.split_off(INLINE_WAKEUPS)
         } else {
-            usize::MAX
+            Vec::new()
         };
 
-        self.inner.notified.store(notified, Ordering::Release);
+        let mut inline = [Self::NONE; INLINE_WAKEUPS];
+        for (slot, task) in inline.iter_mut().zip(tasks.drain(..)) {
+            *slot = Some(task);
+        }
+
+        Self { inline, spilled }
+    }
+
+    /// Wake up all of the tasks, in the order they were added.
+    fn wake(self) {
+        for task in self.inline.into_iter().flatten().chain(self.spilled) {
+            task.wake();
+        }
     }
 }
 
@@ -329,6 +1049,12 @@ struct Link<T> {
 
     /// The next link in the linked list.
     next: Cell<Option<NonNull<Link<T>>>>,
+
+    /// Metadata attached when the listener was inserted, matched by `notify_if`.
+    meta: usize,
+
+    /// The priority of the listener. Unnotified listeners are sorted by it, highest first.
+    priority: usize,
 }
 
 #[cfg(test)]
@@ -389,4 +1115,59 @@ mod tests {
         inner.remove(listen1, true);
         inner.remove(listen2, true);
     }
+
+    #[test]
+    fn wake_after_unlock() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        let inner = crate::Inner::new();
+        make_listeners!(listen1);
+        inner.insert(listen1.as_mut());
+
+        let woken = Arc::new(AtomicBool::new(false));
+        let waker = waker_fn::waker_fn({
+            let woken = woken.clone();
+            move || woken.store(true, Ordering::SeqCst)
+        });
+        inner.register(listen1.as_mut(), TaskRef::Waker(&waker));
+
+        {
+            let mut list = inner.lock();
+            list.notify(GenericNotify::new(1, false, || ()));
+
+            // The task isn't woken up while the list is locked.
+            assert!(!woken.load(Ordering::SeqCst));
+        }
+
+        assert!(woken.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn wakeups_keep_capacity() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let woken = Arc::new(AtomicUsize::new(0));
+        let waker = waker_fn::waker_fn({
+            let woken = woken.clone();
+            move || {
+                woken.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // More tasks than fit on the stack.
+        let mut tasks = Vec::new();
+        for _ in 0..INLINE_WAKEUPS + 2 {
+            tasks.push(Task::Waker(waker.clone()));
+        }
+        let capacity = tasks.capacity();
+
+        let wakeups = Wakeups::take(&mut tasks);
+        assert!(tasks.is_empty());
+        assert_eq!(tasks.capacity(), capacity);
+
+        wakeups.wake();
+        assert_eq!(woken.load(Ordering::SeqCst), INLINE_WAKEUPS + 2);
+    }
 }
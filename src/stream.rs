@@ -0,0 +1,78 @@
+//! A stream of notifications from a single, permanently registered listener.
+
+use crate::{Event, EventListener};
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::Stream;
+
+/// A [`Stream`] that yields every notification delivered to its listener.
+///
+/// This is created by [`Event::stream()`]. Unlike an [`EventListener`], which completes after a
+/// single notification, the listener of an [`EventStream`] stays registered. After yielding a
+/// notification it moves to the back of the list while the list is still locked, so there is no
+/// window in which a notification can be missed.
+///
+/// The stream ends once the event is closed with [`Event::close()`].
+///
+/// This type is available with the `stream` feature.
+pub struct EventStream<T = ()> {
+    /// The listener that stays registered.
+    listener: EventListener<T>,
+}
+
+impl<T> fmt::Debug for EventStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("listener", &self.listener)
+            .finish()
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.listener.listener_mut().poll_rearm(cx).map(Result::ok)
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns a [`Stream`] of the notifications delivered to a permanently registered listener.
+    ///
+    /// The listener is registered right away, so notifications sent after this call are not lost
+    /// even if the stream has not been polled yet. Each notification counts toward `n` in
+    /// [`Event::notify()`] like it would for any other listener.
+    ///
+    /// This method is available with the `stream` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// use futures_lite::stream::StreamExt;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    /// let mut stream = event.stream();
+    ///
+    /// event.notify(1);
+    /// stream.next().await;
+    ///
+    /// // The stream is still registered after the first notification.
+    /// event.notify(1);
+    /// stream.next().await;
+    ///
+    /// // The stream ends once the event is closed.
+    /// event.close();
+    /// assert_eq!(stream.next().await, None);
+    /// # });
+    /// ```
+    pub fn stream(&self) -> EventStream<T> {
+        EventStream {
+            listener: self.listen(),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Rate-limiting notifications.
+
+use crate::timeout::wake_at;
+use crate::{Event, EventListener};
+
+use core::fmt;
+
+use std::sync::{Arc, Mutex};
+use std::task::{Wake, Waker};
+use std::time::{Duration, Instant};
+
+/// An [`Event`] that notifies its listeners at most once per interval.
+///
+/// The first notification is delivered right away. Notifications that come in less than an
+/// interval after the last delivered one are held back and merged: once the interval has passed,
+/// a single notification for the largest number of listeners that any of them asked for is
+/// delivered. No notification is lost, but listeners are woken up at most once per interval.
+///
+/// This is useful for progress or update events, where waking waiters thousands of times per
+/// second would be wasteful. Held back notifications are delivered by the same background thread
+/// that serves [`Timeout`], even if the [`ThrottledEvent`] is dropped in the meantime.
+///
+/// [`Timeout`]: crate::Timeout
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use event_listener::{Listener, ThrottledEvent};
+///
+/// let event = ThrottledEvent::new(Duration::from_millis(10));
+///
+/// let listener = event.listen();
+/// event.notify(1);
+/// listener.wait();
+///
+/// // This one is delivered once the interval has passed.
+/// let listener = event.listen();
+/// event.notify(1);
+/// assert!(!event.as_event().is_notified());
+/// listener.wait();
+/// ```
+pub struct ThrottledEvent {
+    /// The state shared with the timer thread.
+    shared: Arc<Shared>,
+}
+
+/// The state of a [`ThrottledEvent`].
+struct Shared {
+    /// The event that is notified.
+    event: Event,
+
+    /// The minimum time between two notifications.
+    interval: Duration,
+
+    /// The held back notification.
+    state: Mutex<State>,
+}
+
+/// The held back notification of a [`ThrottledEvent`].
+struct State {
+    /// When the last notification was delivered.
+    last: Option<Instant>,
+
+    /// The number of listeners the held back notification is for.
+    pending: usize,
+
+    /// Whether the timer thread is going to deliver the held back notification.
+    scheduled: bool,
+}
+
+impl fmt::Debug for ThrottledEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("ThrottledEvent")
+            .field("event", &self.shared.event)
+            .field("interval", &self.shared.interval)
+            .field("pending", &state.pending)
+            .finish()
+    }
+}
+
+impl ThrottledEvent {
+    /// Creates an event that notifies its listeners at most once per `interval`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::ThrottledEvent;
+    ///
+    /// let event = ThrottledEvent::new(Duration::from_millis(100));
+    /// ```
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                event: Event::new(),
+                interval,
+                state: Mutex::new(State {
+                    last: None,
+                    pending: 0,
+                    scheduled: false,
+                }),
+            }),
+        }
+    }
+
+    /// Returns a guard listening for a notification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::ThrottledEvent;
+    ///
+    /// let event = ThrottledEvent::new(Duration::from_millis(100));
+    /// let listener = event.listen();
+    /// ```
+    pub fn listen(&self) -> EventListener {
+        self.shared.event.listen()
+    }
+
+    /// Notifies a number of active listeners, or holds the notification back.
+    ///
+    /// This works like [`Event::notify()`] if no notification was delivered within the last
+    /// interval. Otherwise, the notification is merged with the other held back ones and
+    /// delivered once the interval has passed.
+    ///
+    /// Returns the number of listeners that were notified right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::ThrottledEvent;
+    ///
+    /// let event = ThrottledEvent::new(Duration::from_secs(60));
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify(1), 1);
+    ///
+    /// // Held back for a minute.
+    /// assert_eq!(event.notify(2), 0);
+    /// ```
+    pub fn notify(&self, n: usize) -> usize {
+        let now = Instant::now();
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let next = state
+            .last
+            .and_then(|last| last.checked_add(self.shared.interval));
+
+        match next {
+            Some(next) if next > now => {
+                // Too early, hold the notification back until the interval has passed.
+                state.pending = state.pending.max(n);
+                if !state.scheduled {
+                    state.scheduled = true;
+                    wake_at(next, Waker::from(Arc::new(Deliver(self.shared.clone()))));
+                }
+                0
+            }
+            _ => {
+                state.last = Some(now);
+                drop(state);
+                self.shared.event.notify(n)
+            }
+        }
+    }
+
+    /// Returns the minimum time between two notifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::ThrottledEvent;
+    ///
+    /// let event = ThrottledEvent::new(Duration::from_millis(100));
+    /// assert_eq!(event.interval(), Duration::from_millis(100));
+    /// ```
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.shared.interval
+    }
+
+    /// Returns the underlying event.
+    ///
+    /// Notifying it directly bypasses the throttling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::ThrottledEvent;
+    ///
+    /// let event = ThrottledEvent::new(Duration::from_millis(100));
+    /// let listener = event.listen();
+    /// assert_eq!(event.as_event().total_listeners(), 1);
+    /// ```
+    #[inline]
+    pub fn as_event(&self) -> &Event {
+        &self.shared.event
+    }
+}
+
+/// Delivers the held back notification when woken by the timer thread.
+struct Deliver(Arc<Shared>);
+
+impl Wake for Deliver {
+    fn wake(self: Arc<Self>) {
+        let n = {
+            let mut state = self.0.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.scheduled = false;
+            state.last = Some(Instant::now());
+            core::mem::replace(&mut state.pending, 0)
+        };
+
+        self.0.event.notify(n);
+    }
+}
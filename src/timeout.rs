@@ -0,0 +1,252 @@
+//! Futures that wait for a notification until a deadline.
+
+use crate::EventListener;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking::{Parker, Unparker};
+
+/// A future that waits for a notification or a deadline, whichever comes first.
+///
+/// This is created by [`EventListener::timeout()`] and [`EventListener::deadline()`]. It
+/// resolves to `Some` with the notification's tag, or to `None` once the deadline has passed.
+///
+/// Deadlines are tracked by a single background thread that is shared by every [`Timeout`], so
+/// no separate timer crate is needed.
+pub struct Timeout<T = ()> {
+    /// The listener waiting for a notification.
+    listener: EventListener<T>,
+
+    /// The deadline, or `None` if it is too far in the future to be represented.
+    deadline: Option<Instant>,
+
+    /// The ID and waker of our entry in the timer thread, if it has one.
+    timer: Option<(usize, Waker)>,
+}
+
+impl<T> Unpin for Timeout<T> {}
+
+impl<T> fmt::Debug for Timeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("listener", &self.listener)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+impl<T> Timeout<T> {
+    pub(crate) fn new(listener: EventListener<T>, deadline: Option<Instant>) -> Self {
+        Self {
+            listener,
+            deadline,
+            timer: None,
+        }
+    }
+
+    /// Removes our entry from the timer thread, if there is one.
+    fn cancel(&mut self) {
+        if let (Some((id, _)), Some(deadline)) = (self.timer.take(), self.deadline) {
+            timers().remove(deadline, id);
+        }
+    }
+}
+
+impl<T> Future for Timeout<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = &mut *self;
+
+        if let Poll::Ready(tag) = Pin::new(&mut this.listener).poll(cx) {
+            this.cancel();
+            return Poll::Ready(Some(tag));
+        }
+
+        let deadline = match this.deadline {
+            Some(deadline) => deadline,
+            None => return Poll::Pending,
+        };
+
+        if Instant::now() >= deadline {
+            this.cancel();
+            return Poll::Ready(None);
+        }
+
+        // Make sure the timer thread wakes the current task once the deadline passes.
+        match &mut this.timer {
+            Some((_, waker)) if waker.will_wake(cx.waker()) => {}
+            Some((id, waker)) => {
+                *waker = cx.waker().clone();
+                timers().insert(deadline, *id, waker.clone());
+            }
+            None => {
+                let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+                let waker = cx.waker().clone();
+                timers().insert(deadline, id, waker.clone());
+                this.timer = Some((id, waker));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Timeout<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl<T> EventListener<T> {
+    /// Waits for a notification until the given timeout has elapsed.
+    ///
+    /// The returned future resolves to `None` if the timeout elapsed first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    ///
+    /// // There is no notification, so this times out.
+    /// let listener = event.listen();
+    /// assert_eq!(listener.timeout(Duration::from_millis(10)).await, None);
+    ///
+    /// let listener = event.listen();
+    /// event.notify(1);
+    /// assert_eq!(listener.timeout(Duration::from_secs(1)).await, Some(()));
+    /// # });
+    /// ```
+    pub fn timeout(self, timeout: Duration) -> Timeout<T> {
+        Timeout::new(self, Instant::now().checked_add(timeout))
+    }
+
+    /// Waits for a notification until the given deadline is reached.
+    ///
+    /// The returned future resolves to `None` if the deadline was reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::Event;
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::new();
+    ///
+    /// // There is no notification, so this times out.
+    /// let listener = event.listen();
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert_eq!(listener.deadline(deadline).await, None);
+    /// # });
+    /// ```
+    pub fn deadline(self, deadline: Instant) -> Timeout<T> {
+        Timeout::new(self, Some(deadline))
+    }
+}
+
+/// Wakes `waker` from the timer thread once the deadline passes.
+pub(crate) fn wake_at(deadline: Instant, waker: Waker) {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    timers().insert(deadline, id, waker);
+}
+
+/// The ID of the next timer entry.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The deadlines of every pending [`Timeout`], served by a background thread.
+struct Timers {
+    /// Wakers sorted by deadline.
+    entries: Mutex<BTreeMap<(Instant, usize), Waker>>,
+
+    /// Unparks the timer thread.
+    unparker: Unparker,
+}
+
+impl Timers {
+    /// Registers a waker to be woken at the deadline, replacing an earlier waker with this ID.
+    fn insert(&self, deadline: Instant, id: usize, waker: Waker) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert((deadline, id), waker);
+
+        // Let the timer thread pick up the deadline if it is the next one.
+        if entries.keys().next() == Some(&(deadline, id)) {
+            self.unparker.unpark();
+        }
+    }
+
+    /// Removes the waker with this deadline and ID.
+    fn remove(&self, deadline: Instant, id: usize) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(&(deadline, id));
+    }
+
+    /// Wakes every task whose deadline has passed and returns the next deadline.
+    fn fire(&self) -> Option<Instant> {
+        let now = Instant::now();
+        let mut wakers = Vec::new();
+
+        let next = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+            while let Some((&(deadline, id), _)) = entries.iter().next() {
+                if deadline > now {
+                    break;
+                }
+
+                wakers.extend(entries.remove(&(deadline, id)));
+            }
+
+            entries.keys().next().map(|&(deadline, _)| deadline)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+
+        next
+    }
+}
+
+/// Returns the timer thread's state, starting the thread on first use.
+fn timers() -> &'static Timers {
+    static TIMERS: AtomicPtr<Timers> = AtomicPtr::new(core::ptr::null_mut());
+    static START: Once = Once::new();
+
+    START.call_once(|| {
+        let parker = Parker::new();
+        let timers: &'static Timers = Box::leak(Box::new(Timers {
+            entries: Mutex::new(BTreeMap::new()),
+            unparker: parker.unparker(),
+        }));
+        TIMERS.store(timers as *const Timers as *mut Timers, Ordering::Release);
+
+        thread::Builder::new()
+            .name("event-listener-timer".into())
+            .spawn(move || loop {
+                match timers.fire() {
+                    Some(next) => {
+                        parker.park_deadline(next);
+                    }
+                    None => parker.park(),
+                }
+            })
+            .expect("cannot spawn timer thread");
+    });
+
+    // SAFETY: The pointer was set to a leaked allocation in `call_once`.
+    unsafe { &*TIMERS.load(Ordering::Acquire) }
+}
@@ -0,0 +1,126 @@
+//! Waiting until every one of several listeners is notified.
+
+use crate::Listener;
+
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+/// Waits until every listener has been notified.
+///
+/// The returned future resolves to the tags of all notifications, in the order the listeners
+/// were given. The listeners may belong to different events, which makes this a building block
+/// for fan-in barriers.
+///
+/// A listener that has been notified is not polled again, so the future keeps the tag until the
+/// remaining listeners have been notified as well. If the future is dropped before that, the
+/// listeners that have not completed yet are dropped with it.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{wait_all, Event, IntoNotification};
+/// # futures_lite::future::block_on(async {
+///
+/// let event1 = Event::with_tag();
+/// let event2 = Event::with_tag();
+///
+/// let all = wait_all(vec![event1.listen(), event2.listen()]);
+/// event2.notify(1.tag(2));
+/// event1.notify(1.tag(1));
+///
+/// assert_eq!(all.await, [1, 2]);
+/// # });
+/// ```
+pub fn wait_all<T, L, I>(listeners: I) -> WaitAll<T, L>
+where
+    L: Listener<T> + Unpin,
+    I: IntoIterator<Item = L>,
+{
+    WaitAll {
+        slots: listeners.into_iter().map(Slot::Waiting).collect(),
+        _marker: PhantomData,
+    }
+}
+
+/// A future that waits until every listener has been notified.
+///
+/// This is created by [`wait_all()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitAll<T, L> {
+    /// The listeners, or the tags of the ones that have been notified.
+    slots: Vec<Slot<T, L>>,
+
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// A listener that is either still waiting or has been notified.
+enum Slot<T, L> {
+    /// The listener is still waiting.
+    Waiting(L),
+
+    /// The listener has been notified with this tag.
+    Done(T),
+
+    /// The tag has been handed out.
+    Taken,
+}
+
+impl<T, L> Unpin for WaitAll<T, L> {}
+
+impl<T, L: fmt::Debug> fmt::Debug for WaitAll<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let waiting = self
+            .slots
+            .iter()
+            .filter(|slot| matches!(slot, Slot::Waiting(_)))
+            .count();
+
+        f.debug_struct("WaitAll")
+            .field("listeners", &self.slots.len())
+            .field("waiting", &waiting)
+            .finish()
+    }
+}
+
+impl<T, L> Future for WaitAll<T, L>
+where
+    L: Listener<T> + Unpin,
+{
+    type Output = Vec<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let mut pending = false;
+
+        for slot in &mut self.slots {
+            match slot {
+                Slot::Waiting(listener) => match Pin::new(listener).poll(cx) {
+                    Poll::Ready(tag) => *slot = Slot::Done(tag),
+                    Poll::Pending => pending = true,
+                },
+                Slot::Done(_) => {}
+                Slot::Taken => panic!("`WaitAll` polled after completion"),
+            }
+        }
+
+        if pending {
+            return Poll::Pending;
+        }
+
+        let tags = self
+            .slots
+            .iter_mut()
+            .map(|slot| match mem::replace(slot, Slot::Taken) {
+                Slot::Done(tag) => tag,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        Poll::Ready(tags)
+    }
+}
@@ -0,0 +1,130 @@
+//! Waiting for a number of notifications with a single listener.
+
+use crate::{Event, EventListener};
+
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// A future that waits until a number of listeners of the same event have been notified.
+///
+/// This is created by [`Event::wait_many()`]. It resolves to the tags of the notifications, in
+/// the order the listeners started listening.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitMany<T = ()> {
+    /// The listeners that have not been notified yet, in the order they started listening.
+    listeners: VecDeque<EventListener<T>>,
+
+    /// The tags of the listeners that have been notified.
+    tags: Vec<T>,
+}
+
+impl<T> Unpin for WaitMany<T> {}
+
+impl<T> fmt::Debug for WaitMany<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitMany")
+            .field("waiting", &self.listeners.len())
+            .field("received", &self.tags.len())
+            .finish()
+    }
+}
+
+impl<T> Future for WaitMany<T> {
+    type Output = Vec<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let this = &mut *self;
+
+        // Every listener has to be notified anyway, so waiting for the first one is enough.
+        while let Some(listener) = this.listeners.front_mut() {
+            match Pin::new(listener).poll(cx) {
+                Poll::Ready(tag) => this.tags.push(tag),
+                Poll::Pending => return Poll::Pending,
+            }
+            this.listeners.pop_front();
+        }
+
+        Poll::Ready(mem::take(&mut this.tags))
+    }
+}
+
+impl<T> WaitMany<T> {
+    /// Blocks until every listener has been notified.
+    ///
+    /// This is the blocking version of awaiting the [`WaitMany`].
+    ///
+    /// This method is available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let event = Arc::new(Event::with_tag());
+    /// let waiter = event.wait_many(3);
+    ///
+    /// thread::spawn({
+    ///     let event = event.clone();
+    ///     move || {
+    ///         for job in 0..3 {
+    ///             event.notify(1.additional().tag(job));
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(waiter.wait(), [0, 1, 2]);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(mut self) -> Vec<T> {
+        use crate::Listener;
+
+        while let Some(listener) = self.listeners.pop_front() {
+            self.tags.push(listener.wait());
+        }
+
+        mem::take(&mut self.tags)
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns a future that resolves once `n` notifications have been received.
+    ///
+    /// Waiting for `n` completions with a single listener means registering a new listener after
+    /// every notification, and checking again for notifications that were sent in between. A
+    /// [`WaitMany`] registers `n` listeners right away instead, and collects their tags. It counts
+    /// toward the count in [`Event::notify()`] like `n` separate listeners would, so completions
+    /// should be signaled with [`additional`] notifications to be counted one by one.
+    ///
+    /// [`additional`]: crate::IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// let event = Event::with_tag();
+    /// let completions = event.wait_many(3);
+    ///
+    /// for job in 0..3 {
+    ///     event.notify(1.additional().tag(job));
+    /// }
+    ///
+    /// assert_eq!(completions.await, [0, 1, 2]);
+    /// # });
+    /// ```
+    pub fn wait_many(&self, n: usize) -> WaitMany<T> {
+        WaitMany {
+            listeners: (0..n).map(|_| self.listen()).collect(),
+            tags: Vec::with_capacity(n),
+        }
+    }
+}
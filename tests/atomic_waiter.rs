@@ -0,0 +1,63 @@
+use std::future::Future;
+use std::task::Context;
+
+use event_listener::primitives::AtomicWaiter;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn operations() {
+    let waiter = AtomicWaiter::new(1);
+
+    waiter.store_and_notify(4);
+    assert_eq!(waiter.swap_and_notify(6), 4);
+    assert_eq!(waiter.fetch_add_and_notify(2), 6);
+    assert_eq!(waiter.fetch_sub_and_notify(1), 8);
+    assert_eq!(waiter.fetch_and_and_notify(0b101), 7);
+    assert_eq!(waiter.fetch_or_and_notify(0b010), 0b101);
+    assert_eq!(waiter.fetch_xor_and_notify(0b001), 0b111);
+    assert_eq!(waiter.load(), 0b110);
+}
+
+#[test]
+fn wait_while() {
+    let waiter = AtomicWaiter::new(0);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut above_two = Box::pin(waiter.wait_while(|v| v <= 2));
+    let mut nonzero = Box::pin(waiter.wait_while(|v| v == 0));
+    assert!(above_two.as_mut().poll(&mut cx).is_pending());
+    assert!(nonzero.as_mut().poll(&mut cx).is_pending());
+
+    // Every waiter checks the new value, but only one is done.
+    waiter.fetch_add_and_notify(1);
+    assert!(above_two.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(nonzero.as_mut().poll(&mut cx), std::task::Poll::Ready(1));
+
+    waiter.store_and_notify(3);
+    assert_eq!(above_two.as_mut().poll(&mut cx), std::task::Poll::Ready(3));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_while_blocking() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let waiter = Arc::new(AtomicWaiter::new(0));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let waiter = waiter.clone();
+            thread::spawn(move || waiter.fetch_add_and_notify(1))
+        })
+        .collect();
+
+    assert_eq!(waiter.wait_while_blocking(|v| v < 4), 4);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{Event, EventListener, IntoNotification, WakeOrder};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified(listener: &mut EventListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn fifo() {
+    let event = Event::builder().wake_order(WakeOrder::Fifo).build();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    event.notify(1);
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+}
+
+#[test]
+fn lifo() {
+    let event = Event::builder().wake_order(WakeOrder::Lifo).build();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    event.notify(1);
+    assert!(!is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+
+    // Notified listeners still count towards the notification.
+    let mut l4 = event.listen();
+    event.notify(1);
+    event.notify(1);
+    assert!(is_notified(&mut l4));
+    assert!(!is_notified(&mut l1));
+
+    event.notify(2.additional());
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+}
+
+#[test]
+fn random() {
+    let event = Event::builder().wake_order(WakeOrder::Random).build();
+    let mut listeners = (0..8).map(|_| event.listen()).collect::<Vec<_>>();
+
+    // Every notification reaches exactly one listener that wasn't notified yet.
+    while !listeners.is_empty() {
+        assert_eq!(event.notify(1.additional()), 1);
+
+        let len = listeners.len();
+        listeners.retain_mut(|listener| !is_notified(listener));
+        assert_eq!(listeners.len(), len - 1);
+    }
+
+    assert_eq!(event.notify(1.additional()), 0);
+}
+
+#[test]
+fn permits() {
+    let event = Event::builder().permit_capacity(3).build();
+
+    // Notifications that are not additional only top up the permits.
+    event.notify(2);
+    event.notify(1);
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(!is_notified(&mut l3));
+
+    // Whatever can't be delivered is kept, up to the capacity.
+    assert_eq!(event.notify(5.additional()), 1);
+    assert!(is_notified(&mut l3));
+    let mut listeners = (0..4).map(|_| event.listen()).collect::<Vec<_>>();
+    let notified = listeners
+        .iter_mut()
+        .map(is_notified)
+        .filter(|&notified| notified)
+        .count();
+    assert_eq!(notified, 3);
+
+    // Closing the event drops the permits.
+    drop(listeners);
+    event.notify(2.additional());
+    event.close();
+    assert!(!event.listen_closeable().discard());
+}
+
+#[test]
+fn sticky_without_permits() {
+    let event = Event::builder().build();
+
+    event.notify(2.additional());
+    assert!(!is_notified(&mut event.listen()));
+
+    event.notify(2.additional().sticky());
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn spin_before_park() {
+    use event_listener::Listener;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(Event::builder().spin_before_park(20).build());
+
+    // A notification that arrives while spinning, or only once parked.
+    for delay in [Duration::ZERO, Duration::from_millis(100)] {
+        let listener = event.listen();
+        let handle = thread::spawn({
+            let event = event.clone();
+            move || {
+                thread::sleep(delay);
+                event.notify(1);
+            }
+        });
+        listener.wait();
+        handle.join().unwrap();
+    }
+
+    // Closing is seen while spinning as well.
+    let listener = event.listen_closeable();
+    event.close();
+    assert!(listener.wait().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_all_in_wake_order() {
+    use event_listener::Listener;
+
+    for (order, expected) in [(WakeOrder::Fifo, [1, 2, 3]), (WakeOrder::Lifo, [3, 2, 1])] {
+        let event = Event::builder().wake_order(order).build_with_tag::<usize>();
+        let first = event.listen();
+
+        // Leave one listener notified already.
+        assert_eq!(event.notify(1.tag(0)), 1);
+        let listeners = (0..3).map(|_| event.listen()).collect::<Vec<_>>();
+
+        let mut next = 0;
+        let tags = || {
+            next += 1;
+            next
+        };
+        assert_eq!(event.notify(usize::MAX.tag_with(tags)), 3);
+        assert_eq!(event.notify(usize::MAX.tag_with(|| 4)), 0);
+
+        assert_eq!(first.discard_tag(), Some(0));
+        let tags = listeners
+            .into_iter()
+            .map(|listener| listener.discard_tag().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, expected);
+    }
+}
+
+#[test]
+fn listen_bound() {
+    use event_listener::TryListenError;
+
+    let event = Event::builder().listen_bound(2).build();
+
+    let first = event.try_listen_bounded().unwrap();
+    let _second = event.listen();
+    assert_eq!(
+        event.try_listen_bounded().unwrap_err(),
+        TryListenError::Full
+    );
+
+    // Waits until a listener goes away.
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    let mut admit = Box::pin(event.listen_bounded());
+    assert!(admit.as_mut().poll(&mut cx).is_pending());
+    assert!(admit.as_mut().poll(&mut cx).is_pending());
+
+    drop(first);
+    let mut third = match admit.as_mut().poll(&mut cx) {
+        std::task::Poll::Ready(listener) => listener,
+        std::task::Poll::Pending => panic!("a listener should have been admitted"),
+    };
+    assert_eq!(event.listener_count(), 2);
+
+    event.notify(2);
+    assert!(is_notified(&mut third));
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn listen_bound_concurrent() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    let event = Arc::new(Event::builder().listen_bound(2).build());
+    let admitted = Arc::new(AtomicUsize::new(0));
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let event = event.clone();
+            let admitted = admitted.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    if let Ok(listener) = event.try_listen_bounded() {
+                        // Callers racing for the last place can't both get it.
+                        assert!(admitted.fetch_add(1, Ordering::SeqCst) < 2);
+                        thread::yield_now();
+                        admitted.fetch_sub(1, Ordering::SeqCst);
+                        drop(listener);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert!(event.is_empty());
+}
@@ -0,0 +1,101 @@
+use std::cell::Cell;
+
+use event_listener::{listener, Clock, Closed, Event, Listener, WaitTimeoutError};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+/// A clock that advances by one tick every time it idles.
+#[derive(Default)]
+struct Ticks {
+    now: Cell<u64>,
+    idles: Cell<u64>,
+}
+
+impl Clock for Ticks {
+    type Instant = u64;
+
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+
+    fn idle(&self) {
+        self.now.set(self.now.get() + 1);
+        self.idles.set(self.idles.get() + 1);
+    }
+}
+
+#[test]
+fn times_out() {
+    let clock = Ticks::default();
+    let event = Event::new();
+
+    let listener = event.listen();
+    assert_eq!(listener.wait_timeout_with(&clock, 5), Err(WaitTimeoutError));
+    assert_eq!(clock.idles.get(), 5);
+
+    // A deadline that has already passed times out without idling.
+    let listener = event.listen();
+    assert_eq!(
+        listener.wait_deadline_with(&clock, 0),
+        Err(WaitTimeoutError)
+    );
+    assert_eq!(clock.idles.get(), 5);
+}
+
+#[test]
+fn notified() {
+    let clock = Ticks::default();
+    let event = Event::new();
+
+    let listener = event.listen();
+    event.notify(1);
+    assert_eq!(listener.wait_timeout_with(&clock, 5), Ok(()));
+    assert_eq!(clock.idles.get(), 0);
+
+    listener!(event => listener);
+    event.notify(1);
+    assert_eq!(listener.wait_deadline_with(&clock, 5), Ok(()));
+}
+
+#[test]
+fn closed() {
+    let clock = Ticks::default();
+    let event = Event::new();
+
+    let listener = event.listen_closeable();
+    event.close();
+    assert_eq!(listener.wait_timeout_with(&clock, 5), Some(Err(Closed)));
+
+    // Plain listeners complete with the close tag.
+    let listener = event.listen();
+    assert_eq!(listener.wait_timeout_with(&clock, 5), Ok(()));
+}
+
+#[test]
+fn wait_with_idle() {
+    let event = Event::new();
+    let idles = Cell::new(0);
+
+    let listener = event.listen();
+    listener.wait_with(|| {
+        idles.set(idles.get() + 1);
+        if idles.get() == 3 {
+            event.notify(1);
+        }
+    });
+    assert_eq!(idles.get(), 3);
+
+    // Already notified, so this never idles.
+    let listener = event.listen_closeable();
+    event.notify(1);
+    assert_eq!(listener.wait_with(|| unreachable!()), Ok(()));
+
+    let listener = event.listen_closeable();
+    assert_eq!(
+        listener.wait_with(|| {
+            event.close();
+        }),
+        Err(Closed)
+    );
+}
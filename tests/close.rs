@@ -0,0 +1,175 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use event_listener::{CloseableListener, Closed, Event, EventListener};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll_closeable(listener: &mut CloseableListener) -> Option<Result<(), Closed>> {
+    let waker = waker_fn(|| ());
+    match Pin::new(listener).poll(&mut Context::from_waker(&waker)) {
+        Poll::Ready(result) => Some(result),
+        Poll::Pending => None,
+    }
+}
+
+fn is_notified(listener: &mut EventListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn close_wakes_listeners() {
+    let event = Event::new();
+    let woken = Arc::new(AtomicBool::new(false));
+
+    let mut listener = event.listen_closeable();
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || woken.store(true, Ordering::SeqCst)
+    });
+    assert!(Pin::new(&mut listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    assert!(!event.is_closed());
+    assert!(event.close());
+    assert!(event.is_closed());
+    assert!(woken.load(Ordering::SeqCst));
+    assert_eq!(poll_closeable(&mut listener), Some(Err(Closed)));
+}
+
+#[test]
+fn close_twice() {
+    let event = Event::new();
+    assert!(event.close());
+    assert!(!event.close());
+}
+
+#[test]
+fn close_keeps_notifications() {
+    let event = Event::new();
+
+    let mut l1 = event.listen_closeable();
+    let mut l2 = event.listen_closeable();
+
+    event.notify(1);
+    event.close();
+
+    assert_eq!(poll_closeable(&mut l1), Some(Ok(())));
+    assert_eq!(poll_closeable(&mut l2), Some(Err(Closed)));
+}
+
+#[test]
+fn listen_after_close() {
+    let event = Event::new();
+    event.close();
+
+    let mut listener = event.listen_closeable();
+    assert_eq!(poll_closeable(&mut listener), Some(Err(Closed)));
+
+    // Plain listeners complete with the close tag, and notifications reach nobody.
+    let mut listener = event.listen();
+    assert_eq!(event.notify(1), 0);
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+fn close_completes_plain_listeners() {
+    let event = Event::new();
+    let woken = Arc::new(AtomicBool::new(false));
+
+    let mut listener = event.listen();
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || woken.store(true, Ordering::SeqCst)
+    });
+    assert!(Pin::new(&mut listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    event.close();
+    assert!(woken.load(Ordering::SeqCst));
+    futures_lite::future::block_on(listener);
+
+    // Listeners created after closing complete right away.
+    futures_lite::future::block_on(event.listen());
+    futures_lite::future::block_on(event.notified());
+
+    let (_notifier, waiter) = {
+        let event = Event::new();
+        event.close();
+        event.pair()
+    };
+    futures_lite::future::block_on(waiter.listen());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn close_with_tag() {
+    let event = Event::<u32>::with_tag();
+    let before = event.listen();
+    let mut closeable = event.listen_closeable();
+
+    assert!(event.close_with(|| 7));
+    assert!(!event.close_with(|| 8));
+
+    assert_eq!(futures_lite::future::block_on(before), 7);
+    assert_eq!(futures_lite::future::block_on(event.listen()), 7);
+    assert_eq!(
+        futures_lite::future::block_on(Pin::new(&mut closeable)),
+        Err(Closed)
+    );
+}
+
+#[test]
+fn close_drops_sticky_permit() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+    event.notify(1.sticky());
+    event.close();
+
+    let mut listener = event.listen_closeable();
+    assert_eq!(poll_closeable(&mut listener), Some(Err(Closed)));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn close_blocking() {
+    use event_listener::{listener, Listener};
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(Event::new());
+    let listener = event.listen_closeable();
+
+    let handle = thread::spawn({
+        let event = event.clone();
+        move || {
+            thread::sleep(Duration::from_millis(10));
+            event.close();
+        }
+    });
+
+    assert_eq!(listener.wait(), Err(Closed));
+    handle.join().unwrap();
+
+    // Plain listeners don't block once the event is closed.
+    event.wait();
+    listener!(event => listener);
+    listener.wait();
+
+    let listener = event.listen_closeable();
+    assert_eq!(
+        listener.wait_timeout(Duration::from_secs(1)),
+        Some(Err(Closed))
+    );
+}
@@ -0,0 +1,103 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use event_listener::primitives::Condvar;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn wait_releases_guard() {
+    let lock = Mutex::new(0);
+    let cvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let guard = lock.lock().unwrap();
+    let mut wait = Box::pin(cvar.wait(guard, || async { lock.lock().unwrap() }));
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+
+    // The mutex can be locked while waiting.
+    *lock.lock().unwrap() = 1;
+    assert_eq!(cvar.notify_one(), 1);
+
+    let guard = match wait.as_mut().poll(&mut cx) {
+        Poll::Ready(guard) => guard,
+        Poll::Pending => panic!("the waiter was notified"),
+    };
+    assert_eq!(*guard, 1);
+}
+
+#[test]
+fn notify_one_wakes_another_waiter_each_time() {
+    let cvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut w1 = Box::pin(cvar.wait((), || async {}));
+    assert!(w1.as_mut().poll(&mut cx).is_pending());
+    let mut w2 = Box::pin(cvar.wait((), || async {}));
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    assert_eq!(cvar.notify_one(), 1);
+    assert_eq!(cvar.notify_one(), 1);
+    assert!(w1.as_mut().poll(&mut cx).is_ready());
+    assert!(w2.as_mut().poll(&mut cx).is_ready());
+
+    assert_eq!(cvar.notify_all(), 0);
+}
+
+#[test]
+fn wait_while() {
+    let lock = Mutex::new(0);
+    let cvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let guard = lock.lock().unwrap();
+    let mut wait = Box::pin(cvar.wait_while(guard, || async { lock.lock().unwrap() }, |n| **n < 2));
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+
+    // Woken up, but the condition still holds.
+    *lock.lock().unwrap() = 1;
+    cvar.notify_all();
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+
+    *lock.lock().unwrap() = 2;
+    cvar.notify_all();
+    let guard = match wait.as_mut().poll(&mut cx) {
+        Poll::Ready(guard) => guard,
+        Poll::Pending => panic!("the condition no longer holds"),
+    };
+    assert_eq!(*guard, 2);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_blocking() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+
+    let handle = thread::spawn({
+        let pair = pair.clone();
+        move || {
+            let (lock, cvar) = &*pair;
+            *lock.lock().unwrap() = true;
+            cvar.notify_all();
+        }
+    });
+
+    let (lock, cvar) = &*pair;
+    let guard = cvar.wait_while_blocking(
+        lock.lock().unwrap(),
+        || lock.lock().unwrap(),
+        |ready| !**ready,
+    );
+    assert!(*guard);
+    drop(guard);
+    handle.join().unwrap();
+}
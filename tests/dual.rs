@@ -0,0 +1,69 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{DualEvent, DualListener, IntoNotification};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified(listener: &mut DualListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn classes_are_separate() {
+    let event = DualEvent::new();
+
+    let mut a1 = event.listen_a();
+    let mut a2 = event.listen_a();
+    let mut b1 = event.listen_b();
+    let mut b2 = event.listen_b();
+
+    assert_eq!(event.notify_b(1), 1);
+    assert!(!is_notified(&mut a1));
+    assert!(is_notified(&mut b1));
+    assert!(!is_notified(&mut b2));
+
+    assert_eq!(event.notify_a(usize::MAX), 2);
+    assert!(is_notified(&mut a1));
+    assert!(is_notified(&mut a2));
+    assert!(!is_notified(&mut b2));
+}
+
+#[test]
+fn notify_b_or_a() {
+    let event = DualEvent::new();
+
+    let mut a1 = event.listen_a();
+    let mut a2 = event.listen_a();
+    let mut b = event.listen_b();
+
+    // A writer is waiting, so it is woken up instead of the readers.
+    assert_eq!(event.notify_b_or_a(1.additional(), usize::MAX), 1);
+    assert!(!is_notified(&mut a1));
+    assert!(is_notified(&mut b));
+
+    assert_eq!(event.notify_b_or_a(1.additional(), usize::MAX), 2);
+    assert!(is_notified(&mut a1));
+    assert!(is_notified(&mut a2));
+}
+
+#[test]
+fn drop_passes_notification_on_within_class() {
+    let event = DualEvent::new();
+
+    let b1 = event.listen_b();
+    let mut a = event.listen_a();
+    let mut b2 = event.listen_b();
+
+    event.notify_b(1);
+    drop(b1);
+
+    assert!(!is_notified(&mut a));
+    assert!(is_notified(&mut b2));
+}
@@ -0,0 +1,75 @@
+use std::future::Future;
+use std::task::Context;
+
+use event_listener::primitives::EventFlag;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn set_and_reset() {
+    let flag = EventFlag::new();
+    assert!(!flag.is_set());
+
+    assert!(flag.set());
+    assert!(!flag.set());
+    assert!(flag.is_set());
+
+    assert!(flag.reset());
+    assert!(!flag.reset());
+    assert!(!flag.is_set());
+}
+
+#[test]
+fn set_wakes_waiters_even_after_reset() {
+    let flag = EventFlag::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut w1 = Box::pin(flag.wait());
+    let mut w2 = Box::pin(flag.wait());
+    assert!(w1.as_mut().poll(&mut cx).is_pending());
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    flag.set();
+    flag.reset();
+
+    assert!(w1.as_mut().poll(&mut cx).is_ready());
+    assert!(w2.as_mut().poll(&mut cx).is_ready());
+
+    // A new waiter has to wait for the next `set()`.
+    let mut w3 = Box::pin(flag.wait());
+    assert!(w3.as_mut().poll(&mut cx).is_pending());
+    flag.set();
+    assert!(w3.as_mut().poll(&mut cx).is_ready());
+
+    // While raised, waits complete immediately.
+    let mut w4 = Box::pin(flag.wait());
+    assert!(w4.as_mut().poll(&mut cx).is_ready());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_blocking_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let flag = Arc::new(EventFlag::new());
+    assert!(!flag.wait_timeout(Duration::from_millis(10)));
+
+    let handles = (0..4)
+        .map(|_| {
+            let flag = flag.clone();
+            thread::spawn(move || flag.wait_blocking())
+        })
+        .collect::<Vec<_>>();
+
+    thread::sleep(Duration::from_millis(50));
+    flag.set();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use event_listener::primitives::EventGroup;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn set_and_clear() {
+    let group = EventGroup::new();
+
+    assert_eq!(group.set(0b001), 0);
+    assert_eq!(group.set(0b100), 0b001);
+    assert_eq!(group.get(), 0b101);
+    assert_eq!(group.clear(0b001), 0b101);
+    assert_eq!(group.get(), 0b100);
+}
+
+#[test]
+fn wait_any_and_all() {
+    let group = EventGroup::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut any = Box::pin(group.wait_any(0b011, false));
+    let mut all = Box::pin(group.wait_all(0b011, true));
+    assert!(any.as_mut().poll(&mut cx).is_pending());
+    assert!(all.as_mut().poll(&mut cx).is_pending());
+
+    group.set(0b010);
+    assert_eq!(any.as_mut().poll(&mut cx), Poll::Ready(0b010));
+    assert!(all.as_mut().poll(&mut cx).is_pending());
+
+    group.set(0b101);
+    assert_eq!(all.as_mut().poll(&mut cx), Poll::Ready(0b111));
+    assert_eq!(group.get(), 0b100);
+}
+
+#[test]
+fn clear_on_exit_consumes_bits() {
+    let group = EventGroup::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut w1 = Box::pin(group.wait_any(0b1, true));
+    let mut w2 = Box::pin(group.wait_any(0b1, true));
+    assert!(w1.as_mut().poll(&mut cx).is_pending());
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    // Only one waiter gets to consume the bit.
+    group.set(0b1);
+    assert!(w1.as_mut().poll(&mut cx).is_ready());
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    group.set(0b1);
+    assert!(w2.as_mut().poll(&mut cx).is_ready());
+    assert_eq!(group.get(), 0);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_all_blocking_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let group = Arc::new(EventGroup::new());
+
+    let handle = thread::spawn({
+        let group = group.clone();
+        move || group.wait_all_blocking(0b11, true)
+    });
+
+    thread::sleep(Duration::from_millis(20));
+    group.set(0b01);
+    thread::sleep(Duration::from_millis(20));
+    group.set(0b10);
+
+    assert_eq!(handle.join().unwrap(), 0b11);
+    assert_eq!(group.get(), 0);
+}
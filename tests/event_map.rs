@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{EventListener, EventMap, IntoNotification};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified<T>(listener: &mut EventListener<T>) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn keys_are_independent() {
+    let map = EventMap::new();
+
+    let mut a = map.listen("a");
+    let mut b = map.listen("b");
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.notify("a", 1), 1);
+    assert!(is_notified(&mut a));
+    assert!(!is_notified(&mut b));
+
+    assert_eq!(map.notify("c", 1), 0);
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn tags() {
+    let map = EventMap::<u32, u32>::with_tag();
+
+    let mut listener = map.listen(1);
+    assert_eq!(map.notify(&1, 1.tag(5)), 1);
+
+    let waker = waker_fn(|| ());
+    assert_eq!(
+        Pin::new(&mut listener).poll(&mut Context::from_waker(&waker)),
+        std::task::Poll::Ready(5)
+    );
+}
+
+#[test]
+fn garbage_collection() {
+    let map = EventMap::new();
+
+    let a = map.listen(1);
+    let b = map.listen(2);
+    drop(a);
+
+    // Notifying a key without listeners removes its event.
+    assert_eq!(map.notify(&1, 1), 0);
+    assert_eq!(map.len(), 1);
+
+    drop(b);
+    map.remove_idle();
+    assert!(map.is_empty());
+
+    // The event is created again on demand.
+    let mut c = map.listen(1);
+    assert_eq!(map.notify(&1, 1), 1);
+    assert!(is_notified(&mut c));
+}
@@ -0,0 +1,128 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use event_listener::{CapacityError, FixedEvent, FixedListener, IntoNotification};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll<const N: usize, T>(listener: &mut FixedListener<'_, N, T>) -> Poll<T> {
+    let waker = waker_fn(|| ());
+    Pin::new(listener).poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn notify() {
+    let event = FixedEvent::<4>::new();
+
+    let mut l1 = event.listen().unwrap();
+    let mut l2 = event.listen().unwrap();
+    let mut l3 = event.listen().unwrap();
+
+    assert!(poll(&mut l1).is_pending());
+    assert_eq!(event.notify(2), 2);
+    assert_eq!(event.notify(1), 0);
+
+    assert!(poll(&mut l1).is_ready());
+    assert!(poll(&mut l2).is_ready());
+    assert!(poll(&mut l3).is_pending());
+
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(poll(&mut l3).is_ready());
+}
+
+#[test]
+fn capacity() {
+    let event = FixedEvent::<2>::new();
+
+    let l1 = event.listen().unwrap();
+    let l2 = event.listen().unwrap();
+    assert_eq!(event.listen().unwrap_err(), CapacityError);
+    assert_eq!(event.listener_count(), 2);
+
+    // Slots are reused once they are freed.
+    drop(l1);
+    let l3 = event.listen().unwrap();
+    assert_eq!(event.listen().unwrap_err(), CapacityError);
+
+    drop(l2);
+    drop(l3);
+    assert_eq!(event.listener_count(), 0);
+
+    let _l4 = event.listen().unwrap();
+    let _l5 = event.listen().unwrap();
+    assert!(event.listen().is_err());
+}
+
+#[test]
+fn drop_propagates() {
+    let event = FixedEvent::<4>::new();
+
+    let l1 = event.listen().unwrap();
+    let mut l2 = event.listen().unwrap();
+
+    event.notify(1);
+    drop(l1);
+    assert!(poll(&mut l2).is_ready());
+}
+
+#[test]
+fn wakes_task() {
+    let event = FixedEvent::<4>::new();
+    let woken = Arc::new(AtomicUsize::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let mut listener = event.listen().unwrap();
+    assert!(Pin::new(&mut listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(poll(&mut listener).is_ready());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tags() {
+    let event = FixedEvent::<4, usize>::with_tag();
+
+    let mut l1 = event.listen().unwrap();
+    let mut l2 = event.listen().unwrap();
+
+    event.notify(1.tag(5));
+    event.notify(1.additional().tag(6));
+    assert_eq!(poll(&mut l1), Poll::Ready(5));
+    assert_eq!(poll(&mut l2), Poll::Ready(6));
+}
+
+// `FixedEvent::new()` is only `const` without loom.
+#[cfg(not(loom))]
+#[test]
+fn in_static() {
+    static EVENT: FixedEvent<4> = FixedEvent::new();
+
+    let mut listener = EVENT.listen().unwrap();
+    EVENT.notify(1);
+    assert!(poll(&mut listener).is_ready());
+}
+
+#[test]
+fn inspect() {
+    let event = FixedEvent::<4>::new();
+    let _listener1 = event.listen().unwrap();
+    let _listener2 = event.listen().unwrap();
+
+    let count = std::cell::Cell::new(usize::MAX);
+    assert_eq!(event.notify(5.inspect(|| (), |n| count.set(n))), 2);
+    assert_eq!(count.get(), 2);
+}
@@ -0,0 +1,129 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use event_listener::{IntoNotification, LocalEvent, LocalEventListener};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll<T>(listener: &mut LocalEventListener<T>) -> Poll<T> {
+    let waker = waker_fn(|| ());
+    Pin::new(listener).poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn notify() {
+    let event = LocalEvent::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    assert!(poll(&mut l1).is_pending());
+    assert_eq!(event.notify(2), 2);
+    assert_eq!(event.notify(1), 0);
+
+    assert!(poll(&mut l1).is_ready());
+    assert!(poll(&mut l2).is_ready());
+    assert!(poll(&mut l3).is_pending());
+    assert_eq!(event.listener_count(), 1);
+}
+
+#[test]
+fn notify_wakes_task() {
+    let event = LocalEvent::new();
+    let woken = Arc::new(AtomicUsize::new(0));
+
+    let mut listener = event.listen();
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    assert!(Pin::new(&mut listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(poll(&mut listener).is_ready());
+}
+
+#[test]
+fn drop_propagates() {
+    let event = LocalEvent::new();
+
+    let l1 = event.listen();
+    let mut l2 = event.listen();
+
+    event.notify(1);
+    drop(l1);
+    assert!(poll(&mut l2).is_ready());
+}
+
+#[test]
+fn discard_does_not_propagate() {
+    let event = LocalEvent::new();
+
+    let l1 = event.listen();
+    let mut l2 = event.listen();
+
+    event.notify(1);
+    assert!(l1.discard());
+    assert!(poll(&mut l2).is_pending());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn non_send_tags() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let event = LocalEvent::<Rc<Cell<u32>>>::with_tag();
+    let tag = Rc::new(Cell::new(7));
+
+    let mut listener = event.listen();
+    event.notify(1.tag(tag.clone()));
+
+    match poll(&mut listener) {
+        Poll::Ready(received) => assert!(Rc::ptr_eq(&received, &tag)),
+        Poll::Pending => panic!("listener was not notified"),
+    }
+}
+
+#[test]
+fn sticky() {
+    let event = LocalEvent::new();
+    event.notify(1.sticky());
+
+    let mut listener = event.listen();
+    assert!(poll(&mut listener).is_ready());
+}
+
+#[test]
+fn reuse_slots() {
+    let event = LocalEvent::new();
+
+    for _ in 0..3 {
+        let listeners: Vec<_> = (0..4).map(|_| event.listen()).collect();
+        assert_eq!(event.notify(usize::MAX), 4);
+        drop(listeners);
+        assert_eq!(event.listener_count(), 0);
+    }
+}
+
+#[test]
+fn inspect() {
+    let event = LocalEvent::new();
+    let _listener1 = event.listen();
+    let _listener2 = event.listen();
+
+    let count = std::cell::Cell::new(usize::MAX);
+    assert_eq!(event.notify(5.inspect(|| (), |n| count.set(n))), 2);
+    assert_eq!(count.get(), 2);
+}
@@ -4,6 +4,8 @@ use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::Context;
 
+#[cfg(feature = "std")]
+use event_listener::Listener;
 use event_listener::{Event, EventListener};
 use waker_fn::waker_fn;
 
@@ -209,3 +211,39 @@ fn notify_all_fair() {
             .is_ready());
     })
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_across_threads() {
+    loom::model(|| {
+        let event = loom::sync::Arc::new(Event::new());
+        let listener = event.listen();
+
+        let notifier = loom::thread::spawn({
+            let event = event.clone();
+            move || event.notify(1)
+        });
+
+        listener.wait();
+        assert_eq!(notifier.join().unwrap(), 1);
+    });
+}
+
+#[test]
+fn drop_while_notifying() {
+    loom::model(|| {
+        let event = loom::sync::Arc::new(Event::new());
+        let l1 = event.listen();
+        let mut l2 = event.listen();
+
+        let notifier = loom::thread::spawn({
+            let event = event.clone();
+            move || event.notify(1)
+        });
+
+        // Whether or not `l1` was notified first, the notification ends up at `l2`.
+        drop(l1);
+        notifier.join().unwrap();
+        assert!(is_notified(&mut l2));
+    });
+}
@@ -0,0 +1,57 @@
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use event_listener::{Event, ListenerExt};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(feature = "std")]
+#[test]
+fn map_tag() {
+    use event_listener::IntoNotification;
+
+    let event = Event::<u32>::with_tag();
+    let mut listener = Box::pin(event.listen().map(|n| n.to_string()));
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(listener.as_mut().poll(&mut cx), Poll::Pending);
+
+    event.notify(1.tag(5));
+    assert_eq!(
+        listener.as_mut().poll(&mut cx),
+        Poll::Ready("5".to_string())
+    );
+}
+
+#[test]
+fn dropped_map_passes_notification_on() {
+    let event = Event::new();
+    let mapped = event.listen().map(|()| 1);
+    let next = event.listen();
+
+    event.notify(1);
+    drop(mapped);
+
+    let waker = waker_fn(|| ());
+    let mut next = Box::pin(next);
+    assert_eq!(
+        next.as_mut().poll(&mut Context::from_waker(&waker)),
+        Poll::Ready(())
+    );
+}
+
+#[test]
+#[should_panic = "`Map` polled after completion"]
+fn poll_after_completion() {
+    let event = Event::new();
+    let mut mapped = Box::pin(event.listen().map(|()| ()));
+    event.notify(1);
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert!(mapped.as_mut().poll(&mut cx).is_ready());
+    let _ = mapped.as_mut().poll(&mut cx);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{merge, Event, EventListener, IntoNotification};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll(listener: &mut EventListener<usize>) -> Option<usize> {
+    let waker = waker_fn(|| ());
+    match Pin::new(listener).poll(&mut Context::from_waker(&waker)) {
+        std::task::Poll::Ready(index) => Some(index),
+        std::task::Poll::Pending => None,
+    }
+}
+
+#[test]
+fn index_as_tag() {
+    let events = [Event::new(), Event::new(), Event::new()];
+    let merged = merge(&events);
+    assert_eq!(merged.len(), 3);
+
+    let mut listener1 = merged.listen();
+    let mut listener2 = merged.listen();
+    assert_eq!(poll(&mut listener1), None);
+
+    events[2].notify(1);
+    assert_eq!(poll(&mut listener1), Some(2));
+    assert_eq!(poll(&mut listener2), None);
+
+    events[0].notify(1.additional());
+    assert_eq!(poll(&mut listener2), Some(0));
+}
+
+#[test]
+fn stops_on_drop() {
+    let event = Event::new();
+    let merged = merge([&event]);
+    let mut listener = merged.listen();
+    drop(merged);
+
+    event.notify(1);
+    assert_eq!(poll(&mut listener), None);
+}
@@ -0,0 +1,89 @@
+use std::future::Future;
+use std::task::Context;
+
+use event_listener::primitives::Mutex;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn try_lock() {
+    let mutex = Mutex::new(1);
+
+    let mut guard = mutex.try_lock().unwrap();
+    assert!(mutex.try_lock().is_none());
+    *guard += 1;
+    drop(guard);
+
+    assert_eq!(*mutex.try_lock().unwrap(), 2);
+    assert_eq!(mutex.into_inner(), 2);
+}
+
+#[test]
+fn unlock_wakes_waiter() {
+    let mutex = Mutex::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let guard = mutex.try_lock().unwrap();
+    let mut lock = Box::pin(mutex.lock());
+    assert!(lock.as_mut().poll(&mut cx).is_pending());
+
+    drop(guard);
+    assert!(lock.as_mut().poll(&mut cx).is_ready());
+}
+
+#[test]
+fn not_poisoned() {
+    let mutex = Mutex::new(0);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _guard = mutex.try_lock().unwrap();
+        panic!("oops");
+    }));
+    assert!(result.is_err());
+
+    assert!(mutex.try_lock().is_some());
+}
+
+#[test]
+fn debug() {
+    let mutex = Mutex::new(5);
+    assert_eq!(format!("{:?}", mutex), "Mutex { data: 5 }");
+
+    let guard = mutex.try_lock().unwrap();
+    assert_eq!(format!("{:?}", mutex), "Mutex { data: <locked> }");
+    assert_eq!(format!("{:?}", guard), "5");
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn lock_blocking() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let mutex = Arc::new(Mutex::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let mutex = mutex.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *mutex.lock_blocking() += 1;
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*mutex.lock_blocking(), 800);
+
+    let guard = mutex.lock_blocking();
+    assert!(mutex.lock_timeout(Duration::from_millis(10)).is_none());
+    drop(guard);
+    assert!(mutex.lock_timeout(Duration::from_millis(10)).is_some());
+}
@@ -1,9 +1,11 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::Context;
 
-use event_listener::{Event, EventListener};
+use event_listener::{Event, EventListener, IntoNotification, Listener};
 use waker_fn::waker_fn;
 
 #[cfg(target_family = "wasm")]
@@ -16,6 +18,37 @@ fn is_notified(listener: &mut EventListener) -> bool {
         .is_ready()
 }
 
+/// An allocator that counts the allocations made by a thread inside `count_allocations()`.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| {
+            if let Some(n) = count.get() {
+                count.set(Some(n + 1));
+            }
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+fn count_allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(Some(0)));
+    f();
+    ALLOCATIONS.with(|count| count.take()).unwrap()
+}
+
 #[test]
 fn notify() {
     let event = Event::new();
@@ -190,3 +223,1485 @@ fn notify_all_fair() {
         .poll(&mut Context::from_waker(&waker3))
         .is_ready());
 }
+
+#[test]
+fn notify_sticky() {
+    let event = Event::new();
+
+    // Nobody is listening, so only a single permit is stored.
+    assert_eq!(event.notify(1.sticky()), 0);
+    assert_eq!(event.notify(3.sticky()), 0);
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+
+    // There are listeners now, so this behaves like a normal notification.
+    assert_eq!(event.notify(1.sticky()), 1);
+    assert!(is_notified(&mut l2));
+
+    let mut l3 = event.listen();
+    assert!(!is_notified(&mut l3));
+}
+
+#[test]
+fn notify_non_sticky_is_lost() {
+    let event = Event::new();
+
+    assert_eq!(event.notify(1), 0);
+    let mut l1 = event.listen();
+    assert!(!is_notified(&mut l1));
+}
+
+#[test]
+fn listener_count() {
+    let event = Event::new();
+    assert!(event.is_empty());
+
+    let mut l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+    assert_eq!(event.listener_count(), 3);
+    assert_eq!(event.notified_count(), 0);
+
+    event.notify(2);
+    assert_eq!(event.notified_count(), 2);
+
+    assert!(is_notified(&mut l1));
+    assert_eq!(event.listener_count(), 2);
+    assert_eq!(event.notified_count(), 1);
+
+    drop(l2);
+    drop(l3);
+    assert!(event.is_empty());
+    assert_eq!(event.notified_count(), 0);
+}
+
+#[test]
+fn notify_count() {
+    let event = Event::new();
+    assert_eq!(event.notify_count(1), 0);
+    assert_eq!(event.notify_count(1.sticky()), 0);
+
+    // The first listener takes the sticky permit.
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    assert_eq!(event.notify_count(2), 1);
+    assert_eq!(event.notify_count(1.additional()), 1);
+    assert_eq!(event.notify_count(usize::MAX), 0);
+
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+}
+
+#[test]
+fn clone_shares_listeners() {
+    let event = Event::new();
+    let handle = event.clone();
+
+    let mut l1 = event.listen();
+    let mut l2 = handle.listen();
+    assert_eq!(event.listener_count(), 2);
+    assert_eq!(handle.listener_count(), 2);
+
+    // Dropping the original handle keeps the listeners alive.
+    drop(event);
+
+    assert_eq!(handle.notify(2), 2);
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+}
+
+// `WeakEvent` is not available under loom.
+#[cfg(not(loom))]
+#[test]
+fn weak_event() {
+    let event = Event::new();
+    let weak = event.downgrade();
+
+    let mut listener = event.listen();
+    drop(event);
+
+    // The listener keeps the list alive.
+    let event = weak.upgrade().unwrap();
+    assert_eq!(event.notify(1), 1);
+    assert!(is_notified(&mut listener));
+
+    drop(event);
+    drop(listener);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn notifier_and_waiter() {
+    let (notifier, waiter) = Event::new().pair();
+    let waiter2 = waiter.clone();
+
+    let mut l1 = waiter.listen();
+    let mut l2 = waiter2.listen();
+    assert_eq!(notifier.listener_count(), 2);
+
+    assert_eq!(notifier.clone().notify(2), 2);
+    assert!(waiter.is_notified());
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+}
+
+#[test]
+fn try_listen() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+
+    let mut l1 = event.try_listen().unwrap();
+    let mut l2 = event.try_listen().unwrap();
+    assert_eq!(event.listener_count(), 2);
+
+    assert_eq!(event.notify(1), 1);
+    assert!(Pin::new(&mut l1)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready());
+    assert!(Pin::new(&mut l2)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+}
+
+#[test]
+fn notify_from_isr() {
+    let event = Event::new();
+
+    // No listeners, so nothing happens.
+    event.notify_from_isr(1);
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    event.notify_from_isr(1);
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn notify_from_isr_wakes() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Event::new();
+    let mut listener = event.listen();
+
+    // The listener is awaited on another thread, and only an interrupt handler notifies it.
+    let polled = Arc::new(AtomicBool::new(false));
+    let (done, finished) = mpsc::channel();
+    thread::spawn({
+        let polled = polled.clone();
+        move || {
+            futures_lite::future::block_on(futures_lite::future::poll_fn(|cx| {
+                let poll = Pin::new(&mut listener).poll(cx);
+                polled.store(true, Ordering::SeqCst);
+                poll
+            }));
+            done.send(()).unwrap();
+        }
+    });
+    while !polled.load(Ordering::SeqCst) {
+        thread::yield_now();
+    }
+
+    event.notify_from_isr(1);
+    finished
+        .recv_timeout(Duration::from_secs(10))
+        .expect("the listener was not woken");
+
+    // More listeners than are notified at a time are all woken.
+    let woken = Arc::new(AtomicUsize::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let mut listeners: Vec<_> = (0..20).map(|_| event.listen()).collect();
+    for listener in &mut listeners {
+        assert!(Pin::new(listener)
+            .poll(&mut Context::from_waker(&waker))
+            .is_pending());
+    }
+
+    event.notify_from_isr(usize::MAX);
+    assert_eq!(woken.load(Ordering::SeqCst), 20);
+    for listener in &mut listeners {
+        assert!(is_notified(listener));
+    }
+}
+
+#[test]
+fn notify_from_isr_does_not_allocate() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+
+    // More listeners than are notified at a time, all with tasks to wake.
+    let mut listeners: Vec<_> = (0..20).map(|_| event.listen()).collect();
+    for listener in &mut listeners {
+        assert!(Pin::new(listener)
+            .poll(&mut Context::from_waker(&waker))
+            .is_pending());
+    }
+
+    assert_eq!(count_allocations(|| event.notify_from_isr(usize::MAX)), 0);
+    for listener in &mut listeners {
+        assert!(is_notified(listener));
+    }
+}
+
+#[cfg(all(
+    feature = "critical-section",
+    not(feature = "std"),
+    not(target_family = "wasm")
+))]
+#[test]
+fn notify_in_critical_section() {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(Event::new());
+    let mut listener = event.listen();
+
+    // Another thread enters a critical section and stays there.
+    let (entered, wait_entered) = mpsc::channel();
+    let (release, wait_release) = mpsc::channel::<()>();
+    let holder = thread::spawn(move || {
+        critical_section::with(|_| {
+            entered.send(()).unwrap();
+            wait_release.recv().unwrap();
+        })
+    });
+    wait_entered.recv().unwrap();
+
+    // The list can't be locked meanwhile, so the notification waits instead of being queued.
+    let notifier = thread::spawn({
+        let event = event.clone();
+        move || event.notify(1)
+    });
+    thread::sleep(Duration::from_millis(50));
+    assert!(!notifier.is_finished());
+
+    release.send(()).unwrap();
+    holder.join().unwrap();
+    assert_eq!(notifier.join().unwrap(), 1);
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+fn notify_from_isr_deferred() {
+    let event = Arc::new(Event::new());
+
+    // This waker runs while the list is locked, like an interrupt handler that fires while the
+    // interrupted code is notifying the event.
+    let waker = waker_fn({
+        let event = event.clone();
+        move || event.notify_from_isr(2)
+    });
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert!(Pin::new(&mut l1)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    assert_eq!(event.notify(1), 1);
+    assert!(is_notified(&mut l2));
+}
+
+#[test]
+fn listen_ref() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+
+    let l1 = event.listen_ref();
+    let l2 = event.listen_ref();
+    futures_lite::pin!(l1);
+    futures_lite::pin!(l2);
+
+    // A notification before listening is lost.
+    event.notify(1);
+    l1.as_mut().listen();
+    l2.as_mut().listen();
+    assert_eq!(event.listener_count(), 2);
+
+    assert!(l1
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    event.notify(1);
+    assert!(l1
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready());
+    assert!(l2
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+}
+
+#[test]
+fn listen_to_rebinds() {
+    let first = Event::new();
+    let second = Event::new();
+
+    let mut listener = EventListener::empty();
+    listener.listen_to(&first);
+    assert_eq!(first.listener_count(), 1);
+
+    // Moving to another event passes the notification on.
+    let mut other = first.listen();
+    first.notify(1);
+    listener.listen_to(&second);
+    assert_eq!(first.listener_count(), 1);
+    assert_eq!(second.listener_count(), 1);
+    assert!(is_notified(&mut other));
+
+    second.notify(1);
+    assert!(is_notified(&mut listener));
+
+    // Listen again after completion.
+    listener.listen_to(&second);
+    assert!(!is_notified(&mut listener));
+    second.notify(1);
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+fn rearm() {
+    let event = Event::new();
+    let mut listener = event.listen();
+
+    // Rearming a registered listener keeps its place.
+    let mut other = event.listen();
+    listener.rearm();
+    event.notify(1);
+    assert!(is_notified(&mut listener));
+    assert!(!is_notified(&mut other));
+
+    listener.rearm();
+    assert_eq!(event.listener_count(), 2);
+    assert!(!is_notified(&mut listener));
+
+    event.notify(2);
+    assert!(is_notified(&mut other));
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+fn debug() {
+    let event = Event::new();
+    assert_eq!(format!("{:?}", event), "Event(<uninitialized>)");
+
+    let mut l1 = event.listen();
+    let l2 = event.listen();
+    assert_eq!(
+        format!("{:?}", event),
+        "Event { listeners_notified: 0, listeners_total: 2 }"
+    );
+    assert_eq!(format!("{:?}", l1), "EventListener { state: Created }");
+
+    assert!(!is_notified(&mut l1));
+    assert_eq!(format!("{:?}", l1), "EventListener { state: Registered }");
+
+    event.notify(1);
+    assert_eq!(
+        format!("{:?}", event),
+        "Event { listeners_notified: 1, listeners_total: 2 }"
+    );
+    assert_eq!(format!("{:?}", l1), "EventListener { state: Notified }");
+    assert_eq!(format!("{:?}", l2), "EventListener { state: Created }");
+}
+
+#[test]
+fn notified() {
+    let event = Event::new();
+
+    // Registered right away, before the first poll.
+    let notified = event.notified();
+    assert_eq!(event.notify(1), 1);
+    futures_lite::future::block_on(notified);
+
+    let mut notified = event.notified();
+    assert!(!is_notified(&mut notified));
+    assert_eq!(event.notify(1), 1);
+    assert!(is_notified(&mut notified));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn event_wait() {
+    use event_listener::WaitTimeoutError;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let event = Arc::new(Event::with_tag());
+
+    let handle = thread::spawn({
+        let event = event.clone();
+        move || {
+            // Keep notifying until the waiter has registered.
+            while event.notify(1.tag(7)) == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    });
+
+    assert_eq!(event.wait(), 7);
+    handle.join().unwrap();
+
+    assert_eq!(
+        event.wait_timeout(Duration::from_millis(10)),
+        Err(WaitTimeoutError)
+    );
+    assert_eq!(event.wait_deadline(Instant::now()), Err(WaitTimeoutError));
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_timeout_remaining() {
+    use event_listener::WaitTimeoutError;
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(Event::new());
+    let timeout = Duration::from_secs(10);
+
+    // Notified before waiting, so hardly any time passes.
+    let listener = event.listen();
+    event.notify(1);
+    let ((), remaining) = listener.wait_timeout_remaining(timeout).unwrap();
+    assert!(remaining > Duration::from_secs(5) && remaining <= timeout);
+
+    // Notified while waiting.
+    let listener = event.listen();
+    let handle = thread::spawn({
+        let event = event.clone();
+        move || {
+            thread::sleep(Duration::from_millis(50));
+            event.notify(1);
+        }
+    });
+    let ((), remaining) = listener.wait_timeout_remaining(timeout).unwrap();
+    assert!(remaining <= timeout - Duration::from_millis(50));
+    handle.join().unwrap();
+
+    let listener = event.listen();
+    assert_eq!(
+        listener.wait_timeout_remaining(Duration::from_millis(10)),
+        Err(WaitTimeoutError)
+    );
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn event_wait_until() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let event = Arc::new(Event::new());
+
+    let handle = thread::spawn({
+        let counter = counter.clone();
+        let event = event.clone();
+        move || {
+            for _ in 0..5 {
+                thread::sleep(Duration::from_millis(1));
+                counter.fetch_add(1, Ordering::SeqCst);
+                event.notify(usize::MAX);
+            }
+        }
+    });
+
+    event.wait_until(|| counter.load(Ordering::SeqCst) >= 3);
+    assert!(event.wait_until_timeout(Duration::from_secs(10), || {
+        counter.load(Ordering::SeqCst) == 5
+    }));
+    handle.join().unwrap();
+
+    // Nobody changes the counter anymore.
+    assert!(!event.wait_until_timeout(Duration::from_millis(10), || {
+        counter.load(Ordering::SeqCst) > 5
+    }));
+    assert!(!event.wait_until_deadline(Instant::now(), || false));
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[test]
+fn parent() {
+    let root = Event::new();
+    let parent = Event::with_parent(&root, usize::MAX);
+    let child1 = Event::with_parent(&parent, 1);
+    let child2 = Event::<()>::with_parent(&parent, 1);
+
+    let mut root1 = root.listen();
+    let mut root2 = root.listen();
+    let mut parent1 = parent.listen();
+    let mut parent2 = parent.listen();
+    let mut child = child1.listen();
+
+    // Notifying a child notifies its parent, even without listeners of its own.
+    assert_eq!(child2.notify(1), 0);
+    assert!(!is_notified(&mut child));
+    assert!(is_notified(&mut parent1));
+    assert!(!is_notified(&mut parent2));
+    assert!(is_notified(&mut root1));
+    assert!(is_notified(&mut root2));
+
+    child1.notify(1);
+    assert!(is_notified(&mut child));
+    assert!(is_notified(&mut parent2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn forward_to() {
+    let source = Event::new();
+    let target1 = Event::new();
+    let target2 = Event::new();
+
+    let forward1 = source.forward_to(&target1);
+    let _forward2 = source.forward_to(&target2);
+
+    let mut listener1 = target1.listen();
+    let mut listener2 = target1.listen();
+    let mut listener3 = target2.listen();
+
+    // The count and strategy of the notification are forwarded.
+    source.notify(1);
+    assert!(is_notified(&mut listener1));
+    assert!(!is_notified(&mut listener2));
+    assert!(is_notified(&mut listener3));
+
+    source.notify(1.additional());
+    assert!(is_notified(&mut listener2));
+
+    // Dropping the guard stops forwarding to its target only.
+    drop(forward1);
+    let mut listener1 = target1.listen();
+    let mut listener2 = target2.listen();
+    source.notify(1);
+    assert!(!is_notified(&mut listener1));
+    assert!(is_notified(&mut listener2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn map_tag() {
+    let event = Event::<u64>::with_tag();
+
+    let mut listener1 = event.listen();
+    let mut listener2 = event.listen();
+    let mut listener3 = event.listen();
+
+    let mut calls = 0;
+    event.notify(
+        1.tag_with(|| 5u32)
+            .map_tag(|tag| {
+                calls += 1;
+                u64::from(tag) * 2
+            })
+            .additional(),
+    );
+    assert_eq!(calls, 1);
+
+    // Composes with the other combinators in either order.
+    event.notify(1.tag(3u8).additional().map_tag(u64::from));
+    event.notify(1.additional().tag(4u8).map_tag(u64::from).relaxed());
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert!(Pin::new(&mut listener1).poll(&mut cx) == std::task::Poll::Ready(10));
+    assert!(Pin::new(&mut listener2).poll(&mut cx) == std::task::Poll::Ready(3));
+    assert!(Pin::new(&mut listener3).poll(&mut cx) == std::task::Poll::Ready(4));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_with_tags() {
+    let event = Event::<usize>::with_tag();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    // Nobody is listening, so no tags are taken.
+    let mut tags = 0..5;
+    assert_eq!(event.notify_with_tags(&mut tags), 0);
+    assert_eq!(tags, 0..5);
+
+    let mut listener1 = event.listen();
+    let mut listener2 = event.listen();
+    let mut listener3 = event.listen();
+    event.notify(1.tag(100));
+
+    // Already notified listeners are skipped.
+    assert_eq!(event.notify_with_tags(&mut tags), 2);
+    assert_eq!(tags, 2..5);
+    assert!(Pin::new(&mut listener1).poll(&mut cx) == std::task::Poll::Ready(100));
+    assert!(Pin::new(&mut listener2).poll(&mut cx) == std::task::Poll::Ready(0));
+    assert!(Pin::new(&mut listener3).poll(&mut cx) == std::task::Poll::Ready(1));
+
+    // Running out of tags leaves the rest of the listeners alone.
+    let mut listener1 = event.listen();
+    let mut listener2 = event.listen();
+    assert_eq!(event.notify_with_tags(Some(7)), 1);
+    assert!(Pin::new(&mut listener1).poll(&mut cx) == std::task::Poll::Ready(7));
+    assert!(Pin::new(&mut listener2).poll(&mut cx).is_pending());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_all_with_tag() {
+    let event = Event::<usize>::with_tag();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(event.notify_all_with_tag(1), 0);
+
+    let mut listener1 = event.listen();
+    let mut listener2 = event.listen();
+    let mut listener3 = event.listen();
+    event.notify(1.tag(2));
+
+    assert_eq!(event.notify_all_with_tag(3), 2);
+    assert!(Pin::new(&mut listener1).poll(&mut cx) == std::task::Poll::Ready(2));
+    assert!(Pin::new(&mut listener2).poll(&mut cx) == std::task::Poll::Ready(3));
+    assert!(Pin::new(&mut listener3).poll(&mut cx) == std::task::Poll::Ready(3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tag_with_index() {
+    let event = Event::<usize>::with_tag();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut listener1 = event.listen();
+    let mut listener2 = event.listen();
+    let mut listener3 = event.listen();
+    let mut listener4 = event.listen();
+
+    // Positions count only the listeners notified by this notification.
+    event.notify(1.tag(100));
+    event.notify(2.additional().tag_with_index(|index| index * 10));
+    event.notify(5.tag_with_index(|index| index + 1));
+
+    assert!(Pin::new(&mut listener1).poll(&mut cx) == std::task::Poll::Ready(100));
+    assert!(Pin::new(&mut listener2).poll(&mut cx) == std::task::Poll::Ready(0));
+    assert!(Pin::new(&mut listener3).poll(&mut cx) == std::task::Poll::Ready(10));
+    assert!(Pin::new(&mut listener4).poll(&mut cx) == std::task::Poll::Ready(1));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_if() {
+    let event = Event::new();
+
+    let mut plain = event.listen();
+    let mut odd1 = event.listen_with_meta(1);
+    let mut even = event.listen_with_meta(2);
+    let mut odd2 = event.listen_with_meta(3);
+    let mut odd3 = event.listen_with_meta(5);
+
+    assert_eq!(event.notify_if(2, |meta| meta % 2 == 1), 2);
+    assert!(!is_notified(&mut plain));
+    assert!(!is_notified(&mut even));
+    assert!(is_notified(&mut odd1));
+    assert!(is_notified(&mut odd2));
+
+    // Skipped listeners are still notified by plain notifications, in order.
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut plain));
+    assert!(!is_notified(&mut even));
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut even));
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut odd3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_if_counts_matching() {
+    let event = Event::new();
+
+    let a1 = event.listen_with_meta(1);
+    let b = event.listen_with_meta(2);
+    let mut a2 = event.listen_with_meta(1);
+
+    // A notified listener that doesn't match doesn't count.
+    event.notify(1);
+    assert_eq!(event.notify_if(1, |meta| meta == 2), 1);
+    assert_eq!(event.notify_if(1, |meta| meta == 2), 0);
+    assert_eq!(event.notify_if(2, |meta| meta == 1), 1);
+
+    // Every listener is notified, so dropping some of them doesn't pass anything on.
+    drop(a1);
+    drop(b);
+    assert!(is_notified(&mut a2));
+    assert_eq!(event.listener_count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn priority() {
+    let event = Event::new();
+
+    let mut low1 = event.listen();
+    let mut high1 = event.listen_with_priority(2);
+    let mut mid = event.listen_with_priority(1);
+    let mut low2 = event.listen();
+    let mut high2 = event.listen_with_priority(2);
+
+    event.notify(1);
+    assert!(is_notified(&mut high1));
+
+    // Notified listeners keep their notification.
+    event.notify(1);
+    let mut high3 = event.listen_with_priority(2);
+    assert!(is_notified(&mut high2));
+    assert!(!is_notified(&mut high3));
+
+    event.notify(2);
+    assert!(is_notified(&mut high3));
+    assert!(is_notified(&mut mid));
+    assert!(!is_notified(&mut low1));
+
+    event.notify(2);
+    assert!(is_notified(&mut low1));
+    assert!(is_notified(&mut low2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_last() {
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+    let mut l4 = event.listen();
+
+    assert_eq!(event.notify_last(2), 2);
+    assert!(!is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+    assert!(is_notified(&mut l4));
+
+    // Notified listeners count towards the notification.
+    let mut l5 = event.listen();
+    event.notify(1);
+    assert_eq!(event.notify_last(1), 0);
+    assert_eq!(event.notify_last(1.additional()), 1);
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+    assert!(is_notified(&mut l5));
+
+    // Nobody is left after this, and a sticky notification stores a permit.
+    assert_eq!(event.notify_last(1.additional()), 1);
+    assert!(is_notified(&mut l2));
+    event.notify_last(1.sticky());
+    assert!(is_notified(&mut event.listen()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn discard_tag() {
+    let event = Event::<Box<usize>>::with_tag();
+
+    let listener1 = event.listen();
+    let listener2 = event.listen();
+    event.notify(1.tag(Box::new(5)));
+
+    // The tag is handed back instead of being passed on.
+    assert_eq!(listener1.discard_tag(), Some(Box::new(5)));
+    assert!(!event.is_notified());
+    assert_eq!(listener2.discard_tag(), None);
+
+    let listener = event.listen_closeable();
+    event.close();
+    assert_eq!(listener.discard_tag(), None);
+}
+
+#[test]
+fn coalesced() {
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    assert_eq!(event.notify(2.additional().coalesced()), 2);
+    assert!(is_notified(&mut l1));
+
+    // `l2` hasn't observed its notification yet.
+    assert_eq!(event.notify(1.additional().coalesced()), 0);
+    assert_eq!(event.notify_coalesced(1), 0);
+    assert!(is_notified(&mut l2));
+
+    // Nobody is notified anymore, so `l3` is.
+    assert_eq!(event.notify_coalesced(usize::MAX), 1);
+    assert!(is_notified(&mut l3));
+
+    // A sticky notification left for a future listener is pending as well.
+    event.notify(1.sticky());
+    assert_eq!(event.notify(1.sticky().coalesced()), 0);
+    assert!(is_notified(&mut event.listen()));
+    assert!(!is_notified(&mut event.listen()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batch() {
+    let event = Event::<usize>::with_tag();
+
+    let l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+
+    let mut batch = event.batch();
+    batch.notify(1.tag(1)).notify(1.additional().tag(2));
+    assert_eq!(batch.len(), 2);
+    assert!(!event.is_notified());
+
+    // Notifications are performed in the order they were recorded.
+    assert_eq!(batch.flush(), 2);
+    assert!(batch.is_empty());
+    assert_eq!(l1.wait(), 1);
+    assert_eq!(l2.wait(), 2);
+
+    // Dropping the batch flushes it.
+    batch.notify(1.tag(3));
+    drop(batch);
+    assert_eq!(l3.wait(), 3);
+}
+
+#[test]
+fn pause() {
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+    let mut l4 = event.listen();
+
+    // Nothing to deliver.
+    assert_eq!(event.resume(), 0);
+
+    event.pause();
+    assert_eq!(event.notify(1), 0);
+    assert_eq!(event.notify(2), 0);
+    assert_eq!(event.notify(1.additional()), 0);
+    assert!(!is_notified(&mut l1));
+
+    // The largest regular notification and the sum of the additional ones.
+    assert!(event.is_paused());
+    assert_eq!(event.resume(), 3);
+    assert!(!event.is_paused());
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+
+    // Notifications go through right away again.
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut l4));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn notify_sync() {
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+
+    let waiters: Vec<_> = (0..2)
+        .map(|_| {
+            let listener = event.listen();
+            thread::spawn(move || listener.wait())
+        })
+        .collect();
+    let unnotified = event.listen();
+
+    // Only the notified listeners have to consume their notification.
+    assert_eq!(event.notify_sync_blocking(2), 2);
+    assert_eq!(event.total_listeners(), 1);
+    for waiter in waiters {
+        waiter.join().unwrap();
+    }
+
+    // Discarding a notification consumes it as well.
+    let discarder = thread::spawn(move || {
+        thread::sleep(std::time::Duration::from_millis(10));
+        unnotified.discard()
+    });
+    assert_eq!(
+        futures_lite::future::block_on(event.notify_sync(1.additional())),
+        1
+    );
+    assert!(discarder.join().unwrap());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_for_listeners() {
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+
+    // Nothing to wait for.
+    event.wait_for_listeners_blocking(0);
+    futures_lite::future::block_on(event.wait_for_listeners(0));
+
+    let waiters: Vec<_> = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.listen().wait())
+        })
+        .collect();
+
+    futures_lite::future::block_on(event.wait_for_listeners(2));
+    event.wait_for_listeners_blocking(4);
+    assert_eq!(event.total_listeners(), 4);
+
+    assert_eq!(event.notify(usize::MAX), 4);
+    for waiter in waiters {
+        waiter.join().unwrap();
+    }
+}
+
+#[test]
+fn wait_until_async() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let counter = AtomicUsize::new(0);
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = Box::pin(event.wait_until_async(|| counter.load(Ordering::SeqCst) == 2));
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+
+    // A notification without the condition being true doesn't complete the future.
+    counter.store(1, Ordering::SeqCst);
+    event.notify(1);
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+
+    counter.store(2, Ordering::SeqCst);
+    event.notify(1);
+    assert!(wait.as_mut().poll(&mut cx).is_ready());
+
+    // The condition is true right away.
+    assert!(Box::pin(event.wait_until_async(|| true))
+        .as_mut()
+        .poll(&mut cx)
+        .is_ready());
+}
+
+#[test]
+fn check() {
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    // Checking doesn't replace the registered task.
+    let woken = Arc::new(Mutex::new(false));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || *woken.lock().unwrap() = true
+    });
+    assert!(Pin::new(&mut l2)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+    assert_eq!(l2.check(), None);
+
+    event.notify(2);
+    assert!(*woken.lock().unwrap());
+    assert_eq!(l1.check(), Some(()));
+    assert_eq!(l1.check(), None);
+    assert_eq!(l2.check(), Some(()));
+
+    let mut closeable = event.listen_closeable();
+    assert_eq!(closeable.check(), None);
+    event.close();
+    assert_eq!(closeable.check(), Some(Err(event_listener::Closed)));
+}
+
+#[test]
+fn register() {
+    use event_listener::Registration;
+
+    let event = Event::new();
+    let mut listener = event.listen();
+
+    let woken = Arc::new(Mutex::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || *woken.lock().unwrap() += 1
+    });
+
+    assert_eq!(listener.register(&waker), Registration::Registered);
+    assert_eq!(listener.register(&waker), Registration::Registered);
+    event.notify(1);
+    assert_eq!(*woken.lock().unwrap(), 1);
+    assert_eq!(listener.register(&waker), Registration::Notified(()));
+
+    // Closing the event is only reported to closeable listeners. Plain listeners get the tag.
+    let mut listener = event.listen();
+    let mut closeable = event.listen_closeable();
+    assert_eq!(closeable.register(&waker), Registration::Registered);
+    event.close();
+    assert_eq!(listener.register(&waker), Registration::Notified(()));
+    assert_eq!(closeable.register(&waker), Registration::Closed);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn peek_tag() {
+    let event = Event::<u32>::with_tag();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert_eq!(l1.peek_tag(|&tag| tag), None);
+
+    event.notify(1.tag(5));
+    assert_eq!(l1.peek_tag(|&tag| tag * 2), Some(10));
+    assert_eq!(l1.peek_tag(|&tag| tag), Some(5));
+    assert_eq!(l2.peek_tag(|&tag| tag), None);
+
+    // Peeking doesn't consume the notification, so it can still be passed on.
+    drop(l1);
+    assert_eq!(l2.peek_tag(|&tag| tag), Some(5));
+    assert_eq!(l2.check(), Some(5));
+
+    let mut closeable = event.listen_closeable();
+    event.notify(1.tag(3));
+    assert_eq!(closeable.peek_tag(|&tag| tag), Some(3));
+    assert_eq!(closeable.check(), Some(Ok(3)));
+}
+
+#[test]
+fn epoch() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let epoch = event.epoch();
+    assert_eq!(event.epoch(), epoch);
+
+    // Notifications advance the epoch, even if nobody is listening.
+    event.notify(1);
+    let next = event.epoch();
+    assert_ne!(next, epoch);
+    assert!(Box::pin(event.wait_epoch(epoch))
+        .as_mut()
+        .poll(&mut cx)
+        .is_ready());
+
+    // Held back notifications advance it once they are delivered.
+    event.pause();
+    event.notify(1);
+    assert_eq!(event.epoch(), next);
+    event.resume();
+    assert_ne!(event.epoch(), next);
+
+    // Without a notification, the future waits for the next one.
+    let epoch = event.epoch();
+    let mut wait = Box::pin(event.wait_epoch(epoch));
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+    event.notify(1);
+    assert!(wait.as_mut().poll(&mut cx).is_ready());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn epoch_notify_last_and_if() {
+    type Notifier = fn(&Event) -> usize;
+    let notifiers: [Notifier; 2] = [
+        |event| event.notify_last(1),
+        |event| event.notify_if(1, |meta| meta == 0),
+    ];
+
+    for notify in notifiers {
+        let event = Arc::new(Event::new());
+        let epoch = event.epoch();
+
+        // Whether or not the waiter got to listen first, the notification must wake it up.
+        let waiter = std::thread::spawn({
+            let event = event.clone();
+            move || event.wait_epoch_blocking(epoch)
+        });
+
+        notify(&event);
+        assert_ne!(event.epoch(), epoch);
+        waiter.join().unwrap();
+    }
+}
+
+#[test]
+fn prepare_wait() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let ticket = event.prepare_wait();
+    event.cancel_wait(ticket);
+
+    // Notified in between.
+    let ticket = event.prepare_wait();
+    event.notify(1);
+    assert!(Box::pin(event.commit_wait(ticket))
+        .as_mut()
+        .poll(&mut cx)
+        .is_ready());
+
+    // Notified after committing.
+    let ticket = event.prepare_wait();
+    let mut wait = Box::pin(event.commit_wait(ticket));
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+    event.notify(1);
+    assert!(wait.as_mut().poll(&mut cx).is_ready());
+}
+
+#[test]
+fn repoll_with_same_waker() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let event = Event::new();
+    let other = event.listen();
+    let mut listener = event.listen();
+
+    let woken = Arc::new(AtomicUsize::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let mut cx = Context::from_waker(&waker);
+
+    // Polling again with the same waker keeps it registered.
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+
+    // Another listener being notified doesn't complete this one.
+    event.notify(1);
+    assert!(other.discard());
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+    assert_eq!(woken.load(Ordering::SeqCst), 0);
+
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_ready());
+
+    // A different waker replaces the registered one.
+    let mut listener = event.listen();
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+    assert!(!is_notified(&mut listener));
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+fn with_listener() {
+    let event = Event::new();
+
+    let notified = event.with_listener(|mut listener| {
+        assert_eq!(event.listener_count(), 1);
+        event.notify(1);
+        listener.check().is_some()
+    });
+    assert!(notified);
+    assert_eq!(event.listener_count(), 0);
+
+    // The listener is removed when the closure unwinds, too.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    {
+        let result = std::panic::catch_unwind(|| {
+            event.with_listener(|_listener| panic!("unwinding"));
+        });
+        assert!(result.is_err());
+        assert_eq!(event.listener_count(), 0);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn listener_macro_many() {
+    use event_listener::listener;
+
+    let first = Event::<usize>::with_tag();
+    let second = Event::<bool>::with_tag();
+
+    listener!(first => one, second => two, first => three,);
+    assert_eq!(first.listener_count(), 2);
+    assert_eq!(second.listener_count(), 1);
+
+    first.notify(2.tag(7));
+    second.notify(1.tag(true));
+    assert_eq!(one.check(), Some(7));
+    assert_eq!(two.check(), Some(true));
+    assert_eq!(three.check(), Some(7));
+}
+
+#[test]
+fn wait_until_macro_async() {
+    use event_listener::wait_until;
+
+    let event = Event::new();
+    let mut checks = 0;
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = Box::pin(async {
+        wait_until!(async event, || {
+            checks += 1;
+            checks == 3
+        });
+        checks
+    });
+
+    // Checked before and after starting to listen.
+    assert!(wait.as_mut().poll(&mut cx).is_pending());
+    event.notify(1);
+    assert_eq!(wait.as_mut().poll(&mut cx), std::task::Poll::Ready(3));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_until_macro_blocking() {
+    use event_listener::wait_until;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let event = Arc::new(Event::new());
+
+    let handle = thread::spawn({
+        let flag = flag.clone();
+        let event = event.clone();
+        move || {
+            flag.store(true, Ordering::SeqCst);
+            event.notify(usize::MAX);
+        }
+    });
+
+    let is_set = || flag.load(Ordering::SeqCst);
+    wait_until!(&event, is_set);
+    handle.join().unwrap();
+
+    // The condition can borrow mutably and leave the function early.
+    fn pop(event: &Event, queue: &mut Vec<u8>) -> u8 {
+        wait_until!(event, || match queue.pop() {
+            Some(item) => return item,
+            None => false,
+        });
+        unreachable!()
+    }
+    assert_eq!(pop(&event, &mut vec![1, 2]), 2);
+}
+
+#[test]
+fn listen_and_check() {
+    use event_listener::{Checked, WakeOrder};
+
+    let event = Event::builder().wake_order(WakeOrder::Lifo).build();
+    let mut other = event.listen();
+
+    // The listener is registered before the condition is checked.
+    let mut listener = match event.listen_and_check(|| event.listener_count() == 1) {
+        Checked::Listening(listener) => listener,
+        Checked::Ready => panic!("the listener should be registered first"),
+    };
+    assert_eq!(event.listener_count(), 2);
+    drop(listener);
+
+    // A listener dropped because the condition holds passes its notification on.
+    let checked = event.listen_and_check(|| {
+        event.notify(1);
+        true
+    });
+    assert!(matches!(checked, Checked::Ready));
+    assert!(is_notified(&mut other));
+
+    listener = match event.listen_and_check(|| false) {
+        Checked::Listening(listener) => listener,
+        Checked::Ready => unreachable!(),
+    };
+    assert!(!is_notified(&mut listener));
+}
+
+#[test]
+fn notify_non_zero() {
+    use std::num::{NonZeroU32, NonZeroUsize};
+
+    let event = Event::new();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    assert_eq!(event.notify(NonZeroUsize::new(1).unwrap()), 1);
+    assert_eq!(event.notify(NonZeroU32::new(1).unwrap().additional()), 1);
+
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(!is_notified(&mut l3));
+}
+
+#[test]
+fn notify_bool() {
+    let event = Event::new();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    assert_eq!(event.notify(false), 0);
+    assert!(!is_notified(&mut l1));
+
+    assert_eq!(event.notify(true), 1);
+    assert_eq!(event.notify(true), 0);
+    assert_eq!(event.notify(true.additional()), 1);
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+}
+
+#[test]
+fn fence_with() {
+    use std::sync::atomic::Ordering;
+
+    let event = Event::new();
+    let mut listeners = (0..5).map(|_| event.listen()).collect::<Vec<_>>();
+
+    assert_eq!(event.notify(1.fence_with(Ordering::Release)), 1);
+    assert_eq!(
+        event.notify(1.fence_with(Ordering::Relaxed).additional()),
+        1
+    );
+    assert_eq!(event.notify(3.additional().fence_with(Ordering::SeqCst)), 3);
+
+    for listener in &mut listeners {
+        assert!(is_notified(listener));
+    }
+}
+
+#[test]
+fn dyn_notification() {
+    use event_listener::{DynNotification, Notification};
+
+    let event = Event::new();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    let boxed: DynNotification<'_> = Box::new(1.additional());
+    assert_eq!(event.notify(boxed), 1);
+
+    let mut inner = 1.additional().relaxed();
+    let borrowed: &mut dyn Notification<Tag = ()> = &mut inner;
+    assert_eq!(event.notify(borrowed), 1);
+
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(!is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dyn_notification_tags() {
+    use event_listener::DynNotification;
+
+    let event = Event::<usize>::with_tag();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    let mut next = 0;
+    let boxed: DynNotification<'_, usize> = Box::new(2.tag_with(move || {
+        next += 1;
+        next
+    }));
+    assert_eq!(event.notify(boxed), 2);
+    assert_eq!(l1.check(), Some(1));
+    assert_eq!(l2.check(), Some(2));
+}
+
+#[test]
+fn inspect() {
+    use std::cell::RefCell;
+
+    let event = Event::new();
+    let _l1 = event.listen();
+    let _l2 = event.listen();
+
+    let log = RefCell::new(Vec::new());
+    let count = event.notify(
+        1.inspect(
+            || log.borrow_mut().push(usize::MAX),
+            |n| log.borrow_mut().push(n),
+        )
+        .additional(),
+    );
+    assert_eq!(count, 1);
+    assert_eq!(*log.borrow(), [usize::MAX, 1]);
+
+    // Both hooks run even if nobody is notified.
+    log.borrow_mut().clear();
+    event.notify_count(1.inspect(
+        || log.borrow_mut().push(usize::MAX),
+        |n| log.borrow_mut().push(n),
+    ));
+    assert_eq!(*log.borrow(), [usize::MAX, 0]);
+
+    // A held back notification reports nothing, and the hooks don't run again on release.
+    log.borrow_mut().clear();
+    event.pause();
+    event.notify(1.additional().inspect(
+        || log.borrow_mut().push(usize::MAX),
+        |n| log.borrow_mut().push(n),
+    ));
+    assert_eq!(event.resume(), 1);
+    assert_eq!(*log.borrow(), [usize::MAX, 0]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn try_notify() {
+    let event = Event::<String>::with_tag();
+    assert_eq!(event.try_notify("lost".into()), Err("lost".into()));
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert_eq!(event.try_notify("first".into()), Ok(()));
+    assert_eq!(event.try_notify("second".into()), Ok(()));
+    assert_eq!(event.try_notify("third".into()), Err("third".into()));
+
+    assert_eq!(l1.check().as_deref(), Some("first"));
+    assert_eq!(l2.check().as_deref(), Some("second"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn try_notify_with_permit_capacity() {
+    let event = Event::builder()
+        .permit_capacity(4)
+        .build_with_tag::<Vec<u8>>();
+
+    // The tag is handed back instead of being stored as a permit.
+    assert_eq!(event.try_notify(vec![1]), Err(vec![1]));
+    let mut listener = event.listen();
+    assert_eq!(listener.check(), None);
+
+    assert_eq!(event.try_notify(vec![2]), Ok(()));
+    assert_eq!(listener.check(), Some(vec![2]));
+}
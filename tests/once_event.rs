@@ -0,0 +1,63 @@
+use std::future::Future;
+use std::task::Context;
+
+use event_listener::primitives::OnceEvent;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn notify_once() {
+    let event = OnceEvent::new();
+    assert!(!event.is_notified());
+
+    assert!(event.notify());
+    assert!(event.is_notified());
+    assert!(!event.notify());
+    assert!(event.is_notified());
+}
+
+#[test]
+fn wakes_current_and_future_waiters() {
+    let event = OnceEvent::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut w1 = Box::pin(event.wait());
+    let mut w2 = Box::pin(event.wait());
+    assert!(w1.as_mut().poll(&mut cx).is_pending());
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    event.notify();
+    assert!(w1.as_mut().poll(&mut cx).is_ready());
+    assert!(w2.as_mut().poll(&mut cx).is_ready());
+
+    let mut w3 = Box::pin(event.wait());
+    assert!(w3.as_mut().poll(&mut cx).is_ready());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_blocking_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(OnceEvent::new());
+    assert!(!event.wait_timeout(Duration::from_millis(10)));
+
+    let handles = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.wait_blocking())
+        })
+        .collect::<Vec<_>>();
+
+    thread::sleep(Duration::from_millis(50));
+    event.notify();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use event_listener::primitives::oneshot::{self, RecvError, TryRecvError};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn send_then_recv() {
+    let (tx, mut rx) = oneshot::channel();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    tx.send(String::from("hello")).unwrap();
+    assert_eq!(rx.try_recv().as_deref(), Ok("hello"));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn recv_wakes_on_send() {
+    let (tx, mut rx) = oneshot::channel();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(std::pin::Pin::new(&mut rx).poll(&mut cx).is_pending());
+    tx.send(5).unwrap();
+    assert_eq!(
+        std::pin::Pin::new(&mut rx).poll(&mut cx),
+        Poll::Ready(Ok(5))
+    );
+}
+
+#[test]
+fn sender_dropped() {
+    let (tx, mut rx) = oneshot::channel::<i32>();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(std::pin::Pin::new(&mut rx).poll(&mut cx).is_pending());
+    drop(tx);
+    assert_eq!(
+        std::pin::Pin::new(&mut rx).poll(&mut cx),
+        Poll::Ready(Err(RecvError))
+    );
+}
+
+#[test]
+fn receiver_dropped() {
+    let (tx, rx) = oneshot::channel();
+    assert!(!tx.is_closed());
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(vec![1, 2, 3]), Err(vec![1, 2, 3]));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn recv_blocking_across_threads() {
+    use std::thread;
+    use std::time::Duration;
+
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        tx.send(1u32).unwrap();
+    });
+
+    assert_eq!(rx.recv_blocking(), Ok(1));
+    handle.join().unwrap();
+}
@@ -0,0 +1,122 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::{race, select_events, Event, EventListener, Race};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll(race: &mut Pin<Box<Race<(), EventListener, EventListener>>>) -> Poll<()> {
+    let waker = waker_fn(|| ());
+    race.as_mut().poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn either_wins() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+
+    let mut listener = Box::pin(race(event1.listen(), event2.listen()));
+    assert_eq!(poll(&mut listener), Poll::Pending);
+    assert_eq!(event1.notify(1), 1);
+    assert_eq!(poll(&mut listener), Poll::Ready(()));
+
+    let mut listener = Box::pin(race(event1.listen(), event2.listen()));
+    assert_eq!(poll(&mut listener), Poll::Pending);
+    assert_eq!(event2.notify(1), 1);
+    assert_eq!(poll(&mut listener), Poll::Ready(()));
+}
+
+#[test]
+fn loser_is_removed() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+
+    let mut listener = Box::pin(race(event1.listen(), event2.listen()));
+    event1.notify(1);
+    assert_eq!(poll(&mut listener), Poll::Ready(()));
+
+    assert_eq!(event1.listener_count(), 0);
+    assert_eq!(event2.listener_count(), 0);
+}
+
+#[test]
+fn loser_passes_notification_on() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+
+    let mut listener = Box::pin(race(event1.listen(), event2.listen()));
+    let mut next = event2.listen();
+
+    event1.notify(1);
+    event2.notify(1);
+    assert_eq!(poll(&mut listener), Poll::Ready(()));
+
+    let waker = waker_fn(|| ());
+    assert!(Pin::new(&mut next)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready());
+}
+
+#[test]
+#[should_panic = "`Race` polled after completion"]
+fn poll_after_completion() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+
+    let mut listener = Box::pin(race(event1.listen(), event2.listen()));
+    event1.notify(1);
+    assert_eq!(poll(&mut listener), Poll::Ready(()));
+    let _ = poll(&mut listener);
+}
+
+#[test]
+fn select_first_notified() {
+    let events = [Event::new(), Event::new(), Event::new()];
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut select = select_events(&[&events[0], &events[1], &events[2]]);
+    assert_eq!(Pin::new(&mut select).poll(&mut cx), Poll::Pending);
+
+    assert_eq!(events[2].notify(1), 1);
+    assert_eq!(Pin::new(&mut select).poll(&mut cx), Poll::Ready((2, ())));
+
+    for event in &events {
+        assert_eq!(event.listener_count(), 0);
+    }
+}
+
+#[test]
+fn select_registers_before_poll() {
+    let events = [Event::new(), Event::new()];
+    let waker = waker_fn(|| ());
+
+    let mut select = select_events(&[&events[0], &events[1]]);
+    assert_eq!(events[0].listener_count(), 1);
+    assert_eq!(events[1].listener_count(), 1);
+
+    // Notified before the first poll.
+    assert_eq!(events[1].notify(1), 1);
+    assert_eq!(
+        Pin::new(&mut select).poll(&mut Context::from_waker(&waker)),
+        Poll::Ready((1, ()))
+    );
+}
+
+#[test]
+fn select_passes_notifications_on() {
+    let events = [Event::new(), Event::new()];
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut select = select_events(&[&events[0], &events[1]]);
+    let mut next = events[1].listen();
+
+    events[0].notify(1);
+    events[1].notify(1);
+    assert_eq!(Pin::new(&mut select).poll(&mut cx), Poll::Ready((0, ())));
+    assert!(Pin::new(&mut next).poll(&mut cx).is_ready());
+}
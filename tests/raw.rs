@@ -0,0 +1,67 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::{EventListenerRef, IntoNotification, RawEvent};
+use futures_lite::pin;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll(listener: Pin<&mut EventListenerRef<'_>>) -> Poll<()> {
+    let waker = waker_fn(|| ());
+    listener.poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn notify() {
+    let event = RawEvent::new();
+
+    let first = event.listen();
+    let second = event.listen();
+    pin!(first, second);
+    first.as_mut().listen();
+    second.as_mut().listen();
+    assert_eq!(event.listener_count(), 2);
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(event.notify(1), 0);
+    assert_eq!(event.notify(1.additional()), 1);
+
+    assert!(poll(first.as_mut()).is_ready());
+    assert!(poll(second.as_mut()).is_ready());
+}
+
+#[test]
+fn close() {
+    let event = RawEvent::new();
+
+    let listener = event.listen();
+    pin!(listener);
+    assert!(poll(listener.as_mut()).is_pending());
+
+    assert!(event.close());
+    assert!(!event.close());
+    assert!(event.is_closed());
+}
+
+#[test]
+fn embedded() {
+    struct Lock {
+        event: RawEvent,
+    }
+
+    let lock = Box::pin(Lock {
+        event: RawEvent::new(),
+    });
+
+    // The lock is pinned, so the event outlives the listener.
+    let listener: EventListenerRef<'static> = unsafe { lock.event.listen_unchecked() };
+    pin!(listener);
+    assert!(poll(listener.as_mut()).is_pending());
+    assert_eq!(lock.event.listener_count(), 1);
+
+    lock.event.notify(1);
+    assert!(poll(listener.as_mut()).is_ready());
+}
@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::task::Context;
+
+use event_listener::primitives::RwLock;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn try_read_and_write() {
+    let lock = RwLock::new(1);
+
+    let reader1 = lock.try_read().unwrap();
+    let reader2 = lock.try_read().unwrap();
+    assert!(lock.try_write().is_none());
+    drop((reader1, reader2));
+
+    let mut writer = lock.try_write().unwrap();
+    assert!(lock.try_read().is_none());
+    assert!(lock.try_write().is_none());
+    *writer += 1;
+    drop(writer);
+
+    assert_eq!(*lock.try_read().unwrap(), 2);
+    assert_eq!(lock.into_inner(), 2);
+}
+
+#[test]
+fn last_reader_wakes_writer() {
+    let lock = RwLock::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let reader1 = lock.try_read().unwrap();
+    let reader2 = lock.try_read().unwrap();
+
+    let mut write = Box::pin(lock.write());
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+
+    drop(reader1);
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+
+    drop(reader2);
+    assert!(write.as_mut().poll(&mut cx).is_ready());
+}
+
+#[test]
+fn writer_preferred() {
+    let lock = RwLock::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+
+    // A waiting writer keeps new readers out.
+    let mut write = Box::pin(lock.write());
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+    assert!(lock.try_read().is_none());
+
+    let mut read = Box::pin(lock.read());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(reader);
+    let writer = match write.as_mut().poll(&mut cx) {
+        std::task::Poll::Ready(writer) => writer,
+        std::task::Poll::Pending => panic!("the writer was woken up"),
+    };
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    // Releasing the write lock lets the reader in.
+    drop(writer);
+    assert!(read.as_mut().poll(&mut cx).is_ready());
+}
+
+#[test]
+fn cancelled_writer_lets_readers_in() {
+    let lock = RwLock::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+
+    let mut write = Box::pin(lock.write());
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+
+    let mut read = Box::pin(lock.read());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(write);
+    assert!(read.as_mut().poll(&mut cx).is_ready());
+    drop(reader);
+}
+
+#[test]
+fn debug() {
+    let lock = RwLock::new(5);
+    assert_eq!(format!("{:?}", lock), "RwLock { data: 5 }");
+
+    let reader = lock.try_read().unwrap();
+    assert_eq!(format!("{:?}", lock), "RwLock { data: 5 }");
+    drop(reader);
+
+    let writer = lock.try_write().unwrap();
+    assert_eq!(format!("{:?}", lock), "RwLock { data: <locked> }");
+    assert_eq!(format!("{:?}", writer), "5");
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn blocking() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let lock = Arc::new(RwLock::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    if i % 2 == 0 {
+                        *lock.write_blocking() += 1;
+                    } else {
+                        assert!(*lock.read_blocking() <= 400);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*lock.read_blocking(), 400);
+}
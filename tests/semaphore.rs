@@ -0,0 +1,120 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::primitives::Semaphore;
+use futures_lite::future;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn try_acquire() {
+    let s = Semaphore::new(2);
+
+    let p1 = s.try_acquire().unwrap();
+    let p2 = s.try_acquire().unwrap();
+    assert!(s.try_acquire().is_none());
+    assert_eq!(s.available_permits(), 0);
+
+    drop(p1);
+    assert_eq!(s.available_permits(), 1);
+    drop(p2);
+    assert_eq!(s.available_permits(), 2);
+}
+
+#[test]
+fn acquire_wakes_on_release() {
+    let s = Semaphore::new(1);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let permit = future::block_on(s.acquire());
+
+    let mut a1 = Box::pin(s.acquire());
+    let mut a2 = Box::pin(s.acquire());
+    assert!(a1.as_mut().poll(&mut cx).is_pending());
+    assert!(a2.as_mut().poll(&mut cx).is_pending());
+
+    drop(permit);
+    let permit = match a1.as_mut().poll(&mut cx) {
+        std::task::Poll::Ready(permit) => permit,
+        std::task::Poll::Pending => panic!("first waiter was not woken"),
+    };
+    assert!(a2.as_mut().poll(&mut cx).is_pending());
+
+    drop(permit);
+    assert!(a2.as_mut().poll(&mut cx).is_ready());
+}
+
+#[test]
+fn add_permits_wakes_multiple() {
+    let s = Semaphore::new(0);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut a1: Pin<Box<dyn Future<Output = _>>> = Box::pin(s.acquire());
+    let mut a2: Pin<Box<dyn Future<Output = _>>> = Box::pin(s.acquire());
+    assert!(a1.as_mut().poll(&mut cx).is_pending());
+    assert!(a2.as_mut().poll(&mut cx).is_pending());
+
+    s.add_permits(2);
+    assert!(a1.as_mut().poll(&mut cx).is_ready());
+    assert!(a2.as_mut().poll(&mut cx).is_ready());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn acquire_blocking_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    let s = Arc::new(Semaphore::new(2));
+    let active = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..8)
+        .map(|_| {
+            let s = s.clone();
+            let active = active.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let _permit = s.acquire_blocking();
+                    assert!(active.fetch_add(1, Ordering::SeqCst) < 2);
+                    active.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(s.available_permits(), 2);
+}
+
+#[test]
+#[should_panic = "a semaphore may not have more than MAX_PERMITS permits"]
+fn add_permits_overflow() {
+    let s = Semaphore::new(Semaphore::MAX_PERMITS);
+    s.add_permits(1);
+}
+
+#[test]
+fn release_permit_at_max_permits() {
+    let s = Semaphore::new(1);
+    let permit = s.try_acquire().unwrap();
+
+    // The outstanding permit can still be returned.
+    s.add_permits(Semaphore::MAX_PERMITS);
+    drop(permit);
+    assert_eq!(s.available_permits(), Semaphore::MAX_PERMITS + 1);
+}
+
+#[test]
+#[should_panic = "a semaphore may not have more than MAX_PERMITS permits"]
+fn new_too_many_permits() {
+    Semaphore::new(Semaphore::MAX_PERMITS + 1);
+}
@@ -0,0 +1,127 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{IntoNotification, ShardedEvent, ShardedListener};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified(listener: &mut ShardedListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn notify_across_shards() {
+    let event = ShardedEvent::new(3);
+    let mut listeners: Vec<_> = (0..6).map(|_| event.listen()).collect();
+    assert_eq!(event.total_listeners(), 6);
+
+    // Already notified listeners count, no matter their shard.
+    assert_eq!(event.notify(4), 4);
+    assert_eq!(event.notify(4), 0);
+    assert_eq!(event.notify(5), 1);
+
+    let notified = listeners.iter_mut().map(is_notified).filter(|&n| n).count();
+    assert_eq!(notified, 5);
+}
+
+#[test]
+fn notify_additional() {
+    let event = ShardedEvent::new(4);
+    let mut listeners: Vec<_> = (0..6).map(|_| event.listen()).collect();
+
+    assert_eq!(event.notify(2.additional()), 2);
+    assert_eq!(event.notify(2.additional()), 2);
+    assert_eq!(event.notify(5.additional()), 2);
+
+    assert!(listeners.iter_mut().all(is_notified));
+}
+
+#[test]
+fn drop_passes_notification_to_other_shard() {
+    let event = ShardedEvent::new(2);
+    let first = event.listen();
+    let mut second = event.listen();
+
+    // The two listeners are in different shards.
+    assert_eq!(event.notify(1), 1);
+    drop(first);
+
+    assert!(is_notified(&mut second));
+}
+
+#[test]
+fn no_listeners() {
+    let event = ShardedEvent::new(2);
+    assert_eq!(event.notify(usize::MAX), 0);
+    assert_eq!(event.total_listeners(), 0);
+
+    // A sticky notification waits for the next listener.
+    event.notify(1.sticky());
+    let mut listener = event.listen();
+    assert!(is_notified(&mut listener));
+}
+
+#[test]
+#[should_panic = "at least one shard"]
+fn zero_shards() {
+    let _ = ShardedEvent::new(0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tags() {
+    use event_listener::Listener;
+
+    let event = ShardedEvent::<usize>::with_tag(2);
+    let first = event.listen();
+    let second = event.listen();
+
+    let mut next = 0;
+    assert_eq!(
+        event.notify(2.tag_with(|| {
+            next += 1;
+            next
+        })),
+        2
+    );
+
+    let mut tags = [first.wait(), second.wait()];
+    tags.sort_unstable();
+    assert_eq!(tags, [1, 2]);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn concurrent_notify() {
+    use event_listener::Listener;
+    use std::sync::Arc;
+    use std::thread;
+
+    let event = Arc::new(ShardedEvent::new(4));
+    let listeners: Vec<_> = (0..64).map(|_| event.listen()).collect();
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || {
+                for _ in 0..8 {
+                    event.notify(1.additional());
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    for listener in listeners {
+        listener.wait();
+    }
+}
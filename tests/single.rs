@@ -0,0 +1,152 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use event_listener::{CapacityError, IntoNotification, SingleEvent, SingleListener};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll(listener: &mut SingleListener<'_>) -> Poll<()> {
+    let waker = waker_fn(|| ());
+    Pin::new(listener).poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn notify() {
+    let event = SingleEvent::new();
+
+    // Nobody is listening.
+    assert_eq!(event.notify(1), 0);
+
+    let mut listener = event.listen().unwrap();
+    assert!(poll(&mut listener).is_pending());
+    assert_eq!(event.notify(0), 0);
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(event.notify(1.additional()), 0);
+    assert!(event.is_notified());
+
+    assert!(poll(&mut listener).is_ready());
+    assert!(!event.is_notified());
+    assert_eq!(event.listener_count(), 0);
+}
+
+#[test]
+fn capacity() {
+    let event = SingleEvent::new();
+
+    let listener = event.listen().unwrap();
+    assert_eq!(event.listen().unwrap_err(), CapacityError);
+    assert_eq!(event.listener_count(), 1);
+
+    drop(listener);
+    assert_eq!(event.listener_count(), 0);
+    let _listener = event.listen().unwrap();
+}
+
+#[test]
+fn drop_discards() {
+    let event = SingleEvent::new();
+
+    let listener = event.listen().unwrap();
+    event.notify(1);
+    drop(listener);
+
+    // The notification is gone with the listener.
+    let mut listener = event.listen().unwrap();
+    assert!(poll(&mut listener).is_pending());
+    assert!(!listener.discard());
+}
+
+#[test]
+fn sticky() {
+    let event = SingleEvent::new();
+
+    assert_eq!(event.notify(1.sticky()), 0);
+    assert_eq!(event.notify(1.sticky()), 0);
+    assert!(event.is_notified());
+
+    let mut listener = event.listen().unwrap();
+    assert!(poll(&mut listener).is_ready());
+
+    // Only one permit is kept.
+    let listener = event.listen().unwrap();
+    assert!(!listener.discard());
+}
+
+#[test]
+fn wakes_task() {
+    let event = SingleEvent::new();
+    let mut listener = event.listen().unwrap();
+
+    let woken = Arc::new(AtomicUsize::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let mut cx = Context::from_waker(&waker);
+
+    // Polling twice keeps a single registration.
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_ready());
+}
+
+// `SingleEvent::new()` is only `const` without loom.
+#[cfg(not(loom))]
+#[test]
+fn in_static() {
+    static EVENT: SingleEvent = SingleEvent::new();
+
+    let listener = EVENT.listen().unwrap();
+    assert!(listener.listens_to(&EVENT));
+    assert!(!listener.listens_to(&SingleEvent::new()));
+
+    EVENT.notify(1);
+    futures_lite::future::block_on(listener);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_blocking() {
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Arc::new(SingleEvent::new());
+
+    for _ in 0..100 {
+        let listener = event.listen().unwrap();
+        let handle = thread::spawn({
+            let event = event.clone();
+            move || event.notify(1)
+        });
+        listener.wait();
+        handle.join().unwrap();
+    }
+
+    let listener = event.listen().unwrap();
+    assert!(!listener.wait_timeout(Duration::from_millis(10)));
+    assert_eq!(event.listener_count(), 0);
+}
+
+#[test]
+fn inspect() {
+    let event = SingleEvent::new();
+    let _listener = event.listen().unwrap();
+
+    let count = std::cell::Cell::new(usize::MAX);
+    assert_eq!(event.notify(1.inspect(|| (), |n| count.set(n))), 1);
+    assert_eq!(count.get(), 1);
+
+    // The hooks run even if the listener is notified already.
+    assert_eq!(event.notify(1.inspect(|| (), |n| count.set(n))), 0);
+    assert_eq!(count.get(), 0);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "snapshot")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::{Event, EventListener, ListenerSnapshot};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified(listener: &mut EventListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn uninitialized() {
+    let event = Event::<()>::new();
+    let snapshot = event.snapshot();
+
+    assert!(snapshot.listeners().is_empty());
+    assert_eq!(snapshot.notified(), 0);
+    assert_eq!(snapshot.queued(), 0);
+}
+
+#[test]
+fn listener_states() {
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+    assert!(!is_notified(&mut l1));
+
+    event.notify(1);
+    event.notify_additional(1);
+
+    let snapshot = event.snapshot();
+    assert_eq!(
+        snapshot.listeners(),
+        &[
+            ListenerSnapshot::Notified { additional: false },
+            ListenerSnapshot::Notified { additional: true },
+            ListenerSnapshot::Created,
+        ]
+    );
+    assert_eq!(snapshot.notified(), 2);
+
+    drop((l1, l2, l3));
+    assert!(event.snapshot().listeners().is_empty());
+    assert_eq!(event.snapshot().notified(), 0);
+}
+
+#[test]
+fn task_waiting() {
+    let event = Event::new();
+
+    let mut listener = event.listen();
+    assert!(!is_notified(&mut listener));
+    assert_eq!(event.snapshot().listeners(), &[ListenerSnapshot::Task]);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn thread_waiting() {
+    use event_listener::Listener;
+    use std::sync::Arc;
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+    let listener = event.listen();
+
+    let waiter = thread::spawn(move || listener.wait());
+
+    while event.snapshot().listeners() != [ListenerSnapshot::Thread] {
+        thread::yield_now();
+    }
+
+    event.notify(1);
+    waiter.join().unwrap();
+}
@@ -0,0 +1,128 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use event_listener::{IntoNotification, StaticEvent, StaticListener};
+use futures_lite::pin;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll<T>(listener: Pin<&mut StaticListener<'_, T>>) -> Poll<T> {
+    let waker = waker_fn(|| ());
+    listener.poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn notify() {
+    let event = StaticEvent::new();
+
+    let l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+    pin!(l1, l2, l3);
+    l1.as_mut().listen();
+    l2.as_mut().listen();
+    l3.as_mut().listen();
+
+    assert!(poll(l1.as_mut()).is_pending());
+    assert_eq!(event.notify(2), 2);
+    assert_eq!(event.notify(1), 0);
+
+    assert!(poll(l1.as_mut()).is_ready());
+    assert!(poll(l2.as_mut()).is_ready());
+    assert!(poll(l3.as_mut()).is_pending());
+
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(poll(l3.as_mut()).is_ready());
+    assert_eq!(event.listener_count(), 0);
+}
+
+#[test]
+fn listens_on_poll() {
+    let event = StaticEvent::new();
+
+    let listener = event.listen();
+    pin!(listener);
+
+    // A notification before listening is lost.
+    event.notify(1);
+    assert!(poll(listener.as_mut()).is_pending());
+    assert!(listener.is_listening());
+
+    event.notify(1);
+    assert!(poll(listener.as_mut()).is_ready());
+    assert!(!listener.is_listening());
+}
+
+#[test]
+fn drop_propagates() {
+    let event = StaticEvent::new();
+
+    let l2 = event.listen();
+    pin!(l2);
+
+    {
+        let l1 = event.listen();
+        pin!(l1);
+        l1.as_mut().listen();
+        l2.as_mut().listen();
+
+        event.notify(1);
+    }
+
+    assert!(poll(l2.as_mut()).is_ready());
+}
+
+#[test]
+fn wakes_task() {
+    let event = StaticEvent::new();
+    let woken = Arc::new(AtomicUsize::new(0));
+    let waker = waker_fn({
+        let woken = woken.clone();
+        move || {
+            woken.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let listener = event.listen();
+    pin!(listener);
+    assert!(listener
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    event.notify(1);
+    assert_eq!(woken.load(Ordering::SeqCst), 1);
+    assert!(poll(listener.as_mut()).is_ready());
+}
+
+// `StaticEvent::new()` is only `const` without loom.
+#[cfg(not(loom))]
+#[test]
+fn sticky() {
+    static EVENT: StaticEvent = StaticEvent::new();
+
+    EVENT.notify(1.sticky());
+
+    let listener = EVENT.listen();
+    pin!(listener);
+    assert!(poll(listener.as_mut()).is_ready());
+}
+
+#[test]
+fn inspect() {
+    let event = StaticEvent::new();
+    let listener1 = event.listen();
+    let listener2 = event.listen();
+    pin!(listener1, listener2);
+    listener1.as_mut().listen();
+    listener2.as_mut().listen();
+
+    let count = std::cell::Cell::new(usize::MAX);
+    assert_eq!(event.notify(5.inspect(|| (), |n| count.set(n))), 2);
+    assert_eq!(count.get(), 2);
+}
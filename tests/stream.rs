@@ -0,0 +1,80 @@
+#![cfg(feature = "stream")]
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(feature = "std")]
+use event_listener::IntoNotification;
+use event_listener::{Event, EventStream};
+use futures_lite::stream::Stream;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll_next<T>(stream: &mut EventStream<T>) -> Poll<Option<T>> {
+    let waker = waker_fn(|| ());
+    Pin::new(stream).poll_next(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn yields_every_notification() {
+    let event = Event::new();
+    let mut stream = event.stream();
+
+    assert_eq!(poll_next(&mut stream), Poll::Pending);
+
+    for _ in 0..3 {
+        assert_eq!(event.notify(1), 1);
+        assert_eq!(poll_next(&mut stream), Poll::Ready(Some(())));
+        assert_eq!(poll_next(&mut stream), Poll::Pending);
+    }
+}
+
+#[test]
+fn registered_before_first_poll() {
+    let event = Event::new();
+    let mut stream = event.stream();
+
+    // Notified before it is ever polled.
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(poll_next(&mut stream), Poll::Ready(Some(())));
+}
+
+#[test]
+fn notified_again_before_poll() {
+    let event = Event::new();
+    let mut stream = event.stream();
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(poll_next(&mut stream), Poll::Ready(Some(())));
+
+    // The listener is back in the list right after yielding, so this is not lost.
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(poll_next(&mut stream), Poll::Ready(Some(())));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rearmed_listener_moves_to_back() {
+    let event = Event::with_tag();
+    let mut stream = event.stream();
+    let _listener = event.listen();
+
+    assert_eq!(event.notify(1.tag(1)), 1);
+    assert_eq!(poll_next(&mut stream), Poll::Ready(Some(1)));
+
+    // The other listener is now first in line.
+    assert_eq!(event.notify(1.tag(2)), 1);
+    assert_eq!(poll_next(&mut stream), Poll::Pending);
+}
+
+#[test]
+fn ends_on_close() {
+    let event = Event::new();
+    let mut stream = event.stream();
+
+    event.close();
+    assert_eq!(poll_next(&mut stream), Poll::Ready(None));
+    assert_eq!(poll_next(&mut stream), Poll::Ready(None));
+}
@@ -0,0 +1,46 @@
+#![cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+
+use std::time::{Duration, Instant};
+
+use event_listener::{Listener, ThrottledEvent};
+
+#[test]
+fn throttled() {
+    let event = ThrottledEvent::new(Duration::from_millis(50));
+
+    let listener = event.listen();
+    let start = Instant::now();
+    assert_eq!(event.notify(1), 1);
+    listener.wait();
+
+    // A burst within the interval is merged into a single delayed notification.
+    let listeners: Vec<_> = (0..3).map(|_| event.listen()).collect();
+    assert_eq!(event.notify(1), 0);
+    assert_eq!(event.notify(2), 0);
+    assert_eq!(event.notify(1), 0);
+    assert_eq!(event.as_event().total_listeners(), 3);
+
+    let mut listeners = listeners.into_iter();
+    listeners.next().unwrap().wait();
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    listeners.next().unwrap().wait();
+
+    let last = listeners.next().unwrap();
+    assert!(last.try_wait_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn after_interval() {
+    let event = ThrottledEvent::new(Duration::from_millis(10));
+
+    let listener = event.listen();
+    assert_eq!(event.notify(1), 1);
+    listener.wait();
+
+    std::thread::sleep(Duration::from_millis(20));
+
+    // The interval has passed, so this is delivered right away.
+    let listener = event.listen();
+    assert_eq!(event.notify(1), 1);
+    listener.wait();
+}
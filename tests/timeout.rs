@@ -0,0 +1,66 @@
+#![cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use event_listener::Event;
+use futures_lite::future::block_on;
+
+#[test]
+fn times_out() {
+    let event = Event::new();
+
+    let start = Instant::now();
+    let listener = event.listen();
+    assert_eq!(block_on(listener.timeout(Duration::from_millis(50))), None);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // A deadline in the past times out right away.
+    let listener = event.listen();
+    assert_eq!(block_on(listener.deadline(start)), None);
+}
+
+#[test]
+fn notified_before_deadline() {
+    let event = Arc::new(Event::new());
+    let listener = event.listen();
+
+    let notifier = thread::spawn({
+        let event = event.clone();
+        move || {
+            thread::sleep(Duration::from_millis(10));
+            event.notify(1);
+        }
+    });
+
+    assert_eq!(
+        block_on(listener.timeout(Duration::from_secs(60))),
+        Some(())
+    );
+    notifier.join().unwrap();
+}
+
+#[test]
+fn many_timeouts() {
+    let event = Event::new();
+
+    let timeouts = (1..=5)
+        .map(|i| event.listen().timeout(Duration::from_millis(10 * i)))
+        .collect::<Vec<_>>();
+
+    for timeout in timeouts {
+        assert_eq!(block_on(timeout), None);
+    }
+}
+
+#[test]
+fn drop_removes_listener() {
+    let event = Event::new();
+
+    let timeout = event.listen().timeout(Duration::from_secs(60));
+    assert_eq!(event.listener_count(), 1);
+
+    drop(timeout);
+    assert_eq!(event.listener_count(), 0);
+}
@@ -0,0 +1,83 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::{wait_all, Event};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn completes_after_all() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut all = wait_all(vec![event1.listen(), event2.listen(), event1.listen()]);
+    assert_eq!(Pin::new(&mut all).poll(&mut cx), Poll::Pending);
+
+    event1.notify(2);
+    assert_eq!(Pin::new(&mut all).poll(&mut cx), Poll::Pending);
+
+    // Listeners that were already notified are not registered anymore.
+    assert_eq!(event1.listener_count(), 0);
+
+    event2.notify(1);
+    assert_eq!(
+        Pin::new(&mut all).poll(&mut cx),
+        Poll::Ready(vec![(), (), ()])
+    );
+}
+
+#[test]
+fn empty() {
+    let waker = waker_fn(|| ());
+    let mut all = wait_all(Vec::<event_listener::EventListener>::new());
+
+    assert_eq!(
+        Pin::new(&mut all).poll(&mut Context::from_waker(&waker)),
+        Poll::Ready(vec![])
+    );
+}
+
+#[test]
+fn drop_unfinished() {
+    let event1 = Event::new();
+    let event2 = Event::new();
+    let waker = waker_fn(|| ());
+
+    let mut all = wait_all(vec![event1.listen(), event2.listen()]);
+    event1.notify(1);
+    assert_eq!(
+        Pin::new(&mut all).poll(&mut Context::from_waker(&waker)),
+        Poll::Pending
+    );
+
+    drop(all);
+    assert_eq!(event2.listener_count(), 0);
+}
+
+#[test]
+fn wait_many() {
+    let event = Event::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut many = event.wait_many(3);
+
+    // Completions are counted one by one.
+    event.notify_additional(1);
+    event.notify_additional(1);
+    assert_eq!(Pin::new(&mut many).poll(&mut cx), Poll::Pending);
+
+    event.notify_additional(1);
+    assert_eq!(Pin::new(&mut many).poll(&mut cx), Poll::Ready(vec![(); 3]));
+
+    // Nothing to wait for.
+    assert_eq!(
+        Pin::new(&mut event.wait_many(0)).poll(&mut cx),
+        Poll::Ready(vec![])
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use event_listener::primitives::watch;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn versions() {
+    let value = watch::Value::new(0);
+    assert_eq!(value.version(), 0);
+
+    value.set(1);
+    assert_eq!(value.version(), 1);
+    assert_eq!(value.replace(2), 1);
+    assert_eq!(value.version(), 2);
+    value.modify(|v| *v += 1);
+    assert_eq!(value.version(), 3);
+    assert_eq!(*value.borrow(), 3);
+}
+
+#[test]
+fn changed_wakes_on_replace() {
+    let value = watch::Value::new("a");
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let seen = value.version();
+    let mut w1 = Box::pin(value.changed(seen));
+    let mut w2 = Box::pin(value.changed(seen));
+    assert!(w1.as_mut().poll(&mut cx).is_pending());
+    assert!(w2.as_mut().poll(&mut cx).is_pending());
+
+    value.set("b");
+    assert_eq!(w1.as_mut().poll(&mut cx), Poll::Ready(1));
+    assert_eq!(w2.as_mut().poll(&mut cx), Poll::Ready(1));
+    assert_eq!(*value.borrow(), "b");
+
+    // Waiting from the new version needs another change.
+    let mut w3 = Box::pin(value.changed(1));
+    assert!(w3.as_mut().poll(&mut cx).is_pending());
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn changed_blocking_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let value = Arc::new(watch::Value::new(0u32));
+    let handle = thread::spawn({
+        let value = value.clone();
+        move || {
+            let mut seen = 0;
+            while *value.borrow() < 3 {
+                seen = value.changed_blocking(seen);
+            }
+        }
+    });
+
+    for i in 1..=3 {
+        thread::sleep(Duration::from_millis(10));
+        value.set(i);
+    }
+
+    handle.join().unwrap();
+}